@@ -1,15 +1,37 @@
-use crate::font::{FontScanner, FontState, GlyphCheckResult, check_glyphs};
+use crate::font::{
+    BitmapGlyphResult, ColorPalette, FamilyGrouping, FontCache, FontMatch, FontQuery, FontScanner, FontState,
+    FontTables, GlyphCheckResult, LayoutFeatures, LintWarning, NameRecord, StylesheetAudit, SubsetOptions,
+    SubsetReport, audit_stylesheet, bitmap_glyphs, check_glyphs, collect_lint_warnings, default_subset_path,
+    generate_font_face_css, inspect_tables, match_font, read_layout_features, read_name_table, read_palettes,
+    subset_font_file,
+};
+use std::collections::BTreeMap;
+use tauri::State;
 
 #[tauri::command]
-pub async fn scan_fonts() -> Result<FontState, String> {
+pub async fn scan_fonts(cache: State<'_, FontCache>) -> Result<FontState, String> {
     let scanner = FontScanner::new();
-    scanner.scan_all_fonts()
+    // Keep the family grouping picked before the rescan
+    let state = scanner.scan_all_fonts_grouped(cache.grouping())?;
+    cache.set(state.clone());
+    Ok(state)
 }
 
 #[tauri::command]
-pub async fn refresh_fonts() -> Result<FontState, String> {
+pub async fn refresh_fonts(cache: State<'_, FontCache>) -> Result<FontState, String> {
     // Same as scan_fonts for now, can add caching later
-    scan_fonts().await
+    scan_fonts(cache).await
+}
+
+#[tauri::command]
+pub async fn set_family_grouping(
+    grouping: FamilyGrouping,
+    cache: State<'_, FontCache>,
+) -> Result<FontState, String> {
+    cache.update(|state| {
+        state.regroup(grouping);
+        state.clone()
+    })
 }
 
 #[tauri::command]
@@ -22,4 +44,82 @@ pub async fn toggle_font(font_id: String, enable: bool) -> Result<(), String> {
 #[tauri::command]
 pub async fn check_glyphs_in_font(font_path: String, text: String) -> Result<Vec<GlyphCheckResult>, String> {
     check_glyphs(font_path, text)
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn get_name_table(font_id: String, cache: State<'_, FontCache>) -> Result<Vec<NameRecord>, String> {
+    let font = cache.get_font(&font_id)?;
+    read_name_table(font.path, font.face_index)
+}
+
+#[tauri::command]
+pub async fn inspect_font_tables(font_id: String, cache: State<'_, FontCache>) -> Result<FontTables, String> {
+    let font = cache.get_font(&font_id)?;
+    inspect_tables(font.path, font.face_index)
+}
+
+#[tauri::command]
+pub async fn lint_fonts(cache: State<'_, FontCache>) -> Result<BTreeMap<String, Vec<LintWarning>>, String> {
+    cache.read(collect_lint_warnings)
+}
+
+#[tauri::command]
+pub async fn get_bitmap_glyphs(
+    font_id: String,
+    text: String,
+    ppem: u16,
+    cache: State<'_, FontCache>,
+) -> Result<Vec<BitmapGlyphResult>, String> {
+    let font = cache.get_font(&font_id)?;
+    bitmap_glyphs(font.path, font.face_index, text, ppem)
+}
+
+#[tauri::command]
+pub async fn get_color_palettes(font_id: String, cache: State<'_, FontCache>) -> Result<Vec<ColorPalette>, String> {
+    let font = cache.get_font(&font_id)?;
+    read_palettes(font.path, font.face_index)
+}
+
+#[tauri::command]
+pub async fn get_layout_features(font_id: String, cache: State<'_, FontCache>) -> Result<LayoutFeatures, String> {
+    let font = cache.get_font(&font_id)?;
+    read_layout_features(font.path, font.face_index)
+}
+
+#[tauri::command]
+pub async fn match_font_face(
+    family: String,
+    weight: String,
+    style: String,
+    stretch: String,
+    cache: State<'_, FontCache>,
+) -> Result<Option<FontMatch>, String> {
+    let query = FontQuery::parse(&family, &weight, &style, &stretch)?;
+    cache.read(|state| match_font(state, &query))
+}
+
+#[tauri::command]
+pub async fn audit_stylesheet_fonts(
+    path: String,
+    sample: String,
+    cache: State<'_, FontCache>,
+) -> Result<StylesheetAudit, String> {
+    cache.read(|state| audit_stylesheet(state, &path, &sample))?
+}
+
+#[tauri::command]
+pub async fn get_font_face_css(family: String, cache: State<'_, FontCache>) -> Result<String, String> {
+    cache.read(|state| generate_font_face_css(state, &family))?
+}
+
+#[tauri::command]
+pub async fn subset_font(
+    font_id: String,
+    options: SubsetOptions,
+    output_path: Option<String>,
+    cache: State<'_, FontCache>,
+) -> Result<SubsetReport, String> {
+    let font = cache.get_font(&font_id)?;
+    let output_path = output_path.filter(|path| !path.trim().is_empty()).unwrap_or_else(|| default_subset_path(&font));
+    subset_font_file(&font.path, font.face_index, &options, &output_path)
+}
@@ -0,0 +1,258 @@
+//! Embedded bitmap strikes (EBLC/EBDT, CBLC/CBDT, Apple bloc/bdat, sbix)
+use super::raw::{self, read_u16, read_u32};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use ttf_parser::{Face, GlyphId, RasterImageFormat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BitmapSource {
+    Eblc, // EBLC/EBDT 单色/灰度位图
+    Cblc, // CBLC/CBDT 彩色位图
+    Bloc, // Apple bloc/bdat
+    Sbix, // Apple sbix (PNG/JPEG/TIFF)
+}
+
+/// A bitmap strike: the glyph images for one pixel size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitmapStrike {
+    pub source: BitmapSource,
+    pub ppem_x: u16,
+    pub ppem_y: u16,
+    pub bit_depth: Option<u8>, // EBLC/CBLC: 1/2/4/8/32，sbix 无此字段
+    pub first_glyph: Option<u16>,
+    pub last_glyph: Option<u16>,
+    pub formats: Vec<String>, // EBLC/CBLC imageFormat 编号 (如 "1", "17")；sbix 图形类型 (如 "png")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BitmapImageFormat {
+    Png,  // 原始 PNG 数据
+    Gray, // 每像素 1 字节覆盖度，255 为墨色
+    Rgba, // 每像素 4 字节，非预乘 RGBA
+}
+
+/// A decoded bitmap glyph image
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitmapGlyph {
+    pub strike_ppem: u16, // 实际使用的 strike 尺寸
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub format: BitmapImageFormat,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitmapGlyphResult {
+    pub glyph: char,
+    pub bitmap: Option<BitmapGlyph>, // 该 ppem 下没有位图时为 None (使用轮廓渲染)
+}
+
+/// List every bitmap strike of a face
+pub fn extract_bitmap_strikes(face: &Face) -> Vec<BitmapStrike> {
+    let mut strikes = Vec::new();
+    for (source, tag) in [
+        (BitmapSource::Eblc, b"EBLC"),
+        (BitmapSource::Cblc, b"CBLC"),
+        (BitmapSource::Bloc, b"bloc"),
+    ] {
+        if let Some(data) = raw::table(face, tag) {
+            strikes.extend(parse_location_table(data, source));
+        }
+    }
+    if let Some(data) = raw::table(face, b"sbix") {
+        strikes.extend(parse_sbix(data, face.number_of_glyphs()));
+    }
+    strikes
+}
+
+/// Bitmap glyphs for each character of `text` at `ppem`
+pub fn bitmap_glyphs(path: String, face_index: u32, text: String, ppem: u16) -> Result<Vec<BitmapGlyphResult>, String> {
+    raw::with_face(&path, face_index, |face| {
+        text.chars()
+            .map(|glyph| BitmapGlyphResult {
+                glyph,
+                bitmap: face.glyph_index(glyph).and_then(|id| bitmap_glyph(face, id, ppem)),
+            })
+            .collect()
+    })
+}
+
+/// Rasterizers only use monochrome/grayscale strikes at their exact size, while color
+/// bitmaps (PNG, BGRA) are scaled from the nearest strike
+pub fn bitmap_glyph(face: &Face, glyph_id: GlyphId, ppem: u16) -> Option<BitmapGlyph> {
+    let image = face.glyph_raster_image(glyph_id, ppem)?;
+    let (format, data) = match image.format {
+        RasterImageFormat::PNG => (BitmapImageFormat::Png, image.data.to_vec()),
+        RasterImageFormat::BitmapPremulBgra32 => (BitmapImageFormat::Rgba, unpremultiply_bgra(image.data)),
+        format => {
+            if image.pixels_per_em != ppem {
+                return None;
+            }
+            let (bits, padded) = match format {
+                RasterImageFormat::BitmapMono => (1, true),
+                RasterImageFormat::BitmapMonoPacked => (1, false),
+                RasterImageFormat::BitmapGray2 => (2, true),
+                RasterImageFormat::BitmapGray2Packed => (2, false),
+                RasterImageFormat::BitmapGray4 => (4, true),
+                RasterImageFormat::BitmapGray4Packed => (4, false),
+                _ => (8, true),
+            };
+            let data = unpack_gray(image.data, image.width as usize, image.height as usize, bits, padded)?;
+            (BitmapImageFormat::Gray, data)
+        }
+    };
+
+    Some(BitmapGlyph {
+        strike_ppem: image.pixels_per_em,
+        x: image.x,
+        y: image.y,
+        width: image.width,
+        height: image.height,
+        format,
+        data,
+    })
+}
+
+/// EBLC, CBLC and bloc share the same layout: a header followed by 48-byte BitmapSize records
+fn parse_location_table(data: &[u8], source: BitmapSource) -> Vec<BitmapStrike> {
+    let num_sizes = read_u32(data, 4).unwrap_or(0) as usize;
+    let mut strikes = Vec::new();
+
+    for i in 0..num_sizes {
+        let record = 8 + i * 48;
+        let (Some(array_offset), Some(num_subtables)) = (read_u32(data, record), read_u32(data, record + 8)) else {
+            break;
+        };
+        let (Some(&ppem_x), Some(&ppem_y), Some(&bit_depth)) =
+            (data.get(record + 44), data.get(record + 45), data.get(record + 46))
+        else {
+            break;
+        };
+
+        // IndexSubTableArray entries: firstGlyph, lastGlyph, additionalOffsetToIndexSubtable
+        let mut formats = BTreeSet::new();
+        for j in 0..num_subtables as usize {
+            let entry = array_offset as usize + j * 8;
+            let image_format = read_u32(data, entry + 4)
+                .and_then(|offset| read_u16(data, array_offset as usize + offset as usize + 2));
+            if let Some(image_format) = image_format {
+                formats.insert(image_format);
+            }
+        }
+
+        strikes.push(BitmapStrike {
+            source,
+            ppem_x: ppem_x as u16,
+            ppem_y: ppem_y as u16,
+            bit_depth: Some(bit_depth),
+            first_glyph: read_u16(data, record + 40),
+            last_glyph: read_u16(data, record + 42),
+            formats: formats.into_iter().map(|f| f.to_string()).collect(),
+        });
+    }
+    strikes
+}
+
+/// sbix: strike offsets, each strike has ppem, ppi and per-glyph data offsets
+fn parse_sbix(data: &[u8], num_glyphs: u16) -> Vec<BitmapStrike> {
+    let num_strikes = read_u32(data, 4).unwrap_or(0) as usize;
+    let mut strikes = Vec::new();
+
+    for i in 0..num_strikes {
+        let Some(strike) = read_u32(data, 8 + i * 4).map(|offset| offset as usize) else {
+            break;
+        };
+        let Some(ppem) = read_u16(data, strike) else {
+            break;
+        };
+
+        // Glyph data: originOffsetX, originOffsetY, graphicType, data; empty glyphs have equal offsets
+        let mut formats = BTreeSet::new();
+        let (mut first_glyph, mut last_glyph) = (None, None);
+        for glyph in 0..num_glyphs as usize {
+            let offset_at = strike + 4 + glyph * 4;
+            let (Some(start), Some(end)) = (read_u32(data, offset_at), read_u32(data, offset_at + 4)) else {
+                break;
+            };
+            if end <= start {
+                continue;
+            }
+            first_glyph.get_or_insert(glyph as u16);
+            last_glyph = Some(glyph as u16);
+            let graphic_type = strike + start as usize + 4;
+            if let Some(tag) = data.get(graphic_type..graphic_type + 4) {
+                formats.insert(String::from_utf8_lossy(tag).trim().to_string());
+            }
+        }
+
+        strikes.push(BitmapStrike {
+            source: BitmapSource::Sbix,
+            ppem_x: ppem,
+            ppem_y: ppem,
+            bit_depth: None,
+            first_glyph,
+            last_glyph,
+            formats: formats.into_iter().collect(),
+        });
+    }
+    strikes
+}
+
+/// Expand 1/2/4/8-bit pixels to one coverage byte per pixel
+fn unpack_gray(data: &[u8], width: usize, height: usize, bits: usize, padded: bool) -> Option<Vec<u8>> {
+    let max = (1u16 << bits) - 1;
+    let row_bits = if padded { (width * bits).div_ceil(8) * 8 } else { width * bits };
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let bit = y * row_bits + x * bits;
+            let byte = *data.get(bit / 8)?;
+            let value = (byte >> (8 - bits - bit % 8)) as u16 & max;
+            pixels.push((value * 255 / max) as u8);
+        }
+    }
+    Some(pixels)
+}
+
+/// Premultiplied BGRA to straight RGBA
+fn unpremultiply_bgra(data: &[u8]) -> Vec<u8> {
+    data.chunks_exact(4)
+        .flat_map(|px| {
+            let (b, g, r, a) = (px[0] as u16, px[1] as u16, px[2] as u16, px[3] as u16);
+            let straight = |c: u16| (c * 255).checked_div(a).map_or(0, |v| v.min(255) as u8);
+            [straight(r), straight(g), straight(b), a as u8]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack_mono() {
+        // 3x2 mono, rows padded: 101xxxxx 011xxxxx
+        let data = [0b1010_0000, 0b0110_0000];
+        assert_eq!(unpack_gray(&data, 3, 2, 1, true), Some(vec![255, 0, 255, 0, 255, 255]));
+        // Packed: 101011xx
+        assert_eq!(unpack_gray(&[0b1010_1100], 3, 2, 1, false), Some(vec![255, 0, 255, 0, 255, 255]));
+        // Truncated data
+        assert_eq!(unpack_gray(&data[..1], 3, 2, 1, true), None);
+    }
+
+    #[test]
+    fn test_unpack_gray2() {
+        // 2x1 gray2: 11 01 xxxx
+        assert_eq!(unpack_gray(&[0b1101_0000], 2, 1, 2, true), Some(vec![255, 85]));
+    }
+
+    #[test]
+    fn test_unpremultiply_bgra() {
+        // Half-transparent full green
+        assert_eq!(unpremultiply_bgra(&[0x00, 0x80, 0x00, 0x80]), vec![0, 255, 0, 0x80]);
+        assert_eq!(unpremultiply_bgra(&[0x10, 0x10, 0x10, 0x00]), vec![0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,257 @@
+//! CJK style classification (宋体/明朝, 黑体/ゴシック, 楷体, 仿宋, 圆体) from localized
+//! names and the stroke endings of sample ideographs
+use super::classification::{tally_votes, FlattenedOutline};
+use serde::{Deserialize, Serialize};
+use ttf_parser::Face;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CjkStyle {
+    Song,     // 宋体 / 明体 / 明朝：横画收笔有三角形“字脚”
+    Hei,      // 黑体 / ゴシック：笔画粗细均匀、方头
+    Kai,      // 楷体：毛笔楷书，横画右上扬
+    Fangsong, // 仿宋：细笔画、右上扬
+    Yuan,     // 圆体 / 丸ゴシック：笔画两端为圆头
+}
+
+/// CJK style of a face; `style` is None for faces without ideographs or without evidence
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CjkStyleInfo {
+    pub style: Option<CjkStyle>,
+    pub confidence: f32,      // 0-1，计算方式同 FontClassification
+    pub signals: Vec<String>, // 名称关键字与笔画测量依据
+}
+
+const NAME_WEIGHT: f32 = 0.6;
+const SHAPE_WEIGHT: f32 = 0.4;
+
+/// Styles in keyword matching order: 仿宋 contains 宋 and 丸ゴシック contains ゴシック,
+/// so Fangsong and Yuan are checked before Song and Hei
+const STYLES: [CjkStyle; 5] = [
+    CjkStyle::Fangsong,
+    CjkStyle::Yuan,
+    CjkStyle::Kai,
+    CjkStyle::Song,
+    CjkStyle::Hei,
+];
+
+/// Whole words of Latin and romanized names (split at non-alphanumerics, lowercase)
+fn name_words(style: CjkStyle) -> &'static [&'static str] {
+    match style {
+        CjkStyle::Fangsong => &["fangsong", "stfangsong"],
+        CjkStyle::Yuan => &["yuan", "youyuan", "maru", "rounded"],
+        CjkStyle::Kai => &["kai", "kaiti", "stkaiti", "kyokasho"],
+        CjkStyle::Song => &[
+            "song", "songti", "stsong", "simsun", "nsimsun", "ming", "mingliu", "pmingliu", "mincho", "batang", "serif",
+        ],
+        CjkStyle::Hei => &[
+            "hei", "heiti", "stheiti", "simhei", "yahei", "jhenghei", "gothic", "sans", "meiryo", "dotum", "gulim",
+        ],
+    }
+}
+
+/// Substrings of CJK names
+fn name_fragments(style: CjkStyle) -> &'static [&'static str] {
+    match style {
+        CjkStyle::Fangsong => &["仿宋", "倣宋"],
+        CjkStyle::Yuan => &["圆", "圓", "丸ゴ", "둥근"],
+        CjkStyle::Kai => &["楷", "教科書"],
+        CjkStyle::Song => &["宋", "明朝", "明體", "明体", "명조", "바탕"],
+        CjkStyle::Hei => &["黑", "黒", "ゴシック", "고딕", "돋움", "굴림"],
+    }
+}
+
+/// Ideographs whose horizontal stroke is measured; 十's vertical stroke stays clear of the
+/// sampled positions
+const SAMPLE_IDEOGRAPHS: &[char] = &['一', '十'];
+
+/// Stroke measurements averaged over the sample ideographs, relative to the stroke thickness
+/// at a quarter of its length
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StrokeShape {
+    end_ratio: f32, // 收笔处 (80%-95%) 最大粗细 / 笔画粗细
+    tip_ratio: f32, // 两端 1% 处粗细 / 笔画粗细，圆头明显变细
+    slope: f32,     // 笔画中心线从 15% 到 85% 的上升量 / 笔画长度
+    weight: f32,    // 笔画粗细 / unitsPerEm
+}
+
+/// Classify the CJK style of a face by `names` (every localized name) and stroke shape
+pub fn detect_cjk_style(face: &Face, names: &[&str]) -> CjkStyleInfo {
+    if face.glyph_index('一').is_none() {
+        return CjkStyleInfo::default();
+    }
+
+    let mut votes: Vec<(CjkStyle, f32, String)> = Vec::new();
+    if let Some((style, keyword)) = name_vote(names) {
+        votes.push((style, NAME_WEIGHT, format!("name contains \"{}\"", keyword)));
+    }
+    if let Some(shape) = measure_strokes(face) {
+        if let Some(style) = shape_vote(&shape) {
+            let signal = format!(
+                "stroke end {:.2}, tip {:.2}, slope {:.3}, weight {:.3}",
+                shape.end_ratio, shape.tip_ratio, shape.slope, shape.weight
+            );
+            votes.push((style, SHAPE_WEIGHT, signal));
+        }
+    }
+
+    tally_votes(&STYLES, votes)
+        .map(|(style, confidence, signals)| CjkStyleInfo {
+            style: Some(style),
+            confidence,
+            signals,
+        })
+        .unwrap_or_default()
+}
+
+fn name_vote(names: &[&str]) -> Option<(CjkStyle, &'static str)> {
+    let lowered: Vec<String> = names.iter().map(|name| name.to_lowercase()).collect();
+    STYLES.iter().find_map(|&style| {
+        let word = name_words(style).iter().find(|keyword| {
+            lowered
+                .iter()
+                .any(|name| name.split(|c: char| !c.is_alphanumeric()).any(|word| word == **keyword))
+        });
+        let fragment = || {
+            name_fragments(style)
+                .iter()
+                .find(|keyword| lowered.iter().any(|name| name.contains(**keyword)))
+        };
+        word.or_else(fragment).map(|&keyword| (style, keyword))
+    })
+}
+
+fn shape_vote(shape: &StrokeShape) -> Option<CjkStyle> {
+    if shape.slope >= 0.03 {
+        // Brush styles; 仿宋 strokes are much thinner than 楷体
+        Some(if shape.weight < 0.045 { CjkStyle::Fangsong } else { CjkStyle::Kai })
+    } else if shape.end_ratio >= 1.6 {
+        Some(CjkStyle::Song)
+    } else if shape.tip_ratio <= 0.75 {
+        Some(CjkStyle::Yuan)
+    } else if shape.end_ratio <= 1.25 && shape.tip_ratio >= 0.85 {
+        Some(CjkStyle::Hei)
+    } else {
+        None
+    }
+}
+
+fn measure_strokes(face: &Face) -> Option<StrokeShape> {
+    let shapes: Vec<StrokeShape> = SAMPLE_IDEOGRAPHS
+        .iter()
+        .filter_map(|&c| {
+            let glyph = face.glyph_index(c)?;
+            let mut outline = FlattenedOutline::default();
+            let bbox = face.outline_glyph(glyph, &mut outline)?;
+            stroke_shape(&outline, bbox.x_min as f32, bbox.x_max as f32, face.units_per_em() as f32)
+        })
+        .collect();
+    if shapes.is_empty() {
+        return None;
+    }
+
+    let average = |value: fn(&StrokeShape) -> f32| shapes.iter().map(value).sum::<f32>() / shapes.len() as f32;
+    Some(StrokeShape {
+        end_ratio: average(|s| s.end_ratio),
+        tip_ratio: average(|s| s.tip_ratio),
+        slope: average(|s| s.slope),
+        weight: average(|s| s.weight),
+    })
+}
+
+/// Measure the horizontal stroke spanning `x_min..x_max` with vertical scanlines
+fn stroke_shape(outline: &FlattenedOutline, x_min: f32, x_max: f32, units_per_em: f32) -> Option<StrokeShape> {
+    let length = x_max - x_min;
+    if length <= 0.0 || units_per_em <= 0.0 {
+        return None;
+    }
+    let at = |fraction: f32| outline.extent_at_x(x_min + length * fraction);
+    let thickness = |fraction: f32| at(fraction).map(|(low, high)| high - low);
+    let center = |fraction: f32| at(fraction).map(|(low, high)| (low + high) / 2.0);
+
+    let stroke = thickness(0.25).filter(|&t| t > 0.0)?;
+    let end = [0.80, 0.85, 0.90, 0.95]
+        .into_iter()
+        .filter_map(thickness)
+        .fold(0.0, f32::max);
+    let tip = thickness(0.01)?.max(thickness(0.99)?);
+
+    Some(StrokeShape {
+        end_ratio: end / stroke,
+        tip_ratio: tip / stroke,
+        slope: (center(0.85)? - center(0.15)?) / length,
+        weight: stroke / units_per_em,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ttf_parser::OutlineBuilder;
+
+    /// Outline through the given points, then measured like a 1000 upem ideograph
+    fn shape_of(points: &[(f32, f32)]) -> StrokeShape {
+        let mut outline = FlattenedOutline::default();
+        outline.move_to(points[0].0, points[0].1);
+        for &(x, y) in &points[1..] {
+            outline.line_to(x, y);
+        }
+        outline.close();
+        let x_min = points.iter().map(|p| p.0).fold(f32::MAX, f32::min);
+        let x_max = points.iter().map(|p| p.0).fold(f32::MIN, f32::max);
+        stroke_shape(&outline, x_min, x_max, 1000.0).unwrap()
+    }
+
+    #[test]
+    fn test_shape_vote() {
+        // 黑体: plain rectangle
+        let hei = shape_of(&[(100.0, 400.0), (900.0, 400.0), (900.0, 470.0), (100.0, 470.0)]);
+        assert_eq!(shape_vote(&hei), Some(CjkStyle::Hei));
+
+        // 宋体: thin stroke ending in a triangular 字脚
+        let song = shape_of(&[
+            (100.0, 420.0),
+            (900.0, 420.0),
+            (900.0, 440.0),
+            (820.0, 520.0),
+            (760.0, 440.0),
+            (100.0, 440.0),
+        ]);
+        assert_eq!(shape_vote(&song), Some(CjkStyle::Song));
+
+        // 圆体: octagon-like rounded caps
+        let yuan = shape_of(&[
+            (100.0, 435.0),
+            (110.0, 405.0),
+            (135.0, 400.0),
+            (865.0, 400.0),
+            (890.0, 405.0),
+            (900.0, 435.0),
+            (890.0, 465.0),
+            (865.0, 470.0),
+            (135.0, 470.0),
+            (110.0, 465.0),
+        ]);
+        assert_eq!(shape_vote(&yuan), Some(CjkStyle::Yuan));
+
+        // 楷体 and 仿宋: rising strokes, told apart by weight
+        let kai = shape_of(&[(100.0, 400.0), (900.0, 480.0), (900.0, 550.0), (100.0, 470.0)]);
+        assert_eq!(shape_vote(&kai), Some(CjkStyle::Kai));
+        let fangsong = shape_of(&[(100.0, 400.0), (900.0, 480.0), (900.0, 510.0), (100.0, 430.0)]);
+        assert_eq!(shape_vote(&fangsong), Some(CjkStyle::Fangsong));
+    }
+
+    #[test]
+    fn test_name_vote() {
+        let style = |name: &str| name_vote(&[name]).map(|(style, _)| style);
+        assert_eq!(style("仿宋_GB2312"), Some(CjkStyle::Fangsong));
+        assert_eq!(style("FangSong"), Some(CjkStyle::Fangsong));
+        assert_eq!(style("ヒラギノ丸ゴ ProN"), Some(CjkStyle::Yuan));
+        assert_eq!(style("幼圆"), Some(CjkStyle::Yuan));
+        assert_eq!(style("STKaiti"), Some(CjkStyle::Kai));
+        assert_eq!(style("Noto Serif CJK SC"), Some(CjkStyle::Song));
+        assert_eq!(style("MS Mincho"), Some(CjkStyle::Song));
+        assert_eq!(style("SimHei"), Some(CjkStyle::Hei));
+        assert_eq!(style("游ゴシック"), Some(CjkStyle::Hei));
+        assert_eq!(style("Arial"), None);
+    }
+}
@@ -0,0 +1,388 @@
+//! Font category classification (serif, sans, script, display, monospace) from PANOSE,
+//! sFamilyClass, name keywords and glyph shape
+use super::models::FontInfo;
+use super::monospace::MonospaceInfo;
+use super::raw::{self, read_i16};
+use serde::{Deserialize, Serialize};
+use ttf_parser::{Face, OutlineBuilder};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FontCategory {
+    SansSerif,
+    Serif,
+    Script, // 手写、书法 (含楷体)
+    Display,
+    Monospace,
+    #[default]
+    Unknown, // 没有任何可用信号
+}
+
+/// Category of a face with the evidence it was derived from
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FontClassification {
+    pub category: FontCategory,
+    pub confidence: f32,      // 0-1：信号一致程度 × 信号强度
+    pub signals: Vec<String>, // 参与判断的依据 (如 "PANOSE serif style 11")
+}
+
+/// Candidate categories of the weighted vote; Monospace is measured rather than voted
+const VOTED: [FontCategory; 4] = [
+    FontCategory::SansSerif,
+    FontCategory::Serif,
+    FontCategory::Script,
+    FontCategory::Display,
+];
+
+const NAME_WEIGHT: f32 = 0.4;
+const PANOSE_WEIGHT: f32 = 0.35;
+const FAMILY_CLASS_WEIGHT: f32 = 0.3;
+const SHAPE_WEIGHT: f32 = 0.3;
+
+/// Whole words of Latin and romanized CJK family names
+const NAME_WORDS: &[(&str, FontCategory)] = &[
+    ("sans", FontCategory::SansSerif),
+    ("gothic", FontCategory::SansSerif),
+    ("grotesk", FontCategory::SansSerif),
+    ("grotesque", FontCategory::SansSerif),
+    ("hei", FontCategory::SansSerif),
+    ("heiti", FontCategory::SansSerif),
+    ("yahei", FontCategory::SansSerif),
+    ("jhenghei", FontCategory::SansSerif),
+    ("dotum", FontCategory::SansSerif),
+    ("gulim", FontCategory::SansSerif),
+    ("serif", FontCategory::Serif),
+    ("roman", FontCategory::Serif),
+    ("song", FontCategory::Serif),
+    ("songti", FontCategory::Serif),
+    ("simsun", FontCategory::Serif),
+    ("nsimsun", FontCategory::Serif),
+    ("ming", FontCategory::Serif),
+    ("mingliu", FontCategory::Serif),
+    ("mincho", FontCategory::Serif),
+    ("batang", FontCategory::Serif),
+    ("script", FontCategory::Script),
+    ("hand", FontCategory::Script),
+    ("handwriting", FontCategory::Script),
+    ("brush", FontCategory::Script),
+    ("calligraphy", FontCategory::Script),
+    ("kai", FontCategory::Script),
+    ("kaiti", FontCategory::Script),
+    ("xingkai", FontCategory::Script),
+    ("display", FontCategory::Display),
+    ("poster", FontCategory::Display),
+    ("decorative", FontCategory::Display),
+    ("ornaments", FontCategory::Display),
+    ("stencil", FontCategory::Display),
+];
+
+/// Substrings of CJK family names
+const NAME_FRAGMENTS: &[(&str, FontCategory)] = &[
+    ("黑", FontCategory::SansSerif),
+    ("ゴシック", FontCategory::SansSerif),
+    ("고딕", FontCategory::SansSerif),
+    ("宋", FontCategory::Serif),
+    ("明體", FontCategory::Serif),
+    ("明体", FontCategory::Serif),
+    ("明朝", FontCategory::Serif),
+    ("바탕", FontCategory::Serif),
+    ("명조", FontCategory::Serif),
+    ("楷", FontCategory::Script),
+    ("行书", FontCategory::Script),
+    ("行書", FontCategory::Script),
+    ("手写", FontCategory::Script),
+    ("手書", FontCategory::Script),
+];
+
+/// Classify a face. `names` are the family names to look for keywords in (English and localized).
+pub fn classify_font(face: &Face, names: &[&str], monospace: &MonospaceInfo) -> FontClassification {
+    if monospace.is_monospace {
+        let (confidence, signal) = if monospace.ascii_fixed_pitch {
+            (1.0, "ASCII advance widths are equal")
+        } else {
+            (0.7, "post.isFixedPitch")
+        };
+        return FontClassification {
+            category: FontCategory::Monospace,
+            confidence,
+            signals: vec![signal.to_string()],
+        };
+    }
+
+    let mut votes: Vec<(FontCategory, f32, String)> = Vec::new();
+    if let Some((category, signal)) = name_vote(names) {
+        votes.push((category, NAME_WEIGHT, signal));
+    }
+    let os2 = raw::table(face, b"OS/2");
+    if let Some((category, signal)) = os2.and_then(panose_vote) {
+        votes.push((category, PANOSE_WEIGHT, signal));
+    }
+    if let Some((category, signal)) = os2.and_then(|data| read_i16(data, 30)).and_then(family_class_vote) {
+        votes.push((category, FAMILY_CLASS_WEIGHT, signal));
+    }
+    if let Some(ratio) = serif_ratio(face) {
+        let category = if ratio >= 1.5 {
+            Some(FontCategory::Serif)
+        } else if ratio <= 1.15 {
+            Some(FontCategory::SansSerif)
+        } else {
+            None
+        };
+        if let Some(category) = category {
+            votes.push((category, SHAPE_WEIGHT, format!("'I' foot/stem width ratio {:.2}", ratio)));
+        }
+    }
+
+    tally(votes)
+}
+
+fn tally(votes: Vec<(FontCategory, f32, String)>) -> FontClassification {
+    tally_votes(&VOTED, votes)
+        .map(|(category, confidence, signals)| FontClassification {
+            category,
+            confidence,
+            signals,
+        })
+        .unwrap_or_default()
+}
+
+/// Pick the candidate with the highest total weight. Confidence is the winner's share of
+/// the votes scaled by how much evidence there was (saturating at 1.0).
+pub(crate) fn tally_votes<T: Copy + PartialEq>(candidates: &[T], votes: Vec<(T, f32, String)>) -> Option<(T, f32, Vec<String>)> {
+    let total: f32 = votes.iter().map(|(_, weight, _)| weight).sum();
+    let (winner, score) = candidates
+        .iter()
+        .map(|&candidate| {
+            let score: f32 = votes.iter().filter(|(c, _, _)| *c == candidate).map(|(_, w, _)| w).sum();
+            (candidate, score)
+        })
+        .filter(|&(_, score)| score > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    let confidence = score / total * total.min(1.0);
+    Some((
+        winner,
+        (confidence * 100.0).round() / 100.0,
+        votes.into_iter().map(|(_, _, signal)| signal).collect(),
+    ))
+}
+
+/// Keyword in the family names; the first category found wins
+fn name_vote(names: &[&str]) -> Option<(FontCategory, String)> {
+    for name in names {
+        let lower = name.to_lowercase();
+        let words: Vec<&str> = lower.split(|c: char| !c.is_alphanumeric()).collect();
+        // "Sans Serif" names are sans, so sans words are checked before "serif"
+        let word = NAME_WORDS
+            .iter()
+            .find(|(keyword, _)| words.contains(keyword))
+            .map(|&(keyword, category)| (keyword, category));
+        let fragment = || {
+            NAME_FRAGMENTS
+                .iter()
+                .find(|(keyword, _)| lower.contains(keyword))
+                .map(|&(keyword, category)| (keyword, category))
+        };
+        if let Some((keyword, category)) = word.or_else(fragment) {
+            return Some((category, format!("name contains \"{}\"", keyword)));
+        }
+    }
+    None
+}
+
+/// PANOSE (OS/2 offset 32): bFamilyType and, for Latin Text, bSerifStyle
+fn panose_vote(os2: &[u8]) -> Option<(FontCategory, String)> {
+    let panose = os2.get(32..42)?;
+    let category = match panose[0] {
+        2 => match panose[1] {
+            2..=10 => FontCategory::Serif,
+            11..=13 => FontCategory::SansSerif,
+            // Flared and rounded serif styles sit between the two
+            _ => return None,
+        },
+        3 => FontCategory::Script,
+        4 => FontCategory::Display,
+        _ => return None,
+    };
+    Some((category, format!("PANOSE family type {}, serif style {}", panose[0], panose[1])))
+}
+
+/// sFamilyClass high byte (IBM font class)
+fn family_class_vote(family_class: i16) -> Option<(FontCategory, String)> {
+    let class = (family_class >> 8) as u8;
+    let category = match class {
+        1..=5 | 7 => FontCategory::Serif,
+        8 => FontCategory::SansSerif,
+        9 => FontCategory::Display,
+        10 => FontCategory::Script,
+        _ => return None,
+    };
+    Some((category, format!("sFamilyClass {}", class)))
+}
+
+/// Width of 'I' near its foot and top relative to mid-stem. Serifs widen the ends,
+/// sans-serif stems keep the same width.
+fn serif_ratio(face: &Face) -> Option<f32> {
+    let glyph = face.glyph_index('I')?;
+    let mut outline = FlattenedOutline::default();
+    let bbox = face.outline_glyph(glyph, &mut outline)?;
+    let height = (bbox.y_max - bbox.y_min) as f32;
+    if height <= 0.0 {
+        return None;
+    }
+
+    let width_at = |fraction: f32| outline.width_at(bbox.y_min as f32 + height * fraction);
+    let mid = width_at(0.5)?;
+    let ends = width_at(0.03)?.min(width_at(0.97)?);
+    (mid > 0.0).then(|| ends / mid)
+}
+
+/// Glyph outline flattened into line segments
+#[derive(Default)]
+pub(crate) struct FlattenedOutline {
+    segments: Vec<((f32, f32), (f32, f32))>,
+    start: (f32, f32),
+    current: (f32, f32),
+}
+
+const CURVE_STEPS: usize = 8;
+
+impl FlattenedOutline {
+    fn push(&mut self, to: (f32, f32)) {
+        self.segments.push((self.current, to));
+        self.current = to;
+    }
+
+    /// Horizontal extent of the outline crossed by the scanline at `y`
+    pub(crate) fn width_at(&self, y: f32) -> Option<f32> {
+        let (min, max) = self.extent_at_y(y)?;
+        Some(max - min)
+    }
+
+    /// Leftmost and rightmost crossings of the horizontal line at `y`
+    pub(crate) fn extent_at_y(&self, y: f32) -> Option<(f32, f32)> {
+        min_max(
+            self.segments
+                .iter()
+                .filter(|&&((_, y0), (_, y1))| (y0 <= y && y < y1) || (y1 <= y && y < y0))
+                .map(|&((x0, y0), (x1, y1))| x0 + (y - y0) / (y1 - y0) * (x1 - x0)),
+        )
+    }
+
+    /// Lowest and highest crossings of the vertical line at `x`
+    pub(crate) fn extent_at_x(&self, x: f32) -> Option<(f32, f32)> {
+        min_max(
+            self.segments
+                .iter()
+                .filter(|&&((x0, _), (x1, _))| (x0 <= x && x < x1) || (x1 <= x && x < x0))
+                .map(|&((x0, y0), (x1, y1))| y0 + (x - x0) / (x1 - x0) * (y1 - y0)),
+        )
+    }
+}
+
+fn min_max(values: impl Iterator<Item = f32>) -> Option<(f32, f32)> {
+    values.fold(None, |acc, v| match acc {
+        Some((min, max)) => Some((f32::min(min, v), f32::max(max, v))),
+        None => Some((v, v)),
+    })
+}
+
+impl OutlineBuilder for FlattenedOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = (x, y);
+        self.current = (x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push((x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x0, y0) = self.current;
+        for step in 1..=CURVE_STEPS {
+            let t = step as f32 / CURVE_STEPS as f32;
+            let u = 1.0 - t;
+            self.push((
+                u * u * x0 + 2.0 * u * t * x1 + t * t * x,
+                u * u * y0 + 2.0 * u * t * y1 + t * t * y,
+            ));
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x0, y0) = self.current;
+        for step in 1..=CURVE_STEPS {
+            let t = step as f32 / CURVE_STEPS as f32;
+            let u = 1.0 - t;
+            self.push((
+                u * u * u * x0 + 3.0 * u * u * t * x1 + 3.0 * u * t * t * x2 + t * t * t * x,
+                u * u * u * y0 + 3.0 * u * u * t * y1 + 3.0 * u * t * t * y2 + t * t * t * y,
+            ));
+        }
+    }
+
+    fn close(&mut self) {
+        let start = self.start;
+        self.push(start);
+    }
+}
+
+/// Family category: each face votes for its category with its confidence; the family
+/// confidence is the winning total averaged over all faces
+pub fn classify_family(fonts: &[&FontInfo]) -> (FontCategory, f32) {
+    let mut totals: Vec<(FontCategory, f32)> = Vec::new();
+    for font in fonts {
+        let classification = &font.classification;
+        if classification.category == FontCategory::Unknown {
+            continue;
+        }
+        match totals.iter_mut().find(|(category, _)| *category == classification.category) {
+            Some((_, total)) => *total += classification.confidence,
+            None => totals.push((classification.category, classification.confidence)),
+        }
+    }
+    totals
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(category, total)| (category, (total / fonts.len() as f32 * 100.0).round() / 100.0))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_vote() {
+        let category = |name: &str| name_vote(&[name]).map(|(category, _)| category);
+        assert_eq!(category("Noto Sans Serif"), Some(FontCategory::SansSerif));
+        assert_eq!(category("PT Serif"), Some(FontCategory::Serif));
+        assert_eq!(category("MS Mincho"), Some(FontCategory::Serif));
+        assert_eq!(category("Microsoft YaHei"), Some(FontCategory::SansSerif));
+        assert_eq!(category("楷体"), Some(FontCategory::Script));
+        assert_eq!(category("思源宋体"), Some(FontCategory::Serif));
+        assert_eq!(category("Arial"), None);
+    }
+
+    #[test]
+    fn test_tally_confidence() {
+        let vote = |category, weight| (category, weight, String::new());
+        // A single name keyword is weak evidence
+        let weak = tally(vec![vote(FontCategory::Serif, NAME_WEIGHT)]);
+        assert_eq!((weak.category, weak.confidence), (FontCategory::Serif, 0.4));
+        // Agreeing signals saturate, disagreement lowers confidence
+        let strong = tally(vec![
+            vote(FontCategory::SansSerif, NAME_WEIGHT),
+            vote(FontCategory::SansSerif, PANOSE_WEIGHT),
+            vote(FontCategory::SansSerif, SHAPE_WEIGHT),
+        ]);
+        assert_eq!(strong.confidence, 1.0);
+        let mixed = tally(vec![
+            vote(FontCategory::SansSerif, NAME_WEIGHT),
+            vote(FontCategory::SansSerif, PANOSE_WEIGHT),
+            vote(FontCategory::Serif, SHAPE_WEIGHT),
+        ]);
+        assert_eq!(mixed.category, FontCategory::SansSerif);
+        assert!(mixed.confidence < 1.0);
+        assert_eq!(tally(Vec::new()).category, FontCategory::Unknown);
+    }
+}
@@ -0,0 +1,166 @@
+//! Color font technologies (COLR/CPAL, SVG, sbix, CBDT) and CPAL palettes
+use super::raw::{self, read_u16, read_u32};
+use super::scanner::FontScanner;
+use serde::{Deserialize, Serialize};
+use ttf_parser::Face;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ColorTechnology {
+    ColrV0, // COLR 版本 0：纯色图层
+    ColrV1, // COLR 版本 1：渐变、变换、合成
+    Svg,    // OpenType-SVG
+    Sbix,   // Apple 位图 (PNG/JPEG/TIFF)
+    Cbdt,   // Google 彩色位图 (CBDT/CBLC)
+}
+
+/// Color capabilities of a face
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColorInfo {
+    pub technologies: Vec<ColorTechnology>,
+    pub palette_count: u16, // CPAL 调色板数量，无 CPAL 为 0
+    pub is_emoji: bool,     // 彩色字体且覆盖常用 emoji 区段 (U+1F300-1F64F)
+}
+
+/// A CPAL palette with colors converted to RGBA
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorPalette {
+    pub index: u16,
+    pub colors: Vec<[u8; 4]>, // RGBA
+    pub usable_with_light_background: bool,
+    pub usable_with_dark_background: bool,
+    pub label: Option<String>, // CPAL v1 paletteLabel 对应的 name 记录
+}
+
+/// Minimum number of mapped emoji for a color font to count as an emoji font.
+/// Text fonts such as DejaVu map many of these as monochrome symbols, hence the color requirement.
+const EMOJI_THRESHOLD: usize = 32;
+
+/// Detect color technologies, CPAL palette count and emoji coverage
+pub fn detect_color_info(face: &Face) -> ColorInfo {
+    let mut technologies = Vec::new();
+    if let Some(version) = raw::table(face, b"COLR").and_then(|data| read_u16(data, 0)) {
+        technologies.push(if version >= 1 { ColorTechnology::ColrV1 } else { ColorTechnology::ColrV0 });
+    }
+    if raw::table(face, b"SVG ").is_some() {
+        technologies.push(ColorTechnology::Svg);
+    }
+    if raw::table(face, b"sbix").is_some() {
+        technologies.push(ColorTechnology::Sbix);
+    }
+    if raw::table(face, b"CBDT").is_some() && raw::table(face, b"CBLC").is_some() {
+        technologies.push(ColorTechnology::Cbdt);
+    }
+
+    let palette_count = raw::table(face, b"CPAL")
+        .and_then(|data| read_u16(data, 4))
+        .unwrap_or(0);
+
+    let emoji_count = (0x1F300..=0x1F64F)
+        .filter_map(char::from_u32)
+        .filter(|&c| face.glyph_index(c).is_some())
+        .take(EMOJI_THRESHOLD)
+        .count();
+
+    ColorInfo {
+        is_emoji: !technologies.is_empty() && emoji_count >= EMOJI_THRESHOLD,
+        technologies,
+        palette_count,
+    }
+}
+
+/// Read the CPAL palettes of a font file
+pub fn read_palettes(path: String, face_index: u32) -> Result<Vec<ColorPalette>, String> {
+    raw::with_face(&path, face_index, color_palettes)
+}
+
+/// Parse every CPAL palette; color records are stored as BGRA
+pub fn color_palettes(face: &Face) -> Vec<ColorPalette> {
+    let Some(data) = raw::table(face, b"CPAL") else {
+        return Vec::new();
+    };
+    parse_cpal(data)
+        .into_iter()
+        .map(|(palette, label_id)| ColorPalette {
+            label: label_id.and_then(|id| FontScanner::extract_name(face, id)),
+            ..palette
+        })
+        .collect()
+}
+
+/// Palettes plus their label name IDs (0xFFFF = no label)
+fn parse_cpal(data: &[u8]) -> Vec<(ColorPalette, Option<u16>)> {
+    let (Some(version), Some(num_entries), Some(num_palettes), Some(records_offset)) = (
+        read_u16(data, 0),
+        read_u16(data, 2),
+        read_u16(data, 4),
+        read_u32(data, 8),
+    ) else {
+        return Vec::new();
+    };
+
+    // Version 1 appends offsets to the palette type and label arrays after the index array
+    let v1_offsets = 12 + num_palettes as usize * 2;
+    let types_offset = (version >= 1)
+        .then(|| read_u32(data, v1_offsets))
+        .flatten()
+        .filter(|&offset| offset != 0);
+    let labels_offset = (version >= 1)
+        .then(|| read_u32(data, v1_offsets + 4))
+        .flatten()
+        .filter(|&offset| offset != 0);
+
+    let mut palettes = Vec::new();
+    for index in 0..num_palettes {
+        let Some(first_record) = read_u16(data, 12 + index as usize * 2) else {
+            break;
+        };
+        let colors = (0..num_entries as usize)
+            .map_while(|entry| {
+                let record = records_offset as usize + (first_record as usize + entry) * 4;
+                let bgra = data.get(record..record + 4)?;
+                Some([bgra[2], bgra[1], bgra[0], bgra[3]])
+            })
+            .collect();
+        let palette_type = types_offset
+            .and_then(|offset| read_u32(data, offset as usize + index as usize * 4))
+            .unwrap_or(0);
+        let label_id = labels_offset
+            .and_then(|offset| read_u16(data, offset as usize + index as usize * 2))
+            .filter(|&id| id != 0xFFFF);
+
+        palettes.push((
+            ColorPalette {
+                index,
+                colors,
+                usable_with_light_background: palette_type & 0x1 != 0,
+                usable_with_dark_background: palette_type & 0x2 != 0,
+                label: None,
+            },
+            label_id,
+        ));
+    }
+    palettes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpal_v0() {
+        // 2 entries, 2 palettes, 3 color records; palette 1 starts at record 1
+        let data = [
+            0x00, 0x00, 0x00, 0x02, 0x00, 0x02, 0x00, 0x03, 0x00, 0x00, 0x00, 0x10, // header
+            0x00, 0x00, 0x00, 0x01, // colorRecordIndices
+            0x00, 0x00, 0xFF, 0xFF, // red (BGRA)
+            0x00, 0xFF, 0x00, 0xFF, // green
+            0xFF, 0x00, 0x00, 0x80, // half transparent blue
+        ];
+        let palettes = parse_cpal(&data);
+        assert_eq!(palettes.len(), 2);
+        assert_eq!(palettes[0].0.colors, vec![[0xFF, 0, 0, 0xFF], [0, 0xFF, 0, 0xFF]]);
+        assert_eq!(palettes[1].0.colors, vec![[0, 0xFF, 0, 0xFF], [0, 0, 0xFF, 0x80]]);
+        assert!(!palettes[0].0.usable_with_dark_background);
+        assert_eq!(palettes[0].1, None);
+    }
+}
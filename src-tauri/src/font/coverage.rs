@@ -0,0 +1,377 @@
+use super::raw;
+use serde::{Deserialize, Serialize};
+use ttf_parser::Face;
+
+/// OS/2 ulUnicodeRange1-4 and ulCodePageRange1-2 compared against actual cmap coverage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Os2Coverage {
+    pub declared_unicode_ranges: Vec<u8>, // 已声明的 Unicode range bit (0-122)
+    pub declared_code_pages: Vec<u8>,     // 已声明的 code page bit (0-63)
+    pub mismatches: Vec<CoverageMismatch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CoverageSource {
+    UnicodeRange,
+    CodePage,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MismatchKind {
+    DeclaredButMissing,   // bit 已设置，但 cmap 中没有对应字符
+    PresentButUndeclared, // cmap 覆盖了足够多的字符，但 bit 未设置
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageMismatch {
+    pub source: CoverageSource,
+    pub kind: MismatchKind,
+    pub bit: u8,
+    pub name: String,
+    pub covered: u32, // cmap 中落在该范围内的字符数 (code page 为命中的探测字符数)
+    pub total: u32,   // 范围大小 (code page 为探测字符数)
+}
+
+/// (bit, name, inclusive code point ranges)
+type UnicodeRangeBit = (u8, &'static str, &'static [(u32, u32)]);
+
+/// OS/2 Unicode range bits, see https://learn.microsoft.com/typography/opentype/spec/os2#ur
+const UNICODE_RANGES: &[UnicodeRangeBit] = &[
+    (0, "Basic Latin", &[(0x0000, 0x007F)]),
+    (1, "Latin-1 Supplement", &[(0x0080, 0x00FF)]),
+    (2, "Latin Extended-A", &[(0x0100, 0x017F)]),
+    (3, "Latin Extended-B", &[(0x0180, 0x024F)]),
+    (4, "IPA Extensions", &[(0x0250, 0x02AF), (0x1D00, 0x1D7F), (0x1D80, 0x1DBF)]),
+    (5, "Spacing Modifier Letters", &[(0x02B0, 0x02FF), (0xA700, 0xA71F)]),
+    (6, "Combining Diacritical Marks", &[(0x0300, 0x036F), (0x1DC0, 0x1DFF)]),
+    (7, "Greek and Coptic", &[(0x0370, 0x03FF)]),
+    (8, "Coptic", &[(0x2C80, 0x2CFF)]),
+    (9, "Cyrillic", &[(0x0400, 0x04FF), (0x0500, 0x052F), (0x2DE0, 0x2DFF), (0xA640, 0xA69F)]),
+    (10, "Armenian", &[(0x0530, 0x058F)]),
+    (11, "Hebrew", &[(0x0590, 0x05FF)]),
+    (12, "Vai", &[(0xA500, 0xA63F)]),
+    (13, "Arabic", &[(0x0600, 0x06FF), (0x0750, 0x077F)]),
+    (14, "NKo", &[(0x07C0, 0x07FF)]),
+    (15, "Devanagari", &[(0x0900, 0x097F)]),
+    (16, "Bengali", &[(0x0980, 0x09FF)]),
+    (17, "Gurmukhi", &[(0x0A00, 0x0A7F)]),
+    (18, "Gujarati", &[(0x0A80, 0x0AFF)]),
+    (19, "Oriya", &[(0x0B00, 0x0B7F)]),
+    (20, "Tamil", &[(0x0B80, 0x0BFF)]),
+    (21, "Telugu", &[(0x0C00, 0x0C7F)]),
+    (22, "Kannada", &[(0x0C80, 0x0CFF)]),
+    (23, "Malayalam", &[(0x0D00, 0x0D7F)]),
+    (24, "Thai", &[(0x0E00, 0x0E7F)]),
+    (25, "Lao", &[(0x0E80, 0x0EFF)]),
+    (26, "Georgian", &[(0x10A0, 0x10FF), (0x2D00, 0x2D2F)]),
+    (27, "Balinese", &[(0x1B00, 0x1B7F)]),
+    (28, "Hangul Jamo", &[(0x1100, 0x11FF)]),
+    (29, "Latin Extended Additional", &[(0x1E00, 0x1EFF), (0x2C60, 0x2C7F), (0xA720, 0xA7FF)]),
+    (30, "Greek Extended", &[(0x1F00, 0x1FFF)]),
+    (31, "General Punctuation", &[(0x2000, 0x206F), (0x2E00, 0x2E7F)]),
+    (32, "Superscripts And Subscripts", &[(0x2070, 0x209F)]),
+    (33, "Currency Symbols", &[(0x20A0, 0x20CF)]),
+    (34, "Combining Diacritical Marks For Symbols", &[(0x20D0, 0x20FF)]),
+    (35, "Letterlike Symbols", &[(0x2100, 0x214F)]),
+    (36, "Number Forms", &[(0x2150, 0x218F)]),
+    (37, "Arrows", &[(0x2190, 0x21FF), (0x27F0, 0x27FF), (0x2900, 0x297F), (0x2B00, 0x2BFF)]),
+    (38, "Mathematical Operators", &[(0x2200, 0x22FF), (0x2A00, 0x2AFF), (0x27C0, 0x27EF), (0x2980, 0x29FF)]),
+    (39, "Miscellaneous Technical", &[(0x2300, 0x23FF)]),
+    (40, "Control Pictures", &[(0x2400, 0x243F)]),
+    (41, "Optical Character Recognition", &[(0x2440, 0x245F)]),
+    (42, "Enclosed Alphanumerics", &[(0x2460, 0x24FF)]),
+    (43, "Box Drawing", &[(0x2500, 0x257F)]),
+    (44, "Block Elements", &[(0x2580, 0x259F)]),
+    (45, "Geometric Shapes", &[(0x25A0, 0x25FF)]),
+    (46, "Miscellaneous Symbols", &[(0x2600, 0x26FF)]),
+    (47, "Dingbats", &[(0x2700, 0x27BF)]),
+    (48, "CJK Symbols And Punctuation", &[(0x3000, 0x303F)]),
+    (49, "Hiragana", &[(0x3040, 0x309F)]),
+    (50, "Katakana", &[(0x30A0, 0x30FF), (0x31F0, 0x31FF)]),
+    (51, "Bopomofo", &[(0x3100, 0x312F), (0x31A0, 0x31BF)]),
+    (52, "Hangul Compatibility Jamo", &[(0x3130, 0x318F)]),
+    (53, "Phags-pa", &[(0xA840, 0xA87F)]),
+    (54, "Enclosed CJK Letters And Months", &[(0x3200, 0x32FF)]),
+    (55, "CJK Compatibility", &[(0x3300, 0x33FF)]),
+    (56, "Hangul Syllables", &[(0xAC00, 0xD7AF)]),
+    (57, "Non-Plane 0", &[(0x10000, 0x10FFFF)]),
+    (58, "Phoenician", &[(0x10900, 0x1091F)]),
+    (
+        59,
+        "CJK Unified Ideographs",
+        &[
+            (0x4E00, 0x9FFF),
+            (0x2E80, 0x2EFF),
+            (0x2F00, 0x2FDF),
+            (0x2FF0, 0x2FFF),
+            (0x3400, 0x4DBF),
+            (0x20000, 0x2A6DF),
+            (0x3190, 0x319F),
+        ],
+    ),
+    (60, "Private Use Area (plane 0)", &[(0xE000, 0xF8FF)]),
+    (61, "CJK Strokes", &[(0x31C0, 0x31EF), (0xF900, 0xFAFF), (0x2F800, 0x2FA1F)]),
+    (62, "Alphabetic Presentation Forms", &[(0xFB00, 0xFB4F)]),
+    (63, "Arabic Presentation Forms-A", &[(0xFB50, 0xFDFF)]),
+    (64, "Combining Half Marks", &[(0xFE20, 0xFE2F)]),
+    (65, "Vertical Forms", &[(0xFE10, 0xFE1F), (0xFE30, 0xFE4F)]),
+    (66, "Small Form Variants", &[(0xFE50, 0xFE6F)]),
+    (67, "Arabic Presentation Forms-B", &[(0xFE70, 0xFEFF)]),
+    (68, "Halfwidth And Fullwidth Forms", &[(0xFF00, 0xFFEF)]),
+    (69, "Specials", &[(0xFFF0, 0xFFFF)]),
+    (70, "Tibetan", &[(0x0F00, 0x0FFF)]),
+    (71, "Syriac", &[(0x0700, 0x074F)]),
+    (72, "Thaana", &[(0x0780, 0x07BF)]),
+    (73, "Sinhala", &[(0x0D80, 0x0DFF)]),
+    (74, "Myanmar", &[(0x1000, 0x109F)]),
+    (75, "Ethiopic", &[(0x1200, 0x137F), (0x1380, 0x139F), (0x2D80, 0x2DDF)]),
+    (76, "Cherokee", &[(0x13A0, 0x13FF)]),
+    (77, "Unified Canadian Aboriginal Syllabics", &[(0x1400, 0x167F)]),
+    (78, "Ogham", &[(0x1680, 0x169F)]),
+    (79, "Runic", &[(0x16A0, 0x16FF)]),
+    (80, "Khmer", &[(0x1780, 0x17FF), (0x19E0, 0x19FF)]),
+    (81, "Mongolian", &[(0x1800, 0x18AF)]),
+    (82, "Braille Patterns", &[(0x2800, 0x28FF)]),
+    (83, "Yi Syllables", &[(0xA000, 0xA48F), (0xA490, 0xA4CF)]),
+    (84, "Tagalog", &[(0x1700, 0x171F), (0x1720, 0x173F), (0x1740, 0x175F), (0x1760, 0x177F)]),
+    (85, "Old Italic", &[(0x10300, 0x1032F)]),
+    (86, "Gothic", &[(0x10330, 0x1034F)]),
+    (87, "Deseret", &[(0x10400, 0x1044F)]),
+    (88, "Byzantine Musical Symbols", &[(0x1D000, 0x1D0FF), (0x1D100, 0x1D1FF), (0x1D200, 0x1D24F)]),
+    (89, "Mathematical Alphanumeric Symbols", &[(0x1D400, 0x1D7FF)]),
+    (90, "Private Use (plane 15)", &[(0xF0000, 0xFFFFD), (0x100000, 0x10FFFD)]),
+    (91, "Variation Selectors", &[(0xFE00, 0xFE0F), (0xE0100, 0xE01EF)]),
+    (92, "Tags", &[(0xE0000, 0xE007F)]),
+    (93, "Limbu", &[(0x1900, 0x194F)]),
+    (94, "Tai Le", &[(0x1950, 0x197F)]),
+    (95, "New Tai Lue", &[(0x1980, 0x19DF)]),
+    (96, "Buginese", &[(0x1A00, 0x1A1F)]),
+    (97, "Glagolitic", &[(0x2C00, 0x2C5F)]),
+    (98, "Tifinagh", &[(0x2D30, 0x2D7F)]),
+    (99, "Yijing Hexagram Symbols", &[(0x4DC0, 0x4DFF)]),
+    (100, "Syloti Nagri", &[(0xA800, 0xA82F)]),
+    (101, "Linear B Syllabary", &[(0x10000, 0x1007F), (0x10080, 0x100FF), (0x10100, 0x1013F)]),
+    (102, "Ancient Greek Numbers", &[(0x10140, 0x1018F)]),
+    (103, "Ugaritic", &[(0x10380, 0x1039F)]),
+    (104, "Old Persian", &[(0x103A0, 0x103DF)]),
+    (105, "Shavian", &[(0x10450, 0x1047F)]),
+    (106, "Osmanya", &[(0x10480, 0x104AF)]),
+    (107, "Cypriot Syllabary", &[(0x10800, 0x1083F)]),
+    (108, "Kharoshthi", &[(0x10A00, 0x10A5F)]),
+    (109, "Tai Xuan Jing Symbols", &[(0x1D300, 0x1D35F)]),
+    (110, "Cuneiform", &[(0x12000, 0x123FF), (0x12400, 0x1247F)]),
+    (111, "Counting Rod Numerals", &[(0x1D360, 0x1D37F)]),
+    (112, "Sundanese", &[(0x1B80, 0x1BBF)]),
+    (113, "Lepcha", &[(0x1C00, 0x1C4F)]),
+    (114, "Ol Chiki", &[(0x1C50, 0x1C7F)]),
+    (115, "Saurashtra", &[(0xA880, 0xA8DF)]),
+    (116, "Kayah Li", &[(0xA900, 0xA92F)]),
+    (117, "Rejang", &[(0xA930, 0xA95F)]),
+    (118, "Cham", &[(0xAA00, 0xAA5F)]),
+    (119, "Ancient Symbols", &[(0x10190, 0x101CF)]),
+    (120, "Phaistos Disc", &[(0x101D0, 0x101FF)]),
+    (121, "Carian", &[(0x102A0, 0x102DF), (0x10280, 0x1029F), (0x10920, 0x1093F)]),
+    (122, "Domino Tiles", &[(0x1F030, 0x1F09F), (0x1F000, 0x1F02F)]),
+];
+
+/// OS/2 code page bits with probe characters that must exist if the code page is supported.
+/// Bits without probes (OEM/Symbol/Macintosh/DOS code pages) are reported but not cross-checked.
+const CODE_PAGES: &[(u8, &str, &[char])] = &[
+    (0, "1252 Latin 1", &['À', 'é', 'ß', 'ÿ']),
+    (1, "1250 Latin 2: Eastern Europe", &['Ł', 'ő', 'ř']),
+    (2, "1251 Cyrillic", &['Ж', 'я', 'Ђ']),
+    (3, "1253 Greek", &['Α', 'Ω', 'ά']),
+    (4, "1254 Turkish", &['Ğ', 'ı', 'Ş']),
+    (5, "1255 Hebrew", &['א', 'ת']),
+    (6, "1256 Arabic", &['ا', 'ب', 'پ']),
+    (7, "1257 Windows Baltic", &['Ą', 'ė', 'Ų', 'ū']),
+    (8, "1258 Vietnamese", &['Ư', 'ơ', '₫']),
+    (16, "874 Thai", &['ก', '๙']),
+    (17, "932 JIS/Japan", &['あ', 'ア', '亜']),
+    (18, "936 Chinese: Simplified", &['的', '这', '们']),
+    (19, "949 Korean Wansung", &['가', '힣']),
+    (20, "950 Chinese: Traditional", &['這', '們', '書']),
+    (21, "1361 Korean Johab", &['가', '똠']),
+    (29, "Macintosh Character Set", &[]),
+    (30, "OEM Character Set", &[]),
+    (31, "Symbol Character Set", &[]),
+    (48, "869 IBM Greek", &[]),
+    (49, "866 MS-DOS Russian", &[]),
+    (50, "865 MS-DOS Nordic", &[]),
+    (51, "864 Arabic", &[]),
+    (52, "863 MS-DOS Canadian French", &[]),
+    (53, "862 Hebrew", &[]),
+    (54, "861 MS-DOS Icelandic", &[]),
+    (55, "860 MS-DOS Portuguese", &[]),
+    (56, "857 IBM Turkish", &[]),
+    (57, "855 IBM Cyrillic", &[]),
+    (58, "852 Latin 2", &[]),
+    (59, "775 MS-DOS Baltic", &[]),
+    (60, "737 Greek", &[]),
+    (61, "708 Arabic; ASMO 708", &[]),
+    (62, "850 WE/Latin 1", &[]),
+    (63, "437 US", &[]),
+];
+
+/// Bit 57 is set for any supplementary-plane coverage rather than a specific block
+const NON_PLANE_0_BIT: u8 = 57;
+
+/// A range counts as present when at least this many characters are mapped...
+const PRESENT_MIN_CHARS: u32 = 256;
+/// ...or when this percentage of the range is mapped
+const PRESENT_MIN_PERCENT: u32 = 10;
+
+/// Collect all Unicode code points mapped by the cmap table, sorted and deduplicated
+pub fn unicode_codepoints(face: &Face) -> Vec<u32> {
+    let mut codepoints = Vec::new();
+    if let Some(cmap) = face.tables().cmap {
+        for subtable in cmap.subtables {
+            if subtable.is_unicode() {
+                subtable.codepoints(|cp| codepoints.push(cp));
+            }
+        }
+    }
+    codepoints.sort_unstable();
+    codepoints.dedup();
+    codepoints
+}
+
+/// Read declared OS/2 ranges and cross-check them against cmap coverage.
+/// `codepoints` is the sorted output of [`unicode_codepoints`].
+/// Returns None when the font has no OS/2 table.
+pub fn check_os2_coverage(face: &Face, codepoints: &[u32]) -> Option<Os2Coverage> {
+    let os2 = raw::table(face, b"OS/2")?;
+
+    // ulUnicodeRange1-4 at offset 42, ulCodePageRange1-2 at offset 78 (version >= 1)
+    let unicode_bits = read_bits(os2, 42, 4);
+    let code_page_bits = if raw::read_u16(os2, 0).unwrap_or(0) >= 1 {
+        read_bits(os2, 78, 2)
+    } else {
+        Vec::new()
+    };
+
+    let mut mismatches = Vec::new();
+
+    for &(bit, name, ranges) in UNICODE_RANGES {
+        let declared = unicode_bits.contains(&bit);
+        let total: u32 = ranges.iter().map(|(start, end)| end - start + 1).sum();
+        let covered: u32 = ranges
+            .iter()
+            .map(|&(start, end)| count_in_range(codepoints, start, end))
+            .sum();
+
+        let present = if bit == NON_PLANE_0_BIT {
+            covered > 0
+        } else {
+            covered >= PRESENT_MIN_CHARS || covered * 100 >= total * PRESENT_MIN_PERCENT
+        };
+
+        let kind = if declared && covered == 0 {
+            MismatchKind::DeclaredButMissing
+        } else if !declared && present {
+            MismatchKind::PresentButUndeclared
+        } else {
+            continue;
+        };
+
+        mismatches.push(CoverageMismatch {
+            source: CoverageSource::UnicodeRange,
+            kind,
+            bit,
+            name: name.to_string(),
+            covered,
+            total,
+        });
+    }
+
+    for &(bit, name, probes) in CODE_PAGES {
+        if probes.is_empty() {
+            continue;
+        }
+        let declared = code_page_bits.contains(&bit);
+        let total = probes.len() as u32;
+        let covered = probes
+            .iter()
+            .filter(|&&c| codepoints.binary_search(&(c as u32)).is_ok())
+            .count() as u32;
+
+        let kind = if declared && covered < total {
+            MismatchKind::DeclaredButMissing
+        } else if !declared && covered == total {
+            MismatchKind::PresentButUndeclared
+        } else {
+            continue;
+        };
+
+        mismatches.push(CoverageMismatch {
+            source: CoverageSource::CodePage,
+            kind,
+            bit,
+            name: name.to_string(),
+            covered,
+            total,
+        });
+    }
+
+    Some(Os2Coverage {
+        declared_unicode_ranges: unicode_bits,
+        declared_code_pages: code_page_bits,
+        mismatches,
+    })
+}
+
+/// Read `count` consecutive u32 bit fields starting at `offset` and return set bit indices
+fn read_bits(data: &[u8], offset: usize, count: usize) -> Vec<u8> {
+    let mut bits = Vec::new();
+    for i in 0..count {
+        let Some(value) = raw::read_u32(data, offset + i * 4) else {
+            break;
+        };
+        for bit in 0..32 {
+            if value & (1 << bit) != 0 {
+                bits.push((i * 32 + bit) as u8);
+            }
+        }
+    }
+    bits
+}
+
+/// Count code points within [start, end] in a sorted slice
+pub(crate) fn count_in_range(codepoints: &[u32], start: u32, end: u32) -> u32 {
+    let lo = codepoints.partition_point(|&cp| cp < start);
+    let hi = codepoints.partition_point(|&cp| cp <= end);
+    (hi - lo) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_bits() {
+        // ulUnicodeRange1 = bit 0 + bit 1, ulUnicodeRange2 = bit 59 (27 in second word)
+        let data = [0x00, 0x00, 0x00, 0x03, 0x08, 0x00, 0x00, 0x00];
+        assert_eq!(read_bits(&data, 0, 2), vec![0, 1, 59]);
+        // Truncated data stops reading instead of panicking
+        assert_eq!(read_bits(&data[..6], 0, 2), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_count_in_range() {
+        let codepoints = vec![0x41, 0x42, 0x43, 0x4E00, 0x4E01, 0x20000];
+        assert_eq!(count_in_range(&codepoints, 0x0000, 0x007F), 3);
+        assert_eq!(count_in_range(&codepoints, 0x4E00, 0x9FFF), 2);
+        assert_eq!(count_in_range(&codepoints, 0x0080, 0x00FF), 0);
+        assert_eq!(count_in_range(&codepoints, 0x10000, 0x10FFFF), 1);
+    }
+
+    #[test]
+    fn test_range_tables_are_sorted_and_unique() {
+        for pair in UNICODE_RANGES.windows(2) {
+            assert!(pair[0].0 < pair[1].0);
+        }
+        for pair in CODE_PAGES.windows(2) {
+            assert!(pair[0].0 < pair[1].0);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Decoders for non-Unicode name records (Macintosh scripts and Windows legacy code pages)
+use encoding_rs::{Encoding, BIG5, EUC_KR, GBK, MACINTOSH, SHIFT_JIS, UTF_16BE, X_MAC_CYRILLIC};
+
+const PLATFORM_UNICODE: u16 = 0;
+const PLATFORM_MACINTOSH: u16 = 1;
+const PLATFORM_WINDOWS: u16 = 3;
+
+/// Decode raw name record bytes for any supported platform/encoding combination.
+/// Returns None for unsupported encodings or undecodable data.
+pub fn decode_name_bytes(platform_id: u16, encoding_id: u16, bytes: &[u8]) -> Option<String> {
+    match (platform_id, encoding_id) {
+        // Unicode platform and Windows Symbol/UCS-2/UCS-4 are UTF-16BE
+        (PLATFORM_UNICODE, _) | (PLATFORM_WINDOWS, 0 | 1 | 10) => decode_strict(UTF_16BE, bytes),
+        (PLATFORM_MACINTOSH, script) => decode_strict(mac_script_encoding(script)?, bytes),
+        (PLATFORM_WINDOWS, 6) => decode_johab(&unpack_wide_bytes(bytes)),
+        (PLATFORM_WINDOWS, encoding) => {
+            decode_strict(windows_legacy_encoding(encoding)?, &unpack_wide_bytes(bytes))
+        }
+        _ => None,
+    }
+}
+
+/// Macintosh script codes, see https://learn.microsoft.com/typography/opentype/spec/name#macintosh-encoding-ids-script-manager-codes
+fn mac_script_encoding(script: u16) -> Option<&'static Encoding> {
+    match script {
+        0 => Some(MACINTOSH),       // Roman
+        1 => Some(SHIFT_JIS),       // Japanese
+        2 => Some(BIG5),            // Chinese (Traditional)
+        3 => Some(EUC_KR),          // Korean
+        7 => Some(X_MAC_CYRILLIC),  // Russian
+        25 => Some(GBK),            // Chinese (Simplified), EUC-CN is a subset of GBK
+        _ => None,
+    }
+}
+
+/// Windows encoding IDs 2-5 (6, Johab, is decoded separately)
+fn windows_legacy_encoding(encoding: u16) -> Option<&'static Encoding> {
+    match encoding {
+        2 => Some(SHIFT_JIS), // ShiftJIS
+        3 => Some(GBK),       // PRC (GB2312)
+        4 => Some(BIG5),      // Big5
+        5 => Some(EUC_KR),    // Wansung
+        _ => None,
+    }
+}
+
+/// Decode without replacement characters; malformed input yields None
+fn decode_strict(encoding: &'static Encoding, bytes: &[u8]) -> Option<String> {
+    let (decoded, had_errors) = encoding.decode_without_bom_handling(bytes);
+    if had_errors {
+        None
+    } else {
+        Some(decoded.into_owned())
+    }
+}
+
+/// Legacy Windows names are usually stored as 16-bit units with a zero high byte for
+/// single-byte characters; drop those to get the multi-byte string. Fonts that store the
+/// plain multi-byte string contain no zero bytes, so this leaves them unchanged.
+fn unpack_wide_bytes(bytes: &[u8]) -> Vec<u8> {
+    if !bytes.len().is_multiple_of(2) {
+        return bytes.to_vec();
+    }
+    let mut packed = Vec::with_capacity(bytes.len());
+    for unit in bytes.chunks_exact(2) {
+        if unit[0] != 0 {
+            packed.push(unit[0]);
+        }
+        packed.push(unit[1]);
+    }
+    packed
+}
+
+/// Decode Johab (KS X 1001 annex 3). Only ASCII and the algorithmic Hangul syllable
+/// area are supported; hanja and symbols would need the full mapping table.
+fn decode_johab(bytes: &[u8]) -> Option<String> {
+    // 5-bit Johab field values to Unicode jamo indices (None = fill / unused)
+    fn initial(v: u16) -> Option<u32> {
+        (2..=20).contains(&v).then(|| (v - 2) as u32)
+    }
+    fn medial(v: u16) -> Option<u32> {
+        match v {
+            3..=7 => Some((v - 3) as u32),
+            10..=15 => Some((v - 5) as u32),
+            18..=23 => Some((v - 7) as u32),
+            26..=29 => Some((v - 9) as u32),
+            _ => None,
+        }
+    }
+    fn final_(v: u16) -> Option<u32> {
+        match v {
+            1 => Some(0),
+            2..=17 => Some((v - 1) as u32),
+            19..=29 => Some((v - 2) as u32),
+            _ => None,
+        }
+    }
+
+    let mut result = String::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let lead = bytes[i];
+        if lead < 0x80 {
+            result.push(lead as char);
+            i += 1;
+            continue;
+        }
+        let trail = *bytes.get(i + 1)?;
+        let code = u16::from_be_bytes([lead, trail]);
+        let syllable = 0xAC00
+            + (initial((code >> 10) & 0x1F)? * 21 + medial((code >> 5) & 0x1F)?) * 28
+            + final_(code & 0x1F)?;
+        result.push(char::from_u32(syllable)?);
+        i += 2;
+    }
+    Some(result)
+}
+
+/// Macintosh language codes to BCP 47 tags
+/// see https://learn.microsoft.com/typography/opentype/spec/name#macintosh-language-ids
+pub fn mac_language_tag(language_id: u16) -> Option<&'static str> {
+    const TAGS: &[&str] = &[
+        "en", "fr", "de", "it", "nl", "sv", "es", "da", "pt", "nb", // 0-9
+        "he", "ja", "ar", "fi", "el", "is", "mt", "tr", "hr", "zh-Hant", // 10-19
+        "ur", "hi", "th", "ko", "lt", "pl", "hu", "et", "lv", "se", // 20-29
+        "fo", "fa", "ru", "zh-Hans", "nl-BE", "ga", "sq", "ro", "cs", "sk", // 30-39
+        "sl", "yi", "sr", "mk", "bg", "uk", "be", "uz", "kk", "az-Cyrl", // 40-49
+        "az-Arab", "hy", "ka", "ro-MD", "ky", "tg", "tk", "mn-Mong", "mn-Cyrl", "ps", // 50-59
+        "ku", "ks", "sd", "bo", "ne", "sa", "mr", "bn", "as", "gu", // 60-69
+        "pa", "or", "ml", "kn", "ta", "te", "si", "my", "km", "lo", // 70-79
+        "vi", "id", "tl", "ms", "ms-Arab", "am", "ti", "om", "so", "sw", // 80-89
+        "rw", "rn", "ny", "mg", "eo", // 90-94
+    ];
+    const TAGS_128: &[&str] = &[
+        "cy", "eu", "ca", "la", "qu", "gn", "ay", "tt", "ug", "dz", // 128-137
+        "jv", "su", "gl", "af", "br", "iu", "gd", "gv", "ga", "to", // 138-147
+        "el-polyton", "kl", "az-Latn", // 148-150
+    ];
+
+    match language_id {
+        0..=94 => TAGS.get(language_id as usize).copied(),
+        128..=150 => TAGS_128.get((language_id - 128) as usize).copied(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_mac_roman() {
+        // "Café" with é = 0x8E in Mac Roman
+        assert_eq!(decode_name_bytes(1, 0, b"Caf\x8E"), Some("Café".to_string()));
+    }
+
+    #[test]
+    fn test_decode_mac_japanese() {
+        // "ゴシック" in Shift_JIS
+        let bytes = [0x83, 0x53, 0x83, 0x56, 0x83, 0x62, 0x83, 0x4E];
+        assert_eq!(decode_name_bytes(1, 1, &bytes), Some("ゴシック".to_string()));
+    }
+
+    #[test]
+    fn test_decode_windows_prc_wide_units() {
+        // "宋体 A" in GB2312 stored as 16-bit units: 0xCBCE 0xCCE5 0x0020 0x0041
+        let bytes = [0xCB, 0xCE, 0xCC, 0xE5, 0x00, 0x20, 0x00, 0x41];
+        assert_eq!(decode_name_bytes(3, 3, &bytes), Some("宋体 A".to_string()));
+    }
+
+    #[test]
+    fn test_decode_windows_big5_plain_bytes() {
+        // "細明體" in Big5 stored as a plain multi-byte string
+        let bytes = [0xB2, 0xD3, 0xA9, 0xFA, 0xC5, 0xE9];
+        assert_eq!(decode_name_bytes(3, 4, &bytes), Some("細明體".to_string()));
+    }
+
+    #[test]
+    fn test_decode_johab_hangul() {
+        // 가 = 0x8861, 한 = 0xD065 in Johab
+        assert_eq!(decode_name_bytes(3, 6, &[0x88, 0x61, 0xD0, 0x65]), Some("가한".to_string()));
+        // Hanja area is not supported
+        assert_eq!(decode_johab(&[0xE0, 0x31]), None);
+    }
+
+    #[test]
+    fn test_unsupported_encoding() {
+        assert_eq!(decode_name_bytes(1, 4, b"abc"), None); // Mac Arabic
+        assert_eq!(decode_name_bytes(2, 0, b"abc"), None); // ISO platform
+    }
+
+    #[test]
+    fn test_mac_language_tag() {
+        assert_eq!(mac_language_tag(0), Some("en"));
+        assert_eq!(mac_language_tag(11), Some("ja"));
+        assert_eq!(mac_language_tag(19), Some("zh-Hant"));
+        assert_eq!(mac_language_tag(23), Some("ko"));
+        assert_eq!(mac_language_tag(33), Some("zh-Hans"));
+        assert_eq!(mac_language_tag(150), Some("az-Latn"));
+        assert_eq!(mac_language_tag(100), None);
+    }
+}
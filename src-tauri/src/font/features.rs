@@ -0,0 +1,286 @@
+//! GSUB/GPOS feature listing by script and language system
+use super::raw::{self, read_u16};
+use super::scanner::FontScanner;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use ttf_parser::{Face, Tag};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LayoutTableKind {
+    Gsub,
+    Gpos,
+}
+
+/// A feature tag with its registered name and the font's own UI label
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutFeature {
+    pub tag: String,
+    pub name: Option<String>,    // 注册特性的友好名称 (如 "Small Capitals")
+    pub ui_name: Option<String>, // ssXX/cvXX featureParams 指向的 name 记录 (字体自定义名称)
+    pub tables: Vec<LayoutTableKind>,
+}
+
+/// Features available to one script and language system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageSystemFeatures {
+    pub script: String,   // OpenType script 标签 (如 "latn", "hani")
+    pub language: String, // OpenType language 标签，默认语言系统为 "dflt"
+    pub gsub: Vec<String>,
+    pub gpos: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LayoutFeatures {
+    pub features: Vec<LayoutFeature>,
+    pub language_systems: Vec<LanguageSystemFeatures>,
+}
+
+/// Friendly names of commonly used registered features; ssXX and cvXX are handled separately
+const FEATURE_NAMES: &[(&str, &str)] = &[
+    ("aalt", "Access All Alternates"),
+    ("abvm", "Above-base Mark Positioning"),
+    ("abvs", "Above-base Substitutions"),
+    ("afrc", "Alternative Fractions"),
+    ("akhn", "Akhand"),
+    ("blwm", "Below-base Mark Positioning"),
+    ("blws", "Below-base Substitutions"),
+    ("c2pc", "Petite Capitals From Capitals"),
+    ("c2sc", "Small Capitals From Capitals"),
+    ("calt", "Contextual Alternates"),
+    ("case", "Case-Sensitive Forms"),
+    ("ccmp", "Glyph Composition / Decomposition"),
+    ("cpsp", "Capital Spacing"),
+    ("cswh", "Contextual Swash"),
+    ("curs", "Cursive Positioning"),
+    ("dist", "Distances"),
+    ("dlig", "Discretionary Ligatures"),
+    ("dnom", "Denominators"),
+    ("expt", "Expert Forms"),
+    ("fina", "Terminal Forms"),
+    ("frac", "Fractions"),
+    ("fwid", "Full Widths"),
+    ("halt", "Alternate Half Widths"),
+    ("hist", "Historical Forms"),
+    ("hkna", "Horizontal Kana Alternates"),
+    ("hlig", "Historical Ligatures"),
+    ("hngl", "Hangul"),
+    ("hwid", "Half Widths"),
+    ("init", "Initial Forms"),
+    ("isol", "Isolated Forms"),
+    ("ital", "Italics"),
+    ("jp04", "JIS2004 Forms"),
+    ("jp78", "JIS78 Forms"),
+    ("jp83", "JIS83 Forms"),
+    ("jp90", "JIS90 Forms"),
+    ("kern", "Kerning"),
+    ("lfbd", "Left Bounds"),
+    ("liga", "Standard Ligatures"),
+    ("lnum", "Lining Figures"),
+    ("locl", "Localized Forms"),
+    ("mark", "Mark Positioning"),
+    ("medi", "Medial Forms"),
+    ("mkmk", "Mark to Mark Positioning"),
+    ("nalt", "Alternate Annotation Forms"),
+    ("nlck", "NLC Kanji Forms"),
+    ("numr", "Numerators"),
+    ("onum", "Oldstyle Figures"),
+    ("opbd", "Optical Bounds"),
+    ("ordn", "Ordinals"),
+    ("ornm", "Ornaments"),
+    ("palt", "Proportional Alternate Widths"),
+    ("pcap", "Petite Capitals"),
+    ("pkna", "Proportional Kana"),
+    ("pnum", "Proportional Figures"),
+    ("pwid", "Proportional Widths"),
+    ("qwid", "Quarter Widths"),
+    ("rand", "Randomize"),
+    ("rclt", "Required Contextual Alternates"),
+    ("rlig", "Required Ligatures"),
+    ("rtbd", "Right Bounds"),
+    ("rtla", "Right-to-left Alternates"),
+    ("rtlm", "Right-to-left Mirrored Forms"),
+    ("ruby", "Ruby Notation Forms"),
+    ("salt", "Stylistic Alternates"),
+    ("sinf", "Scientific Inferiors"),
+    ("size", "Optical Size"),
+    ("smcp", "Small Capitals"),
+    ("smpl", "Simplified Forms"),
+    ("subs", "Subscript"),
+    ("sups", "Superscript"),
+    ("swsh", "Swash"),
+    ("titl", "Titling"),
+    ("tnam", "Traditional Name Forms"),
+    ("tnum", "Tabular Figures"),
+    ("trad", "Traditional Forms"),
+    ("twid", "Third Widths"),
+    ("unic", "Unicase"),
+    ("valt", "Alternate Vertical Metrics"),
+    ("vert", "Vertical Alternates"),
+    ("vhal", "Alternate Vertical Half Metrics"),
+    ("vkna", "Vertical Kana Alternates"),
+    ("vkrn", "Vertical Kerning"),
+    ("vpal", "Proportional Alternate Vertical Metrics"),
+    ("vrt2", "Vertical Alternates and Rotation"),
+    ("vrtr", "Vertical Alternates for Rotation"),
+    ("zero", "Slashed Zero"),
+];
+
+/// Every GSUB/GPOS feature tag of a face, sorted and deduplicated
+pub fn layout_feature_tags(face: &Face) -> Vec<String> {
+    let tables = face.tables();
+    let tags: BTreeSet<String> = [tables.gsub, tables.gpos]
+        .into_iter()
+        .flatten()
+        .flat_map(|table| table.features.into_iter().map(|feature| tag_string(feature.tag)))
+        .collect();
+    tags.into_iter().collect()
+}
+
+/// Read the features and language systems of a font file
+pub fn read_layout_features(path: String, face_index: u32) -> Result<LayoutFeatures, String> {
+    raw::with_face(&path, face_index, layout_features)
+}
+
+/// Features with names plus the features each script/language system enables
+pub fn layout_features(face: &Face) -> LayoutFeatures {
+    let tables = face.tables();
+    let mut features: BTreeMap<String, LayoutFeature> = BTreeMap::new();
+    let mut systems: BTreeMap<(String, String), [BTreeSet<String>; 2]> = BTreeMap::new();
+
+    for (kind, table, tag) in [
+        (LayoutTableKind::Gsub, tables.gsub, b"GSUB"),
+        (LayoutTableKind::Gpos, tables.gpos, b"GPOS"),
+    ] {
+        let Some(table) = table else {
+            continue;
+        };
+        let slot = kind as usize;
+
+        for (index, feature) in table.features.into_iter().enumerate() {
+            let tag_name = tag_string(feature.tag);
+            let entry = features.entry(tag_name.clone()).or_insert_with(|| LayoutFeature {
+                name: feature_name(&tag_name),
+                tag: tag_name,
+                ui_name: None,
+                tables: Vec::new(),
+            });
+            if !entry.tables.contains(&kind) {
+                entry.tables.push(kind);
+            }
+            if entry.ui_name.is_none() && has_ui_name_params(&entry.tag) {
+                entry.ui_name = raw::table(face, tag)
+                    .and_then(|data| feature_ui_name_id(data, index))
+                    .and_then(|name_id| FontScanner::extract_name(face, name_id));
+            }
+        }
+
+        for script in table.scripts {
+            let languages = script.default_language.into_iter().chain(script.languages);
+            for language in languages {
+                let tags = language
+                    .required_feature
+                    .into_iter()
+                    .chain(language.feature_indices)
+                    .filter_map(|index| table.features.get(index))
+                    .map(|feature| tag_string(feature.tag));
+                systems
+                    .entry((tag_string(script.tag), tag_string(language.tag)))
+                    .or_default()[slot]
+                    .extend(tags);
+            }
+        }
+    }
+
+    LayoutFeatures {
+        features: features.into_values().collect(),
+        language_systems: systems
+            .into_iter()
+            .map(|((script, language), [gsub, gpos])| LanguageSystemFeatures {
+                script,
+                language,
+                gsub: gsub.into_iter().collect(),
+                gpos: gpos.into_iter().collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Tags are space padded ("KUR "); the padding is noise in the UI
+fn tag_string(tag: Tag) -> String {
+    tag.to_string().trim_end().to_string()
+}
+
+/// Registered name of a feature tag
+pub fn feature_name(tag: &str) -> Option<String> {
+    let numbered = |prefix: &str| {
+        tag.strip_prefix(prefix)
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|&n| n > 0)
+    };
+    if let Some(n) = numbered("ss") {
+        return Some(format!("Stylistic Set {}", n));
+    }
+    if let Some(n) = numbered("cv") {
+        return Some(format!("Character Variant {}", n));
+    }
+    FEATURE_NAMES
+        .iter()
+        .find(|(registered, _)| *registered == tag)
+        .map(|(_, name)| name.to_string())
+}
+
+/// Stylistic sets and character variants carry a UI label name ID in their featureParams
+fn has_ui_name_params(tag: &str) -> bool {
+    (tag.starts_with("ss") || tag.starts_with("cv"))
+        && tag.get(2..).is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// FeatureList → Feature → featureParams; ssXX params are (version, uiNameID) and cvXX
+/// params start with (format, featUiLabelNameID), so the name ID is at offset 2 in both
+fn feature_ui_name_id(table: &[u8], feature_index: usize) -> Option<u16> {
+    let feature_list = read_u16(table, 6)? as usize;
+    if feature_index >= read_u16(table, feature_list)? as usize {
+        return None;
+    }
+    let record = feature_list + 2 + feature_index * 6;
+    let feature = feature_list + read_u16(table, record + 4)? as usize;
+    let params = read_u16(table, feature)? as usize;
+    if params == 0 {
+        return None;
+    }
+    read_u16(table, feature + params + 2).filter(|&name_id| name_id > 255)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_name() {
+        assert_eq!(feature_name("smcp").as_deref(), Some("Small Capitals"));
+        assert_eq!(feature_name("ss03").as_deref(), Some("Stylistic Set 3"));
+        assert_eq!(feature_name("cv11").as_deref(), Some("Character Variant 11"));
+        assert_eq!(feature_name("ss00"), None);
+        assert_eq!(feature_name("xxxx"), None);
+    }
+
+    #[test]
+    fn test_feature_ui_name_id() {
+        // GSUB header, FeatureList with one ss01 feature whose params point to name ID 256
+        let table = [
+            0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x00, // header, FeatureList at 10
+            0x00, 0x01, b's', b's', b'0', b'1', 0x00, 0x08, // FeatureList: 1 record, feature at +8
+            0x00, 0x04, 0x00, 0x00, // Feature: params at +4, no lookups
+            0x00, 0x00, 0x01, 0x00, // FeatureParams: version 0, uiNameID 256
+        ];
+        assert_eq!(feature_ui_name_id(&table, 0), Some(256));
+        assert_eq!(feature_ui_name_id(&table, 1), None);
+    }
+
+    #[test]
+    fn test_feature_names_are_sorted_and_unique() {
+        for pair in FEATURE_NAMES.windows(2) {
+            assert!(pair[0].0 < pair[1].0, "{} before {}", pair[0].0, pair[1].0);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! `@font-face` rules for every member of a family, for copying the font files into a web
+//! project
+use super::coverage::unicode_codepoints;
+use super::matching::FaceRanges;
+use super::models::{FontFormat, FontInfo};
+use super::raw;
+use super::state::FontState;
+use super::style_name::FontSlope;
+use std::path::Path;
+use ttf_parser::Face;
+
+/// Gaps of at most this many unmapped code points are bridged to keep unicode-range short
+const UNICODE_RANGE_GAP: u32 = 16;
+
+/// `@font-face` rules for the members of `family_name`, in style matrix order
+pub fn generate_font_face_css(state: &FontState, family_name: &str) -> Result<String, String> {
+    let family = state
+        .get_css_font_family(family_name)
+        .ok_or_else(|| format!("Font family not found: {}", family_name))?;
+
+    let rules = family
+        .styles
+        .iter()
+        .filter_map(|style| state.get_font(&style.font_id))
+        .map(|font| raw::with_face(&font.path, font.face_index, |face| font_face_rule(&family.name, font, face)))
+        .collect::<Result<Vec<String>, String>>()?;
+    Ok(rules.join("\n"))
+}
+
+fn font_face_rule(family: &str, font: &FontInfo, face: &Face) -> String {
+    let mut ranges = FaceRanges::of(font);
+    // Static oblique faces carry their real angle in post.italicAngle (counter-clockwise)
+    let has_slnt = font.variation_axes.iter().any(|axis| axis.tag == "slnt");
+    let italic_angle = face.italic_angle().unwrap_or(0.0);
+    if font.style_attributes.slope == FontSlope::Oblique && !has_slnt && italic_angle != 0.0 {
+        ranges.oblique = Some((-italic_angle, -italic_angle));
+    }
+
+    let mut descriptors = vec![
+        format!("font-family: {}", css_string(family)),
+        format!("src: {}", sources(font).join(",\n       ")),
+        format!("font-weight: {}", css_range(ranges.weight, "")),
+        format!("font-style: {}", font_style(&ranges)),
+        format!("font-stretch: {}", css_range(ranges.stretch, "%")),
+        "font-display: swap".to_string(),
+    ];
+
+    // C0 controls are mapped by some fonts but never rendered
+    let codepoints: Vec<u32> = unicode_codepoints(face).into_iter().filter(|&c| c >= 0x20).collect();
+    let unicode_range = unicode_range(&codepoints);
+    if !unicode_range.is_empty() {
+        descriptors.push(format!("unicode-range: {}", unicode_range));
+    }
+
+    // Pin the line metrics so every platform lays the face out like its hhea/typo metrics
+    let units_per_em = face.units_per_em() as f32;
+    if units_per_em > 0.0 {
+        let percent = |value: i16| format!("{}%", css_number(value as f32 / units_per_em * 100.0));
+        descriptors.push(format!("ascent-override: {}", percent(face.ascender())));
+        descriptors.push(format!("descent-override: {}", percent(face.descender().saturating_abs())));
+        descriptors.push(format!("line-gap-override: {}", percent(face.line_gap())));
+    }
+
+    let body: String = descriptors.iter().map(|d| format!("  {};\n", d)).collect();
+    format!("/* {} */\n@font-face {{\n{}}}\n", font.full_name, body)
+}
+
+/// local() by full and PostScript name, then the file itself next to the stylesheet.
+/// CSS cannot pick a face inside a collection and browsers load face 0, so later
+/// collection members only get local(); serve them from an extracted or subset file.
+fn sources(font: &FontInfo) -> Vec<String> {
+    let mut sources: Vec<String> = Vec::new();
+    for name in [&font.full_name, &font.postscript_name] {
+        let source = format!("local({})", css_string(name));
+        if !name.is_empty() && !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+
+    let file_name = Path::new(&font.path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if font.format == FontFormat::TrueTypeCollection && font.face_index != 0 {
+        return sources;
+    }
+    let format = match font.format {
+        FontFormat::TrueType => "truetype",
+        FontFormat::OpenType => "opentype",
+        FontFormat::TrueTypeCollection => "collection",
+        FontFormat::Woff => "woff",
+        FontFormat::Woff2 => "woff2",
+    };
+    sources.push(format!("url({}) format(\"{}\")", css_string(&file_name), format));
+    sources
+}
+
+fn font_style(ranges: &FaceRanges) -> String {
+    match ranges.oblique {
+        None => "italic".to_string(),
+        Some((0.0, 0.0)) => "normal".to_string(),
+        Some((min, max)) if min == max => format!("oblique {}deg", css_number(min)),
+        Some((min, max)) => format!("oblique {}deg {}deg", css_number(min), css_number(max)),
+    }
+}
+
+/// A single value, or "min max" for variable fonts
+fn css_range((min, max): (f32, f32), unit: &str) -> String {
+    if min == max {
+        format!("{}{}", css_number(min), unit)
+    } else {
+        format!("{}{} {}{}", css_number(min), unit, css_number(max), unit)
+    }
+}
+
+/// Round to two decimals and drop trailing zeros
+fn css_number(value: f32) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
+
+fn css_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Sorted code points as `U+XXXX-YYYY` ranges, bridging short gaps
+fn unicode_range(codepoints: &[u32]) -> String {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for &codepoint in codepoints {
+        match ranges.last_mut() {
+            Some((_, end)) if codepoint <= *end + UNICODE_RANGE_GAP + 1 => *end = codepoint,
+            _ => ranges.push((codepoint, codepoint)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(start, end)| {
+            if start == end {
+                format!("U+{:X}", start)
+            } else {
+                format!("U+{:X}-{:X}", start, end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::state::tests::create_test_font;
+
+    #[test]
+    fn test_unicode_range() {
+        assert_eq!(unicode_range(&[]), "");
+        assert_eq!(unicode_range(&[0x41]), "U+41");
+        // 0x5B-0x60 is bridged, 0x7F-0xA0 is too long a gap
+        let mut codepoints: Vec<u32> = (0x41..=0x5A).chain(0x61..=0x7A).collect();
+        codepoints.push(0xA1);
+        assert_eq!(unicode_range(&codepoints), "U+41-7A, U+A1");
+    }
+
+    #[test]
+    fn test_collection_sources() {
+        let mut font = create_test_font("1", "Microsoft YaHei", 400);
+        font.full_name = "Microsoft YaHei UI".to_string();
+        font.postscript_name = "MicrosoftYaHeiUI".to_string();
+        font.path = r"C:\Windows\Fonts\msyh.ttc".to_string();
+        font.format = FontFormat::TrueTypeCollection;
+        font.face_index = 1;
+        assert_eq!(sources(&font), vec!["local(\"Microsoft YaHei UI\")", "local(\"MicrosoftYaHeiUI\")"]);
+
+        // Face 0 is the one browsers load from the collection file
+        font.face_index = 0;
+        assert!(sources(&font).last().unwrap().ends_with("format(\"collection\")"));
+    }
+
+    #[test]
+    fn test_descriptor_values() {
+        assert_eq!(css_range((400.0, 400.0), ""), "400");
+        assert_eq!(css_range((75.0, 112.5), "%"), "75% 112.5%");
+        assert_eq!(css_number(90.5469), "90.55");
+        assert_eq!(css_number(-0.001), "0");
+        assert_eq!(css_string("Say \"Hi\""), "\"Say \\\"Hi\\\"\"");
+
+        let upright = FaceRanges { weight: (400.0, 400.0), stretch: (100.0, 100.0), italic: false, oblique: Some((0.0, 0.0)) };
+        assert_eq!(font_style(&upright), "normal");
+        assert_eq!(font_style(&FaceRanges { italic: true, oblique: None, ..upright }), "italic");
+        assert_eq!(font_style(&FaceRanges { oblique: Some((0.0, 10.0)), ..upright }), "oblique 0deg 10deg");
+    }
+}
@@ -0,0 +1,405 @@
+//! Structural integrity checks: table checksums, bounds, overlaps, loca/glyf and cmap
+use super::raw::{self, read_u16, read_u32};
+use serde::{Deserialize, Serialize};
+use ttf_parser::Face;
+
+/// head.checkSumAdjustment is chosen so the whole file sums to this value
+const CHECKSUM_MAGIC: u32 = 0xB1B0_AFBA;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HealthCheck {
+    TableChecksum,      // 表目录中的校验和
+    ChecksumAdjustment, // head.checkSumAdjustment (整个文件)
+    TableBounds,        // 表超出文件范围或未按 4 字节对齐
+    TableOverlap,       // 表数据互相重叠
+    LocaGlyf,           // loca 偏移与 glyf 长度一致性
+    Cmap,               // cmap 子表与字形索引
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthIssue {
+    pub severity: Severity,
+    pub check: HealthCheck,
+    pub table: Option<String>,
+    pub message: String,
+}
+
+/// Per-font health report
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FontHealth {
+    pub worst: Option<Severity>, // 最严重的问题级别，无问题为 None
+    pub issues: Vec<HealthIssue>,
+}
+
+impl FontHealth {
+    fn push(&mut self, severity: Severity, check: HealthCheck, table: Option<&str>, message: String) {
+        self.worst = self.worst.max(Some(severity));
+        self.issues.push(HealthIssue {
+            severity,
+            check,
+            table: table.map(str::to_string),
+            message,
+        });
+    }
+}
+
+/// Run every integrity check on a face
+pub fn check_health(face: &Face) -> FontHealth {
+    let mut health = FontHealth::default();
+    check_table_directory(face, &mut health);
+    check_loca_glyf(face, &mut health);
+    check_cmap(face, &mut health);
+    health
+}
+
+/// Sum of big-endian u32 words, zero-padding the last word
+fn table_checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+fn check_table_directory(face: &Face, health: &mut FontHealth) {
+    let raw_face = face.raw_face();
+    let data = raw_face.data;
+    let mut ranges = Vec::new();
+
+    for record in raw_face.table_records {
+        let tag = record.tag.to_string();
+        let start = record.offset as usize;
+        let end = start + record.length as usize;
+
+        let Some(table) = data.get(start..end) else {
+            health.push(
+                Severity::Error,
+                HealthCheck::TableBounds,
+                Some(&tag),
+                format!("Table ends at {} but the file is only {} bytes", end, data.len()),
+            );
+            continue;
+        };
+        if !start.is_multiple_of(4) {
+            health.push(
+                Severity::Warning,
+                HealthCheck::TableBounds,
+                Some(&tag),
+                format!("Table offset {} is not 4-byte aligned", start),
+            );
+        }
+
+        // The head checksum is computed with checkSumAdjustment set to zero
+        let mut checksum = table_checksum(table);
+        if tag == "head" {
+            checksum = checksum.wrapping_sub(read_u32(table, 8).unwrap_or(0));
+        }
+        if checksum != record.check_sum {
+            health.push(
+                Severity::Warning,
+                HealthCheck::TableChecksum,
+                Some(&tag),
+                format!("Checksum is {:#010X}, directory says {:#010X}", checksum, record.check_sum),
+            );
+        }
+
+        ranges.push((start, end, tag));
+    }
+
+    // Faces in a collection may share tables, so identical ranges are allowed
+    ranges.sort();
+    for pair in ranges.windows(2) {
+        let ((start_a, end_a, tag_a), (start_b, end_b, tag_b)) = (&pair[0], &pair[1]);
+        if start_b < end_a && (start_a, end_a) != (start_b, end_b) {
+            health.push(
+                Severity::Error,
+                HealthCheck::TableOverlap,
+                Some(tag_b),
+                format!("Table overlaps {} ({}..{} and {}..{})", tag_a, start_a, end_a, start_b, end_b),
+            );
+        }
+    }
+
+    check_checksum_adjustment(face, health);
+}
+
+/// checkSumAdjustment only covers single-font files; collections share tables between faces
+fn check_checksum_adjustment(face: &Face, health: &mut FontHealth) {
+    let data = face.raw_face().data;
+    if data.starts_with(b"ttcf") {
+        return;
+    }
+    let Some(adjustment) = raw::table(face, b"head").and_then(|head| read_u32(head, 8)) else {
+        return;
+    };
+
+    let expected = CHECKSUM_MAGIC.wrapping_sub(table_checksum(data).wrapping_sub(adjustment));
+    if adjustment != expected {
+        health.push(
+            Severity::Warning,
+            HealthCheck::ChecksumAdjustment,
+            Some("head"),
+            format!("checkSumAdjustment is {:#010X}, expected {:#010X}", adjustment, expected),
+        );
+    }
+}
+
+fn check_loca_glyf(face: &Face, health: &mut FontHealth) {
+    let Some(glyf) = raw::table(face, b"glyf") else {
+        return;
+    };
+    let Some(loca) = raw::table(face, b"loca") else {
+        health.push(Severity::Error, HealthCheck::LocaGlyf, Some("loca"), "glyf table without loca".to_string());
+        return;
+    };
+    let long_format = raw::table(face, b"head").and_then(|head| read_u16(head, 50)) == Some(1);
+    let entry_size = if long_format { 4 } else { 2 };
+    let num_glyphs = face.number_of_glyphs() as usize;
+
+    let expected_len = (num_glyphs + 1) * entry_size;
+    if loca.len() < expected_len {
+        health.push(
+            Severity::Error,
+            HealthCheck::LocaGlyf,
+            Some("loca"),
+            format!("loca has {} bytes, {} glyphs need {}", loca.len(), num_glyphs, expected_len),
+        );
+    } else if loca.len() > expected_len {
+        health.push(
+            Severity::Info,
+            HealthCheck::LocaGlyf,
+            Some("loca"),
+            format!("loca has {} trailing bytes", loca.len() - expected_len),
+        );
+    }
+
+    let offsets: Vec<usize> = (0..=num_glyphs)
+        .map_while(|i| {
+            if long_format {
+                read_u32(loca, i * 4).map(|v| v as usize)
+            } else {
+                read_u16(loca, i * 2).map(|v| v as usize * 2)
+            }
+        })
+        .collect();
+
+    if let Some(glyph) = offsets.windows(2).position(|pair| pair[1] < pair[0]) {
+        health.push(
+            Severity::Error,
+            HealthCheck::LocaGlyf,
+            Some("loca"),
+            format!("loca offsets decrease at glyph {}", glyph),
+        );
+    }
+    if let Some(&last) = offsets.last().filter(|&&last| last > glyf.len()) {
+        health.push(
+            Severity::Error,
+            HealthCheck::LocaGlyf,
+            Some("glyf"),
+            format!("loca points to offset {} but glyf is {} bytes", last, glyf.len()),
+        );
+    }
+}
+
+fn check_cmap(face: &Face, health: &mut FontHealth) {
+    let Some(data) = raw::table(face, b"cmap") else {
+        health.push(Severity::Error, HealthCheck::Cmap, Some("cmap"), "Missing cmap table".to_string());
+        return;
+    };
+
+    // Encoding records: platformID, encodingID, subtable offset (8 bytes each)
+    let num_tables = read_u16(data, 2).unwrap_or(0) as usize;
+    for i in 0..num_tables {
+        let record = 4 + i * 8;
+        let (Some(platform_id), Some(encoding_id), Some(offset)) =
+            (read_u16(data, record), read_u16(data, record + 2), read_u32(data, record + 4))
+        else {
+            health.push(
+                Severity::Error,
+                HealthCheck::Cmap,
+                Some("cmap"),
+                format!("cmap declares {} subtables but the record array is truncated", num_tables),
+            );
+            break;
+        };
+        if offset as usize >= data.len() {
+            health.push(
+                Severity::Error,
+                HealthCheck::Cmap,
+                Some("cmap"),
+                format!("Subtable {}/{} starts at {} past the end of cmap", platform_id, encoding_id, offset),
+            );
+        }
+    }
+
+    let Some(cmap) = face.tables().cmap else {
+        return;
+    };
+    let num_glyphs = face.number_of_glyphs();
+    let mut has_unicode = false;
+    let mut has_symbol = false;
+
+    for subtable in cmap.subtables {
+        has_unicode |= subtable.is_unicode();
+        has_symbol |= subtable.platform_id == ttf_parser::PlatformId::Windows && subtable.encoding_id == 0;
+
+        let mut out_of_range = 0usize;
+        subtable.codepoints(|c| {
+            if subtable.glyph_index(c).is_some_and(|id| id.0 >= num_glyphs) {
+                out_of_range += 1;
+            }
+        });
+        if out_of_range > 0 {
+            health.push(
+                Severity::Error,
+                HealthCheck::Cmap,
+                Some("cmap"),
+                format!(
+                    "Subtable {}/{} maps {} characters to glyphs beyond numGlyphs {}",
+                    subtable.platform_id as u16, subtable.encoding_id, out_of_range, num_glyphs
+                ),
+            );
+        }
+    }
+
+    if !has_unicode {
+        let (severity, message) = if has_symbol {
+            (Severity::Info, "Only a Windows Symbol cmap subtable")
+        } else {
+            (Severity::Warning, "No Unicode cmap subtable")
+        };
+        health.push(severity, HealthCheck::Cmap, Some("cmap"), message.to_string());
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_checksum() {
+        assert_eq!(table_checksum(&[0, 0, 0, 1, 0, 0, 0, 2]), 3);
+        // The last word is zero padded
+        assert_eq!(table_checksum(&[0, 0, 0, 1, 0x80]), 0x8000_0001);
+        assert_eq!(table_checksum(&[0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 2]), 1);
+    }
+
+    /// Minimal sfnt: head, hhea and maxp for `num_glyphs` glyphs plus `extra` tables, with
+    /// valid checksums. Tables are laid out in the given order after the directory.
+    pub(crate) fn build_font(num_glyphs: u16, extra: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut head = vec![0u8; 54];
+        head[0..4].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+        head[12..16].copy_from_slice(&0x5F0F_3CF5u32.to_be_bytes());
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+        let mut hhea = vec![0u8; 36];
+        hhea[0..4].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+        let mut maxp = vec![0x00, 0x00, 0x50, 0x00];
+        maxp.extend_from_slice(&num_glyphs.to_be_bytes());
+
+        let mut tables: Vec<(&[u8; 4], Vec<u8>)> = vec![(b"head", head), (b"hhea", hhea), (b"maxp", maxp)];
+        tables.extend(extra.iter().cloned());
+        tables.sort_by_key(|(tag, _)| **tag);
+
+        let mut font = vec![0, 1, 0, 0];
+        font.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+        font.extend_from_slice(&[0; 6]);
+        let mut offset = 12 + tables.len() * 16;
+        for (tag, table) in &tables {
+            font.extend_from_slice(*tag);
+            font.extend_from_slice(&table_checksum(table).to_be_bytes());
+            font.extend_from_slice(&(offset as u32).to_be_bytes());
+            font.extend_from_slice(&(table.len() as u32).to_be_bytes());
+            offset += table.len().div_ceil(4) * 4;
+        }
+        for (_, table) in &tables {
+            font.extend_from_slice(table);
+            font.resize(font.len().div_ceil(4) * 4, 0);
+        }
+        font
+    }
+
+    /// Overwrite the offset and length of a table directory record
+    fn patch_record(font: &mut [u8], tag: &[u8; 4], offset: u32, length: u32) {
+        let count = read_u16(font, 4).unwrap() as usize;
+        let record = (0..count).map(|i| 12 + i * 16).find(|&r| &font[r..r + 4] == tag).unwrap();
+        font[record + 8..record + 12].copy_from_slice(&offset.to_be_bytes());
+        font[record + 12..record + 16].copy_from_slice(&length.to_be_bytes());
+    }
+
+    fn run(font: &[u8], check: fn(&Face, &mut FontHealth)) -> FontHealth {
+        let face = Face::parse(font, 0).unwrap();
+        let mut health = FontHealth::default();
+        check(&face, &mut health);
+        health
+    }
+
+    #[test]
+    fn test_table_out_of_bounds() {
+        let mut font = build_font(1, &[(b"zzzz", vec![1; 8])]);
+        let offset = read_u32(&font, 12 + 3 * 16 + 8).unwrap();
+        patch_record(&mut font, b"zzzz", offset, 4096);
+        let health = run(&font, check_table_directory);
+        assert!(health.issues.iter().any(|issue| {
+            issue.check == HealthCheck::TableBounds && issue.severity == Severity::Error && issue.table.as_deref() == Some("zzzz")
+        }));
+    }
+
+    #[test]
+    fn test_overlapping_tables() {
+        let mut font = build_font(1, &[(b"aaaa", vec![1; 16]), (b"bbbb", vec![2; 16])]);
+        let aaaa = read_u32(&font, 12 + 8).unwrap();
+        patch_record(&mut font, b"bbbb", aaaa + 8, 16);
+        let health = run(&font, check_table_directory);
+        let overlap = health.issues.iter().find(|issue| issue.check == HealthCheck::TableOverlap).unwrap();
+        assert_eq!(overlap.table.as_deref(), Some("bbbb"));
+        assert_eq!(health.worst, Some(Severity::Error));
+
+        // A clean font only fails checkSumAdjustment, which build_font leaves at zero
+        let health = run(&build_font(1, &[]), check_table_directory);
+        assert!(health.issues.iter().all(|issue| issue.check == HealthCheck::ChecksumAdjustment));
+    }
+
+    #[test]
+    fn test_decreasing_loca() {
+        // Short loca offsets (in words) 0, 4, 2 for two glyphs
+        let loca = [0, 0, 0, 2, 0, 1].to_vec();
+        let font = build_font(2, &[(b"glyf", vec![0; 8]), (b"loca", loca)]);
+        let health = run(&font, check_loca_glyf);
+        assert!(health.issues.iter().any(|issue| issue.message == "loca offsets decrease at glyph 1"));
+
+        // Offsets past the end of glyf
+        let font = build_font(2, &[(b"glyf", vec![0; 8]), (b"loca", [0, 0, 0, 2, 0, 8].to_vec())]);
+        let health = run(&font, check_loca_glyf);
+        assert!(health.issues.iter().any(|issue| issue.table.as_deref() == Some("glyf")));
+    }
+
+    #[test]
+    fn test_cmap_glyph_out_of_range() {
+        // One (3, 1) format 6 subtable mapping 'A' to glyph 5
+        let cmap = [0, 0, 0, 1, 0, 3, 0, 1, 0, 0, 0, 12, 0, 6, 0, 12, 0, 0, 0, 0x41, 0, 1, 0, 5].to_vec();
+        let health = run(&build_font(2, &[(b"cmap", cmap.clone())]), check_cmap);
+        let issue = health.issues.iter().find(|issue| issue.check == HealthCheck::Cmap).unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert!(issue.message.contains("beyond numGlyphs 2"));
+
+        assert!(run(&build_font(6, &[(b"cmap", cmap)]), check_cmap).issues.is_empty());
+    }
+
+    #[test]
+    fn test_worst_severity() {
+        let mut health = FontHealth::default();
+        assert_eq!(health.worst, None);
+        health.push(Severity::Warning, HealthCheck::Cmap, None, String::new());
+        health.push(Severity::Info, HealthCheck::Cmap, None, String::new());
+        assert_eq!(health.worst, Some(Severity::Warning));
+        health.push(Severity::Error, HealthCheck::LocaGlyf, None, String::new());
+        assert_eq!(health.worst, Some(Severity::Error));
+    }
+}
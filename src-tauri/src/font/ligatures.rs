@@ -0,0 +1,372 @@
+//! Programming ligature detection by running GSUB calt/liga/dlig over code sequences
+use serde::{Deserialize, Serialize};
+use ttf_parser::gsub::{SingleSubstitution, SubstitutionSubtable};
+use ttf_parser::opentype_layout::{
+    ChainedContextLookup, ChainedSequenceRule, ContextLookup, LayoutTable, SequenceLookupRecord,
+};
+use ttf_parser::{Face, GlyphId, LazyArray16, Tag};
+
+/// Coding ligatures substituted by a face
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LigatureInfo {
+    pub has_programming_ligatures: bool,
+    pub sequences: Vec<String>, // 被 calt/liga/dlig 替换的编程符号序列 (如 "->", "!=")
+}
+
+/// Operator sequences that coding fonts typically turn into ligatures
+const CODE_SEQUENCES: &[&str] = &[
+    "->", "=>", "<-", "<=", ">=", "==", "!=", "===", "!==", "::", "&&", "||", "|>", "<|", "<>", "//", "/*", "*/",
+    "++", "<<", ">>", "=<<", ">>=", "<=>", "..=", "</", "/>", "</>",
+];
+
+/// Features that shapers enable by default (calt, liga) or that fonts use for optional coding ligatures (dlig)
+const LIGATURE_FEATURES: &[&[u8; 4]] = &[b"calt", b"liga", b"dlig"];
+
+/// Nested contextual lookups deeper than this are not followed
+const MAX_NESTING: usize = 8;
+
+/// Detect which code sequences the GSUB calt/liga/dlig lookups substitute
+pub fn detect_programming_ligatures(face: &Face) -> LigatureInfo {
+    let Some(gsub) = face.tables().gsub else {
+        return LigatureInfo::default();
+    };
+
+    // Lookups of all enabled features are applied together, in lookup list order
+    let mut lookups: Vec<u16> = gsub
+        .features
+        .into_iter()
+        .filter(|feature| LIGATURE_FEATURES.iter().any(|tag| feature.tag == Tag::from_bytes(tag)))
+        .flat_map(|feature| feature.lookup_indices)
+        .collect();
+    lookups.sort_unstable();
+    lookups.dedup();
+    if lookups.is_empty() {
+        return LigatureInfo::default();
+    }
+
+    let sequences: Vec<String> = CODE_SEQUENCES
+        .iter()
+        .filter(|sequence| {
+            let glyphs: Option<Vec<GlyphId>> = sequence.chars().map(|c| face.glyph_index(c)).collect();
+            glyphs.is_some_and(|glyphs| is_substituted(&gsub, &lookups, glyphs))
+        })
+        .map(|sequence| sequence.to_string())
+        .collect();
+
+    LigatureInfo {
+        has_programming_ligatures: !sequences.is_empty(),
+        sequences,
+    }
+}
+
+/// Run the lookups over the glyph run and report whether it was ligated: glyphs were
+/// merged, or every glyph was replaced (the spacer + ligature glyph scheme of coding
+/// fonts). Contextual alternates that swap only some glyphs do not count.
+fn is_substituted(gsub: &LayoutTable, lookups: &[u16], glyphs: Vec<GlyphId>) -> bool {
+    let mut buffer = glyphs.clone();
+    for &lookup in lookups {
+        let mut pos = 0;
+        while pos < buffer.len() {
+            pos += apply_lookup(gsub, lookup, &mut buffer, pos, 0).unwrap_or(1);
+        }
+    }
+    buffer.len() < glyphs.len()
+        || (buffer.len() == glyphs.len() && buffer.iter().zip(&glyphs).all(|(new, old)| new != old))
+}
+
+/// Apply the first matching subtable of a lookup at `pos`; returns how many glyphs to advance
+fn apply_lookup(gsub: &LayoutTable, index: u16, buffer: &mut Vec<GlyphId>, pos: usize, depth: usize) -> Option<usize> {
+    let lookup = gsub.lookups.get(index)?;
+    lookup
+        .subtables
+        .into_iter::<SubstitutionSubtable>()
+        .find_map(|subtable| apply_subtable(gsub, &subtable, buffer, pos, depth))
+}
+
+fn apply_subtable(
+    gsub: &LayoutTable,
+    subtable: &SubstitutionSubtable,
+    buffer: &mut Vec<GlyphId>,
+    pos: usize,
+    depth: usize,
+) -> Option<usize> {
+    let glyph = *buffer.get(pos)?;
+    let coverage_index = subtable.coverage().get(glyph);
+
+    match subtable {
+        SubstitutionSubtable::Single(single) => {
+            let index = coverage_index?;
+            buffer[pos] = match single {
+                SingleSubstitution::Format1 { delta, .. } => GlyphId((glyph.0 as i32 + *delta as i32) as u16),
+                SingleSubstitution::Format2 { substitutes, .. } => substitutes.get(index)?,
+            };
+            Some(1)
+        }
+        SubstitutionSubtable::Multiple(multiple) => {
+            let sequence = multiple.sequences.get(coverage_index?)?;
+            buffer.splice(pos..pos + 1, sequence.substitutes);
+            Some(sequence.substitutes.len() as usize)
+        }
+        SubstitutionSubtable::Ligature(ligature) => {
+            let set = ligature.ligature_sets.get(coverage_index?)?;
+            let ligature = set.into_iter().find(|ligature| {
+                match_forward(buffer, pos + 1, ligature.components.len(), |i, g| ligature.components.get(i) == Some(g))
+            })?;
+            buffer.splice(pos..pos + 1 + ligature.components.len() as usize, [ligature.glyph]);
+            Some(1)
+        }
+        SubstitutionSubtable::Context(context) => {
+            let (input_len, records) = match context {
+                ContextLookup::Format1 { sets, .. } => {
+                    let set = sets.get(coverage_index?)?;
+                    let rule = set.into_iter().find(|rule| {
+                        match_forward(buffer, pos + 1, rule.input.len(), |i, g| rule.input.get(i) == Some(g.0))
+                    })?;
+                    (rule.input.len() + 1, rule.lookups)
+                }
+                ContextLookup::Format2 { classes, sets, .. } => {
+                    coverage_index?;
+                    let set = sets.get(classes.get(glyph))?;
+                    let rule = set.into_iter().find(|rule| {
+                        match_forward(buffer, pos + 1, rule.input.len(), |i, g| rule.input.get(i) == Some(classes.get(g)))
+                    })?;
+                    (rule.input.len() + 1, rule.lookups)
+                }
+                ContextLookup::Format3 { coverages, lookups, .. } => {
+                    coverage_index?;
+                    let matched = match_forward(buffer, pos + 1, coverages.len(), |i, g| {
+                        coverages.get(i).is_some_and(|coverage| coverage.contains(g))
+                    });
+                    matched.then_some(())?;
+                    (coverages.len() + 1, *lookups)
+                }
+            };
+            Some(apply_context(gsub, input_len, records, buffer, pos, depth))
+        }
+        SubstitutionSubtable::ChainContext(context) => {
+            let (input_len, records) = match context {
+                ChainedContextLookup::Format1 { sets, .. } => {
+                    let set = sets.get(coverage_index?)?;
+                    let id = |g: GlyphId| g.0;
+                    let rule = set.into_iter().find(|rule| match_chain_rule(buffer, pos, rule, id, id, id))?;
+                    (rule.input.len() + 1, rule.lookups)
+                }
+                ChainedContextLookup::Format2 {
+                    backtrack_classes,
+                    input_classes,
+                    lookahead_classes,
+                    sets,
+                    ..
+                } => {
+                    coverage_index?;
+                    let set = sets.get(input_classes.get(glyph))?;
+                    let rule = set.into_iter().find(|rule| {
+                        match_chain_rule(
+                            buffer,
+                            pos,
+                            rule,
+                            |g| backtrack_classes.get(g),
+                            |g| input_classes.get(g),
+                            |g| lookahead_classes.get(g),
+                        )
+                    })?;
+                    (rule.input.len() + 1, rule.lookups)
+                }
+                ChainedContextLookup::Format3 {
+                    backtrack_coverages,
+                    input_coverages,
+                    lookahead_coverages,
+                    lookups,
+                    ..
+                } => {
+                    coverage_index?;
+                    let lookahead_start = pos + 1 + input_coverages.len() as usize;
+                    let matched = match_backward(buffer, pos, backtrack_coverages.len(), |i, g| {
+                        backtrack_coverages.get(i).is_some_and(|coverage| coverage.contains(g))
+                    }) && match_forward(buffer, pos + 1, input_coverages.len(), |i, g| {
+                        input_coverages.get(i).is_some_and(|coverage| coverage.contains(g))
+                    }) && match_forward(buffer, lookahead_start, lookahead_coverages.len(), |i, g| {
+                        lookahead_coverages.get(i).is_some_and(|coverage| coverage.contains(g))
+                    });
+                    matched.then_some(())?;
+                    (input_coverages.len() + 1, *lookups)
+                }
+            };
+            Some(apply_context(gsub, input_len, records, buffer, pos, depth))
+        }
+        SubstitutionSubtable::Alternate(_) | SubstitutionSubtable::ReverseChainSingle(_) => None,
+    }
+}
+
+/// Apply the nested lookups of a matched context rule. Returns the number of glyphs to skip:
+/// the matched input, adjusted for glyphs added or removed. Rules without records only
+/// block later rules.
+fn apply_context(
+    gsub: &LayoutTable,
+    input_len: u16,
+    records: LazyArray16<SequenceLookupRecord>,
+    buffer: &mut Vec<GlyphId>,
+    pos: usize,
+    depth: usize,
+) -> usize {
+    let len = buffer.len();
+    if depth < MAX_NESTING {
+        for record in records {
+            let at = pos + record.sequence_index as usize;
+            if at < buffer.len() {
+                apply_lookup(gsub, record.lookup_list_index, buffer, at, depth + 1);
+            }
+        }
+    }
+    (input_len as usize + buffer.len()).saturating_sub(len).max(1)
+}
+
+fn match_chain_rule(
+    buffer: &[GlyphId],
+    pos: usize,
+    rule: &ChainedSequenceRule,
+    backtrack_value: impl Fn(GlyphId) -> u16,
+    input_value: impl Fn(GlyphId) -> u16,
+    lookahead_value: impl Fn(GlyphId) -> u16,
+) -> bool {
+    let lookahead_start = pos + 1 + rule.input.len() as usize;
+    match_backward(buffer, pos, rule.backtrack.len(), |i, g| rule.backtrack.get(i) == Some(backtrack_value(g)))
+        && match_forward(buffer, pos + 1, rule.input.len(), |i, g| rule.input.get(i) == Some(input_value(g)))
+        && match_forward(buffer, lookahead_start, rule.lookahead.len(), |i, g| {
+            rule.lookahead.get(i) == Some(lookahead_value(g))
+        })
+}
+
+/// `len` glyphs starting at `start` satisfy `matches(index, glyph)`
+fn match_forward(buffer: &[GlyphId], start: usize, len: u16, matches: impl Fn(u16, GlyphId) -> bool) -> bool {
+    (0..len).all(|i| buffer.get(start + i as usize).is_some_and(|&g| matches(i, g)))
+}
+
+/// `len` glyphs before `pos`, nearest first, satisfy `matches(index, glyph)`
+fn match_backward(buffer: &[GlyphId], pos: usize, len: u16, matches: impl Fn(u16, GlyphId) -> bool) -> bool {
+    (0..len).all(|i| {
+        pos.checked_sub(i as usize + 1)
+            .and_then(|at| buffer.get(at))
+            .is_some_and(|&g| matches(i, g))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::health::tests::build_font;
+
+    const HYPHEN: u16 = 1;
+    const GREATER: u16 = 2;
+    const LIG: u16 = 10;
+    const ARROW: u16 = 11;
+    const HYPHEN_RAISED: u16 = 12;
+
+    fn be(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|value| value.to_be_bytes()).collect()
+    }
+
+    /// Coverage format 1 with a single glyph
+    fn coverage(glyph: u16) -> Vec<u8> {
+        be(&[1, 1, glyph])
+    }
+
+    /// Lookup table with one subtable
+    fn lookup(kind: u16, subtable: Vec<u8>) -> Vec<u8> {
+        [be(&[kind, 0, 1, 8]), subtable].concat()
+    }
+
+    /// Single substitution format 2 of one glyph
+    fn single(from: u16, to: u16) -> Vec<u8> {
+        lookup(1, [be(&[2, 8, 1, to]), coverage(from)].concat())
+    }
+
+    /// Ligature substitution format 1 with one ligature
+    fn ligature(first: u16, rest: &[u16], glyph: u16) -> Vec<u8> {
+        let ligature = [be(&[glyph, rest.len() as u16 + 1]), be(rest)].concat();
+        let set = [be(&[1, 4]), ligature].concat();
+        let coverage_offset = 8 + set.len() as u16;
+        lookup(4, [be(&[1, coverage_offset, 1, 8]), set, coverage(first)].concat())
+    }
+
+    /// Chained context format 3 with one glyph per position
+    fn chain(backtrack: &[u16], input: &[u16], lookahead: &[u16], records: &[(u16, u16)]) -> Vec<u8> {
+        let header_len = 10 + 2 * (backtrack.len() + input.len() + lookahead.len()) + 4 * records.len();
+        let offset = |i: usize| (header_len + 6 * i) as u16;
+        let mut glyphs = Vec::new();
+        let mut header = be(&[3]);
+        for list in [backtrack, input, lookahead] {
+            header.extend(be(&[list.len() as u16]));
+            for &glyph in list {
+                header.extend(be(&[offset(glyphs.len())]));
+                glyphs.push(glyph);
+            }
+        }
+        header.extend(be(&[records.len() as u16]));
+        for &(sequence_index, lookup_index) in records {
+            header.extend(be(&[sequence_index, lookup_index]));
+        }
+        let coverages = glyphs.into_iter().flat_map(coverage);
+        lookup(6, header.into_iter().chain(coverages).collect())
+    }
+
+    /// Font whose GSUB has no scripts or features, only the given lookups
+    fn font_with_lookups(lookups: &[Vec<u8>]) -> Vec<u8> {
+        let mut list = be(&[lookups.len() as u16]);
+        let mut offset = 2 + 2 * lookups.len();
+        for lookup in lookups {
+            list.extend(be(&[offset as u16]));
+            offset += lookup.len();
+        }
+        let gsub = [be(&[1, 0, 10, 12, 14, 0, 0]), list, lookups.concat()].concat();
+        build_font(20, &[(b"GSUB", gsub)])
+    }
+
+    fn substituted(data: &[u8], lookups: &[u16], glyphs: &[u16]) -> bool {
+        let face = Face::parse(data, 0).unwrap();
+        let gsub = face.tables().gsub.unwrap();
+        is_substituted(&gsub, lookups, glyphs.iter().map(|&g| GlyphId(g)).collect())
+    }
+
+    #[test]
+    fn test_ligature_substitution() {
+        let data = font_with_lookups(&[ligature(HYPHEN, &[GREATER], ARROW)]);
+        assert!(substituted(&data, &[0], &[HYPHEN, GREATER]));
+        assert!(!substituted(&data, &[0], &[HYPHEN, HYPHEN]));
+    }
+
+    #[test]
+    fn test_chained_single_substitutions() {
+        // Coding-font scheme: "-" before ">" becomes a spacer, ">" after the spacer the arrow
+        let data = font_with_lookups(&[
+            chain(&[], &[HYPHEN], &[GREATER], &[(0, 2)]),
+            chain(&[LIG], &[GREATER], &[], &[(0, 3)]),
+            single(HYPHEN, LIG),
+            single(GREATER, ARROW),
+        ]);
+        assert!(substituted(&data, &[0, 1], &[HYPHEN, GREATER]));
+        // The lookahead does not match
+        assert!(!substituted(&data, &[0, 1], &[HYPHEN, HYPHEN]));
+        // The backtrack does not match, so ">" is left alone
+        assert!(!substituted(&data, &[1], &[GREATER, GREATER]));
+        assert!(!substituted(&data, &[0], &[HYPHEN, GREATER]), "only the first glyph changed");
+    }
+
+    #[test]
+    fn test_contextual_alternate_is_not_ligature() {
+        // calt raising "-" in front of ">" without joining the pair
+        let data = font_with_lookups(&[chain(&[], &[HYPHEN], &[GREATER], &[(0, 1)]), single(HYPHEN, HYPHEN_RAISED)]);
+        assert!(!substituted(&data, &[0], &[HYPHEN, GREATER]));
+    }
+
+    #[test]
+    fn test_match_forward_backward() {
+        let buffer = [GlyphId(1), GlyphId(2), GlyphId(3)];
+        let expected = [GlyphId(2), GlyphId(3)];
+        assert!(match_forward(&buffer, 1, 2, |i, g| expected[i as usize] == g));
+        assert!(!match_forward(&buffer, 2, 2, |_, _| true), "runs past the end");
+        // Backtrack is matched nearest first
+        assert!(match_backward(&buffer, 2, 2, |i, g| [GlyphId(2), GlyphId(1)][i as usize] == g));
+        assert!(!match_backward(&buffer, 1, 2, |_, _| true), "runs past the start");
+    }
+}
@@ -0,0 +1,280 @@
+//! Metadata consistency lints over FontInfo and the raw OS/2 / head tables
+use super::health::Severity;
+use super::models::FontInfo;
+use super::raw::{self, read_u16};
+use super::state::FontState;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use ttf_parser::Face;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LintRule {
+    WeightClassOutOfRange,    // usWeightClass 不在 100-900
+    WeightNameMismatch,       // 样式名与字重不符 (如 "Bold" 却是 400)
+    ItalicBitMissing,         // 样式名含 Italic/Oblique 但 fsSelection/macStyle 未设置
+    ItalicBitUnexpected,      // 设置了 italic 位但样式名不含 Italic/Oblique
+    BoldBitMismatch,          // Name ID 2 为 Bold 但 fsSelection/macStyle 未设置 bold 位
+    InvalidPostScriptName,    // 缺失、含空格或非法字符、超过 63 字符
+    DuplicatePostScriptName,  // 不同字体家族使用相同的 PostScript 名称
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintWarning {
+    pub rule: LintRule,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl LintWarning {
+    fn new(rule: LintRule, severity: Severity, message: String) -> Self {
+        Self { rule, severity, message }
+    }
+}
+
+// OS/2 fsSelection bits
+const FS_ITALIC: u16 = 1 << 0;
+const FS_BOLD: u16 = 1 << 5;
+const FS_OBLIQUE: u16 = 1 << 9;
+// head macStyle bits
+const MAC_BOLD: u16 = 1 << 0;
+const MAC_ITALIC: u16 = 1 << 1;
+
+/// Lint a single face. Duplicate PostScript names need every font and are
+/// handled by `lint_duplicate_postscript_names`.
+pub fn lint_face(face: &Face, font: &FontInfo) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+    let os2 = raw::table(face, b"OS/2");
+    let weight_class = os2.and_then(|data| read_u16(data, 4));
+    let fs_selection = os2.and_then(|data| read_u16(data, 62)).unwrap_or(0);
+    let mac_style = raw::table(face, b"head").and_then(|data| read_u16(data, 44)).unwrap_or(0);
+
+    if let Some(weight_class) = weight_class.filter(|w| !(100..=900).contains(w)) {
+        warnings.push(LintWarning::new(
+            LintRule::WeightClassOutOfRange,
+            Severity::Warning,
+            format!("usWeightClass is {}, expected 100-900", weight_class),
+        ));
+    }
+
+    // Variable fonts name their default instance, so the weight comes from fvar
+    let style = font
+        .metadata
+        .typographic_subfamily
+        .as_deref()
+        .unwrap_or(&font.style);
+    // The name may come from CJK or W-number notations, which is why it is inferred once at scan time
+    if let (Some(expected), Some(declared)) = (font.style_attributes.name_weight, weight_class) {
+        if !font.is_variable && declared.abs_diff(expected) > 100 {
+            warnings.push(LintWarning::new(
+                LintRule::WeightNameMismatch,
+                Severity::Warning,
+                format!("Style \"{}\" suggests weight {}, but usWeightClass is {}", style, expected, declared),
+            ));
+        }
+    }
+
+    let style_lower = style.to_lowercase();
+    let named_italic = style_lower.contains("italic") || style_lower.contains("oblique");
+    let italic_bits = fs_selection & (FS_ITALIC | FS_OBLIQUE) != 0;
+    if named_italic && !italic_bits {
+        warnings.push(LintWarning::new(
+            LintRule::ItalicBitMissing,
+            Severity::Warning,
+            format!(
+                "Style \"{}\" is italic but fsSelection has no ITALIC/OBLIQUE bit (macStyle italic: {})",
+                style,
+                mac_style & MAC_ITALIC != 0
+            ),
+        ));
+    } else if !named_italic && italic_bits {
+        warnings.push(LintWarning::new(
+            LintRule::ItalicBitUnexpected,
+            Severity::Info,
+            format!("fsSelection marks the font italic but style \"{}\" does not say so", style),
+        ));
+    }
+
+    // RIBBI: Name ID 2 "Bold" / "Bold Italic" must match the bold bits
+    let subfamily = font.style.to_lowercase();
+    if matches!(subfamily.as_str(), "bold" | "bold italic")
+        && (fs_selection & FS_BOLD == 0 || mac_style & MAC_BOLD == 0)
+    {
+        warnings.push(LintWarning::new(
+            LintRule::BoldBitMismatch,
+            Severity::Warning,
+            format!(
+                "Subfamily \"{}\" but fsSelection bold: {}, macStyle bold: {}",
+                font.style,
+                fs_selection & FS_BOLD != 0,
+                mac_style & MAC_BOLD != 0
+            ),
+        ));
+    }
+
+    match font.metadata.postscript_name.as_deref() {
+        None => warnings.push(LintWarning::new(
+            LintRule::InvalidPostScriptName,
+            Severity::Warning,
+            "Missing PostScript name (Name ID 6)".to_string(),
+        )),
+        Some(name) => {
+            if let Some(problem) = postscript_name_problem(name) {
+                warnings.push(LintWarning::new(
+                    LintRule::InvalidPostScriptName,
+                    Severity::Warning,
+                    format!("PostScript name \"{}\" {}", name, problem),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Flag PostScript names shared by different families; the same font installed
+/// twice (e.g. system and user folders) is only reported as info
+pub fn lint_duplicate_postscript_names(fonts: &mut [FontInfo]) {
+    let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, font) in fonts.iter().enumerate() {
+        if let Some(name) = &font.metadata.postscript_name {
+            by_name.entry(name.clone()).or_default().push(index);
+        }
+    }
+
+    for (name, indices) in by_name {
+        if indices.len() < 2 {
+            continue;
+        }
+        let families: BTreeSet<String> = indices
+            .iter()
+            .map(|&i| fonts[i].css_font_family.clone())
+            .collect();
+
+        for &index in &indices {
+            let warning = if families.len() > 1 {
+                let others: Vec<&str> = families
+                    .iter()
+                    .filter(|family| **family != fonts[index].css_font_family)
+                    .map(String::as_str)
+                    .collect();
+                LintWarning::new(
+                    LintRule::DuplicatePostScriptName,
+                    Severity::Warning,
+                    format!("PostScript name \"{}\" is also used by {}", name, others.join(", ")),
+                )
+            } else {
+                LintWarning::new(
+                    LintRule::DuplicatePostScriptName,
+                    Severity::Info,
+                    format!("PostScript name \"{}\" is installed {} times", name, indices.len()),
+                )
+            };
+            fonts[index].lint_warnings.push(warning);
+        }
+    }
+}
+
+/// Lint warnings of every font that has any, keyed by font ID
+pub fn collect_lint_warnings(state: &FontState) -> BTreeMap<String, Vec<LintWarning>> {
+    state
+        .fonts
+        .iter()
+        .filter(|font| !font.lint_warnings.is_empty())
+        .map(|font| (font.id.clone(), font.lint_warnings.clone()))
+        .collect()
+}
+
+/// PostScript names are limited to 63 printable ASCII characters, excluding `[](){}<>/%` and space
+fn postscript_name_problem(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        Some("is empty")
+    } else if name.contains(' ') {
+        Some("contains spaces")
+    } else if name.len() > 63 {
+        Some("is longer than 63 characters")
+    } else if name
+        .chars()
+        .any(|c| !c.is_ascii_graphic() || "[](){}<>/%".contains(c))
+    {
+        Some("contains characters not allowed in PostScript names")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::health::tests::build_font;
+    use crate::font::state::tests::create_test_font;
+
+    fn rules(warnings: &[LintWarning]) -> Vec<LintRule> {
+        warnings.iter().map(|warning| warning.rule).collect()
+    }
+
+    #[test]
+    fn test_lint_face() {
+        // OS/2 version 0: usWeightClass 950, no fsSelection bits
+        let mut os2 = vec![0u8; 78];
+        os2[4..6].copy_from_slice(&950u16.to_be_bytes());
+        let data = build_font(1, &[(b"OS/2", os2)]);
+        let face = Face::parse(&data, 0).unwrap();
+
+        let mut font = create_test_font("1", "Test", 950);
+        font.style = "Bold Italic".to_string();
+        font.style_attributes.name_weight = Some(700);
+        let warnings = lint_face(&face, &font);
+        assert_eq!(
+            rules(&warnings),
+            vec![
+                LintRule::WeightClassOutOfRange,
+                LintRule::WeightNameMismatch,
+                LintRule::ItalicBitMissing,
+                LintRule::BoldBitMismatch,
+                LintRule::InvalidPostScriptName,
+            ]
+        );
+
+        font.style = "Regular".to_string();
+        font.style_attributes.name_weight = None;
+        font.metadata.postscript_name = Some("Test-Regular".to_string());
+        assert_eq!(rules(&lint_face(&face, &font)), vec![LintRule::WeightClassOutOfRange]);
+    }
+
+    #[test]
+    fn test_duplicate_postscript_names() {
+        let mut fonts = vec![
+            create_test_font("1", "Alpha", 400),
+            create_test_font("2", "Beta", 400),
+            create_test_font("3", "Gamma", 400),
+            create_test_font("4", "Gamma", 400),
+            create_test_font("5", "Delta", 400),
+        ];
+        for (font, name) in fonts.iter_mut().zip(["Shared-Regular", "Shared-Regular", "Gamma", "Gamma", "Delta"]) {
+            font.metadata.postscript_name = Some(name.to_string());
+        }
+        lint_duplicate_postscript_names(&mut fonts);
+
+        // Two families sharing a name are both warned, naming the other family
+        for (index, other) in [(0, "Beta"), (1, "Alpha")] {
+            let warnings = &fonts[index].lint_warnings;
+            assert_eq!(rules(warnings), vec![LintRule::DuplicatePostScriptName]);
+            assert_eq!(warnings[0].severity, Severity::Warning);
+            assert!(warnings[0].message.ends_with(other));
+        }
+        // The same family installed twice is only info
+        assert_eq!(fonts[2].lint_warnings[0].severity, Severity::Info);
+        assert_eq!(fonts[3].lint_warnings[0].severity, Severity::Info);
+        assert!(fonts[4].lint_warnings.is_empty());
+    }
+
+    #[test]
+    fn test_postscript_name_problem() {
+        assert_eq!(postscript_name_problem("ArialMT"), None);
+        assert_eq!(postscript_name_problem("Noto-Sans-CJK-SC"), None);
+        assert_eq!(postscript_name_problem("Arial MT"), Some("contains spaces"));
+        assert!(postscript_name_problem("Font(1)").is_some());
+        assert!(postscript_name_problem("微软雅黑").is_some());
+        assert!(postscript_name_problem(&"A".repeat(64)).is_some());
+    }
+}
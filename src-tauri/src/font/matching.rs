@@ -0,0 +1,526 @@
+//! CSS Fonts Level 4 font matching (§5.2): which installed face a browser uses for a given
+//! font-family, font-weight, font-style and font-stretch
+//! see https://www.w3.org/TR/css-fonts-4/#font-style-matching
+use super::models::FontInfo;
+use super::state::FontState;
+use super::style_name::FontSlope;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Requested or matched font-style
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CssFontStyle {
+    Normal,
+    Italic,
+    Oblique(f32), // 倾斜角度 (deg)，正值向右倾斜
+}
+
+/// font-family / font-weight / font-style / font-stretch to match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontQuery {
+    pub families: Vec<String>, // font-family 列表，按顺序回退
+    pub weight: f32,           // font-weight，1-1000
+    pub style: CssFontStyle,
+    pub stretch: f32,          // font-stretch 百分比，100 = normal
+}
+
+/// Variation axis value the browser applies to the matched face
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AxisSetting {
+    pub tag: String,
+    pub value: f32,
+}
+
+/// Face chosen for a query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontMatch {
+    pub font_id: String,
+    pub family: String,               // 命中的 font-family 列表项
+    pub weight: f32,                  // 字体实际字重；可变字体为夹取到 wght 范围内的值
+    pub stretch: f32,                 // 字体实际宽度百分比
+    pub style: CssFontStyle,          // 字体实际提供的样式
+    pub variations: Vec<AxisSetting>, // 浏览器为可变字体设置的 wght/wdth/slnt/ital 值
+    pub synthetic_bold: bool,         // 请求 >= 600 而字体字重 < 600 时浏览器加粗描边
+    pub synthetic_italic: bool,       // 请求 italic/oblique 而字体为正体时浏览器倾斜字形
+}
+
+/// Angle used for `oblique` without an angle, and for italic requests matched by oblique faces
+const DEFAULT_OBLIQUE_ANGLE: f32 = 14.0;
+
+/// Oblique requests at or above this angle look for steeper faces first
+const OBLIQUE_THRESHOLD: f32 = 11.0;
+
+/// Weight at which browsers synthesize bold for lighter faces
+const BOLD_THRESHOLD: f32 = 600.0;
+
+/// font-stretch percentages of usWidthClass 1-9
+const WIDTH_CLASS_PERCENT: [f32; 9] = [50.0, 62.5, 75.0, 87.5, 100.0, 112.5, 125.0, 150.0, 200.0];
+
+const STRETCH_KEYWORDS: &[(&str, f32)] = &[
+    ("ultra-condensed", 50.0),
+    ("extra-condensed", 62.5),
+    ("condensed", 75.0),
+    ("semi-condensed", 87.5),
+    ("normal", 100.0),
+    ("semi-expanded", 112.5),
+    ("expanded", 125.0),
+    ("extra-expanded", 150.0),
+    ("ultra-expanded", 200.0),
+];
+
+/// Generic families resolve to per-platform preferences rather than installed names
+const GENERIC_FAMILIES: &[&str] = &[
+    "serif", "sans-serif", "monospace", "cursive", "fantasy", "system-ui", "ui-serif", "ui-sans-serif",
+    "ui-monospace", "ui-rounded", "emoji", "math", "fangsong",
+];
+
+/// What a face (or the range of instances of a variable face) can render
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FaceRanges {
+    pub(crate) weight: (f32, f32),
+    pub(crate) stretch: (f32, f32),
+    pub(crate) italic: bool,                 // 斜体字面，或 ital 轴可到 1
+    pub(crate) oblique: Option<(f32, f32)>, // 可提供的倾斜角度范围，正体为 (0, 0)；纯斜体字面为 None
+}
+
+impl FaceRanges {
+    pub(crate) fn of(font: &FontInfo) -> Self {
+        let axis = |tag: &str| {
+            font.variation_axes
+                .iter()
+                .find(|axis| axis.tag == tag)
+                .map(|axis| (axis.min, axis.max))
+        };
+
+        let weight = axis("wght").unwrap_or((font.weight as f32, font.weight as f32));
+        let stretch = axis("wdth").unwrap_or_else(|| {
+            let percent = match font.style_attributes.width {
+                width @ 1..=9 => WIDTH_CLASS_PERCENT[width as usize - 1],
+                _ => 100.0,
+            };
+            (percent, percent)
+        });
+
+        let slope = font.style_attributes.slope;
+        let ital = axis("ital");
+        let italic = ital.map_or(slope == FontSlope::Italic, |(_, max)| max >= 1.0);
+        let upright = ital.map_or(slope == FontSlope::Upright, |(min, _)| min <= 0.0);
+        // slnt is measured counter-clockwise, CSS oblique angles clockwise
+        let oblique = match axis("slnt") {
+            Some((min, max)) => Some((-max, -min)),
+            None if upright => Some((0.0, 0.0)),
+            None if slope == FontSlope::Oblique => Some((DEFAULT_OBLIQUE_ANGLE, DEFAULT_OBLIQUE_ANGLE)),
+            None => None,
+        };
+
+        Self {
+            weight,
+            stretch,
+            italic,
+            oblique,
+        }
+    }
+}
+
+/// Match `query` against the scanned fonts, trying each family in order
+pub fn match_font(state: &FontState, query: &FontQuery) -> Option<FontMatch> {
+    query.families.iter().find_map(|family| {
+        let faces = family_faces(state, family);
+        if faces.is_empty() {
+            return None;
+        }
+
+        let faces: Vec<(&FontInfo, FaceRanges)> = faces.into_iter().map(|font| (font, FaceRanges::of(font))).collect();
+        let faces = narrow(faces, |ranges| stretch_key(query.stretch, ranges.stretch));
+        let faces = narrow(faces, |ranges| style_key(query.style, ranges));
+        let faces = narrow(faces, |ranges| weight_key(query.weight, ranges.weight));
+        faces.first().map(|(font, ranges)| resolve(font, ranges, family, query))
+    })
+}
+
+/// Faces whose typographic (or localized typographic) family equals `family`; browsers
+/// fall back to legacy Name ID 1 families such as "Arial Black"
+fn family_faces<'a>(state: &'a FontState, family: &str) -> Vec<&'a FontInfo> {
+    if is_generic_family(family) {
+        return Vec::new();
+    }
+    let wanted = family.to_lowercase();
+    let is_wanted = |name: &str| name.to_lowercase() == wanted;
+
+    let typographic: Vec<&FontInfo> = state
+        .fonts
+        .iter()
+        .filter(|font| {
+            is_wanted(&font.css_font_family)
+                || font
+                    .localized_names
+                    .values()
+                    .filter_map(|names| names.typographic_family.as_deref())
+                    .any(is_wanted)
+        })
+        .collect();
+    if !typographic.is_empty() {
+        return typographic;
+    }
+
+    state
+        .fonts
+        .iter()
+        .filter(|font| {
+            is_wanted(&font.family)
+                || font.metadata.family_name.as_deref().is_some_and(is_wanted)
+                || font
+                    .localized_names
+                    .values()
+                    .filter_map(|names| names.family.as_deref())
+                    .any(is_wanted)
+        })
+        .collect()
+}
+
+/// Whether `family` is a CSS generic family such as `sans-serif`
+pub fn is_generic_family(family: &str) -> bool {
+    GENERIC_FAMILIES.contains(&family.to_lowercase().as_str())
+}
+
+/// Keep the faces with the smallest key; keys are (tier, distance) in the order the
+/// spec checks candidate values
+fn narrow(faces: Vec<(&FontInfo, FaceRanges)>, key: impl Fn(&FaceRanges) -> (u8, f32)) -> Vec<(&FontInfo, FaceRanges)> {
+    let compare = |a: &(u8, f32), b: &(u8, f32)| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1));
+    let Some(best) = faces.iter().map(|(_, ranges)| key(ranges)).min_by(compare) else {
+        return faces;
+    };
+    faces
+        .into_iter()
+        .filter(|(_, ranges)| compare(&key(ranges), &best) == Ordering::Equal)
+        .collect()
+}
+
+/// font-stretch: up to 100% narrower widths are checked first, above 100% wider ones
+fn stretch_key(stretch: f32, (min, max): (f32, f32)) -> (u8, f32) {
+    if (min..=max).contains(&stretch) {
+        (0, 0.0)
+    } else if stretch <= 100.0 {
+        if max < stretch { (1, stretch - max) } else { (2, min - stretch) }
+    } else if min > stretch {
+        (1, min - stretch)
+    } else {
+        (2, stretch - max)
+    }
+}
+
+/// font-weight: 400-500 looks up to 500 first, then lighter, then heavier; below 400 looks
+/// lighter first; above 500 looks heavier first
+fn weight_key(weight: f32, (min, max): (f32, f32)) -> (u8, f32) {
+    if (min..=max).contains(&weight) {
+        (0, 0.0)
+    } else if (400.0..=500.0).contains(&weight) {
+        if min > weight && min <= 500.0 {
+            (1, min - weight)
+        } else if max < weight {
+            (2, weight - max)
+        } else {
+            (3, min - weight)
+        }
+    } else if weight < 400.0 {
+        if max < weight { (1, weight - max) } else { (2, min - weight) }
+    } else if min > weight {
+        (1, min - weight)
+    } else {
+        (2, weight - max)
+    }
+}
+
+/// font-style: italic checks italic, then oblique, then normal faces; oblique and normal
+/// (oblique 0deg) check oblique angles, then italic faces, then opposite angles
+fn style_key(style: CssFontStyle, ranges: &FaceRanges) -> (u8, f32) {
+    match style {
+        CssFontStyle::Italic if ranges.italic => (0, 0.0),
+        CssFontStyle::Italic => {
+            let (tier, distance) = ranges.oblique.map_or((3, 0.0), |range| oblique_key(DEFAULT_OBLIQUE_ANGLE, range));
+            (tier + 1, distance)
+        }
+        CssFontStyle::Normal => ranges.oblique.map_or((3, 0.0), |range| oblique_key(0.0, range)),
+        CssFontStyle::Oblique(angle) => ranges.oblique.map_or((3, 0.0), |range| oblique_key(angle, range)),
+    }
+}
+
+/// Tiers for an oblique range: 0 contains the angle, 1-2 same direction (steeper first from
+/// 11deg, shallower first below), 3 is left for italic faces, 4 the opposite direction
+fn oblique_key(angle: f32, (min, max): (f32, f32)) -> (u8, f32) {
+    if angle < 0.0 {
+        return oblique_key(-angle, (-max, -min));
+    }
+    if (min..=max).contains(&angle) {
+        (0, 0.0)
+    } else if min > angle {
+        (if angle >= OBLIQUE_THRESHOLD || angle == 0.0 { 1 } else { 2 }, min - angle)
+    } else if max > 0.0 {
+        (if angle >= OBLIQUE_THRESHOLD { 2 } else { 1 }, angle - max)
+    } else {
+        (if angle == 0.0 { 2 } else { 4 }, angle - max)
+    }
+}
+
+/// Clamp the request into the face's ranges and decide what the browser synthesizes
+fn resolve(font: &FontInfo, ranges: &FaceRanges, family: &str, query: &FontQuery) -> FontMatch {
+    let clamp = |value: f32, (min, max): (f32, f32)| value.clamp(min, max);
+    let weight = clamp(query.weight, ranges.weight);
+    let stretch = clamp(query.stretch, ranges.stretch);
+
+    let wanted_angle = match query.style {
+        CssFontStyle::Normal => 0.0,
+        CssFontStyle::Italic => DEFAULT_OBLIQUE_ANGLE,
+        CssFontStyle::Oblique(angle) => angle,
+    };
+    let style = match (query.style, ranges.oblique) {
+        (CssFontStyle::Italic, _) if ranges.italic => CssFontStyle::Italic,
+        (_, Some(range)) => match clamp(wanted_angle, range) {
+            0.0 => CssFontStyle::Normal,
+            angle => CssFontStyle::Oblique(angle),
+        },
+        (_, None) => CssFontStyle::Italic,
+    };
+
+    let variations = font
+        .variation_axes
+        .iter()
+        .filter_map(|axis| {
+            let value = match axis.tag.as_str() {
+                "wght" => weight,
+                "wdth" => stretch,
+                "slnt" => match style {
+                    CssFontStyle::Oblique(angle) => -angle,
+                    _ => 0.0,
+                },
+                "ital" => {
+                    if style == CssFontStyle::Italic { 1.0 } else { 0.0 }
+                }
+                _ => return None,
+            };
+            Some(AxisSetting {
+                tag: axis.tag.clone(),
+                value: value.clamp(axis.min, axis.max),
+            })
+        })
+        .collect();
+
+    FontMatch {
+        font_id: font.id.clone(),
+        family: family.to_string(),
+        weight,
+        stretch,
+        style,
+        variations,
+        synthetic_bold: query.weight >= BOLD_THRESHOLD && weight < BOLD_THRESHOLD,
+        synthetic_italic: query.style != CssFontStyle::Normal && style == CssFontStyle::Normal,
+    }
+}
+
+impl FontQuery {
+    /// Build a query from CSS property values
+    pub fn parse(family: &str, weight: &str, style: &str, stretch: &str) -> Result<Self, String> {
+        let families = parse_font_family_list(family);
+        if families.is_empty() {
+            return Err("font-family is empty".to_string());
+        }
+        Ok(Self {
+            families,
+            weight: parse_font_weight(weight)?,
+            style: parse_font_style(style)?,
+            stretch: parse_font_stretch(stretch)?,
+        })
+    }
+}
+
+/// Split a font-family list at commas outside quotes and unquote each entry
+pub fn parse_font_family_list(value: &str) -> Vec<String> {
+    let mut families = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    for c in value.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (',', None) => families.push(std::mem::take(&mut current)),
+            (c, _) => current.push(c),
+        }
+    }
+    families.push(current);
+
+    families
+        .into_iter()
+        .map(|family| family.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|family| !family.is_empty())
+        .collect()
+}
+
+/// `normal`, `bold` or a number in 1-1000
+pub fn parse_font_weight(value: &str) -> Result<f32, String> {
+    match value.trim().to_lowercase().as_str() {
+        "" | "normal" => Ok(400.0),
+        "bold" => Ok(700.0),
+        number => number
+            .parse::<f32>()
+            .ok()
+            .filter(|weight| (1.0..=1000.0).contains(weight))
+            .ok_or_else(|| format!("Invalid font-weight: {}", value)),
+    }
+}
+
+/// `normal`, `italic`, or `oblique` with an optional angle in -90deg..90deg
+pub fn parse_font_style(value: &str) -> Result<CssFontStyle, String> {
+    let lowered = value.trim().to_lowercase();
+    let mut parts = lowered.split_whitespace();
+    let style = match (parts.next(), parts.next(), parts.next()) {
+        (None | Some("normal"), None, _) => CssFontStyle::Normal,
+        (Some("italic"), None, _) => CssFontStyle::Italic,
+        (Some("oblique"), None, _) => CssFontStyle::Oblique(DEFAULT_OBLIQUE_ANGLE),
+        (Some("oblique"), Some(angle), None) => angle
+            .strip_suffix("deg")
+            .and_then(|angle| angle.parse::<f32>().ok())
+            .filter(|angle| (-90.0..=90.0).contains(angle))
+            .map(CssFontStyle::Oblique)
+            .ok_or_else(|| format!("Invalid font-style: {}", value))?,
+        _ => return Err(format!("Invalid font-style: {}", value)),
+    };
+    Ok(style)
+}
+
+/// A width keyword or a non-negative percentage
+pub fn parse_font_stretch(value: &str) -> Result<f32, String> {
+    let lowered = value.trim().to_lowercase();
+    if lowered.is_empty() {
+        return Ok(100.0);
+    }
+    if let Some(&(_, percent)) = STRETCH_KEYWORDS.iter().find(|(keyword, _)| *keyword == lowered) {
+        return Ok(percent);
+    }
+    lowered
+        .strip_suffix('%')
+        .and_then(|percent| percent.parse::<f32>().ok())
+        .filter(|percent| *percent >= 0.0)
+        .ok_or_else(|| format!("Invalid font-stretch: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::models::VariationAxis;
+    use crate::font::state::tests::create_test_font;
+
+    fn face(id: &str, weight: u16, width: u16, slope: FontSlope) -> FontInfo {
+        let mut font = create_test_font(id, "Test", weight);
+        font.style_attributes.width = width;
+        font.style_attributes.slope = slope;
+        font
+    }
+
+    fn query(weight: f32, style: CssFontStyle, stretch: f32) -> FontQuery {
+        FontQuery {
+            families: vec!["Missing".to_string(), "test".to_string()],
+            weight,
+            style,
+            stretch,
+        }
+    }
+
+    fn matched(state: &FontState, query: &FontQuery) -> String {
+        match_font(state, query).unwrap().font_id
+    }
+
+    #[test]
+    fn test_weight_fallback() {
+        let state = FontState::new(vec![
+            face("300", 300, 5, FontSlope::Upright),
+            face("600", 600, 5, FontSlope::Upright),
+            face("900", 900, 5, FontSlope::Upright),
+        ]);
+        let normal = |weight| query(weight, CssFontStyle::Normal, 100.0);
+
+        // 400-500 looks up to 500, then lighter
+        assert_eq!(matched(&state, &normal(400.0)), "300");
+        // Below 400 looks lighter first, then heavier
+        assert_eq!(matched(&state, &normal(200.0)), "300");
+        // Above 500 looks heavier first
+        assert_eq!(matched(&state, &normal(700.0)), "900");
+        assert_eq!(matched(&state, &normal(950.0)), "900");
+
+        let light = match_font(&state, &normal(400.0)).unwrap();
+        assert!(!light.synthetic_bold);
+        let state = FontState::new(vec![face("400", 400, 5, FontSlope::Upright)]);
+        let bold = match_font(&state, &normal(700.0)).unwrap();
+        assert!(bold.synthetic_bold);
+        assert_eq!(bold.family, "test");
+    }
+
+    #[test]
+    fn test_stretch_before_style_and_weight() {
+        let state = FontState::new(vec![
+            face("normal", 400, 5, FontSlope::Upright),
+            face("condensed-italic", 700, 3, FontSlope::Italic),
+            face("semi-condensed", 400, 4, FontSlope::Upright),
+        ]);
+        // 87.5% exists, so the italic request is synthesized on the semi-condensed face
+        let result = match_font(&state, &query(600.0, CssFontStyle::Italic, 87.5)).unwrap();
+        assert_eq!(result.font_id, "semi-condensed");
+        assert!(result.synthetic_italic && result.synthetic_bold);
+
+        // 80% is missing: narrower widths are checked first
+        assert_eq!(matched(&state, &query(400.0, CssFontStyle::Normal, 80.0)), "condensed-italic");
+    }
+
+    #[test]
+    fn test_style_fallback() {
+        let state = FontState::new(vec![
+            face("upright", 400, 5, FontSlope::Upright),
+            face("oblique", 400, 5, FontSlope::Oblique),
+        ]);
+        // Italic falls back to oblique before normal
+        let result = match_font(&state, &query(400.0, CssFontStyle::Italic, 100.0)).unwrap();
+        assert_eq!(result.font_id, "oblique");
+        assert!(!result.synthetic_italic);
+
+        let state = FontState::new(vec![
+            face("upright", 400, 5, FontSlope::Upright),
+            face("italic", 400, 5, FontSlope::Italic),
+        ]);
+        assert_eq!(matched(&state, &query(400.0, CssFontStyle::Oblique(20.0), 100.0)), "italic");
+        assert_eq!(matched(&state, &query(400.0, CssFontStyle::Normal, 100.0)), "upright");
+    }
+
+    #[test]
+    fn test_variable_ranges() {
+        let mut variable = face("variable", 400, 5, FontSlope::Upright);
+        variable.variation_axes = vec![
+            VariationAxis { tag: "wght".to_string(), min: 100.0, default: 400.0, max: 900.0 },
+            VariationAxis { tag: "wdth".to_string(), min: 75.0, default: 100.0, max: 100.0 },
+            VariationAxis { tag: "slnt".to_string(), min: -10.0, default: 0.0, max: 0.0 },
+        ];
+        let state = FontState::new(vec![face("static-bold", 700, 5, FontSlope::Upright), variable]);
+
+        let result = match_font(&state, &query(650.0, CssFontStyle::Oblique(14.0), 87.5)).unwrap();
+        assert_eq!(result.font_id, "variable");
+        assert_eq!(result.weight, 650.0);
+        assert_eq!(result.style, CssFontStyle::Oblique(10.0));
+        assert!(!result.synthetic_bold && !result.synthetic_italic);
+        let slnt = result.variations.iter().find(|axis| axis.tag == "slnt").unwrap();
+        assert_eq!(slnt.value, -10.0);
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = FontQuery::parse("\"Noto Sans\", 'Source  Han', serif", "600", "oblique 10deg", "semi-condensed").unwrap();
+        assert_eq!(query.families, vec!["Noto Sans", "Source Han", "serif"]);
+        assert_eq!(query.weight, 600.0);
+        assert_eq!(query.style, CssFontStyle::Oblique(10.0));
+        assert_eq!(query.stretch, 87.5);
+
+        assert_eq!(parse_font_weight("bold"), Ok(700.0));
+        assert_eq!(parse_font_stretch("87.5%"), Ok(87.5));
+        assert_eq!(parse_font_style("italic"), Ok(CssFontStyle::Italic));
+        assert!(parse_font_weight("1200").is_err());
+        assert!(parse_font_style("oblique 100deg").is_err());
+        assert!(FontQuery::parse(" , ", "", "", "").is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! OpenType MATH table constants and math block coverage
+use super::coverage::count_in_range;
+use serde::{Deserialize, Serialize};
+use ttf_parser::math::Constants;
+use ttf_parser::Face;
+
+/// Math typesetting support of a face
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MathInfo {
+    pub has_math_table: bool,           // 含 MATH 表，可用于 OpenType 数学排版 (LuaTeX/XeTeX/MathML)
+    pub constants: Vec<MathConstant>,   // MathConstants，按规范顺序；无 MATH 表为空
+    pub blocks: Vec<MathBlockCoverage>, // 数学字母数字符号与运算符区段覆盖
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MathConstant {
+    pub name: String, // 规范中的名称，如 "AxisHeight"
+    pub value: i32,   // font units；ScalePercent 类常量为百分比
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MathBlockCoverage {
+    pub name: String,
+    pub covered: u32,
+    pub total: u32, // 区段内已分配的字符数
+}
+
+/// Blocks used by math typesetting: (name, first, last, assigned code points)
+const MATH_BLOCKS: &[(&str, u32, u32, u32)] = &[
+    ("Mathematical Alphanumeric Symbols", 0x1D400, 0x1D7FF, 996),
+    ("Mathematical Operators", 0x2200, 0x22FF, 256),
+    ("Supplemental Mathematical Operators", 0x2A00, 0x2AFF, 256),
+    ("Miscellaneous Mathematical Symbols-A", 0x27C0, 0x27EF, 48),
+    ("Miscellaneous Mathematical Symbols-B", 0x2980, 0x29FF, 128),
+];
+
+type ConstantReader = fn(&Constants) -> i32;
+
+/// MathConstants in table order
+const MATH_CONSTANTS: &[(&str, ConstantReader)] = &[
+    ("ScriptPercentScaleDown", |c| c.script_percent_scale_down() as i32),
+    ("ScriptScriptPercentScaleDown", |c| c.script_script_percent_scale_down() as i32),
+    ("DelimitedSubFormulaMinHeight", |c| c.delimited_sub_formula_min_height() as i32),
+    ("DisplayOperatorMinHeight", |c| c.display_operator_min_height() as i32),
+    ("MathLeading", |c| c.math_leading().value as i32),
+    ("AxisHeight", |c| c.axis_height().value as i32),
+    ("AccentBaseHeight", |c| c.accent_base_height().value as i32),
+    ("FlattenedAccentBaseHeight", |c| c.flattened_accent_base_height().value as i32),
+    ("SubscriptShiftDown", |c| c.subscript_shift_down().value as i32),
+    ("SubscriptTopMax", |c| c.subscript_top_max().value as i32),
+    ("SubscriptBaselineDropMin", |c| c.subscript_baseline_drop_min().value as i32),
+    ("SuperscriptShiftUp", |c| c.superscript_shift_up().value as i32),
+    ("SuperscriptShiftUpCramped", |c| c.superscript_shift_up_cramped().value as i32),
+    ("SuperscriptBottomMin", |c| c.superscript_bottom_min().value as i32),
+    ("SuperscriptBaselineDropMax", |c| c.superscript_baseline_drop_max().value as i32),
+    ("SubSuperscriptGapMin", |c| c.sub_superscript_gap_min().value as i32),
+    ("SuperscriptBottomMaxWithSubscript", |c| c.superscript_bottom_max_with_subscript().value as i32),
+    ("SpaceAfterScript", |c| c.space_after_script().value as i32),
+    ("UpperLimitGapMin", |c| c.upper_limit_gap_min().value as i32),
+    ("UpperLimitBaselineRiseMin", |c| c.upper_limit_baseline_rise_min().value as i32),
+    ("LowerLimitGapMin", |c| c.lower_limit_gap_min().value as i32),
+    ("LowerLimitBaselineDropMin", |c| c.lower_limit_baseline_drop_min().value as i32),
+    ("StackTopShiftUp", |c| c.stack_top_shift_up().value as i32),
+    ("StackTopDisplayStyleShiftUp", |c| c.stack_top_display_style_shift_up().value as i32),
+    ("StackBottomShiftDown", |c| c.stack_bottom_shift_down().value as i32),
+    ("StackBottomDisplayStyleShiftDown", |c| c.stack_bottom_display_style_shift_down().value as i32),
+    ("StackGapMin", |c| c.stack_gap_min().value as i32),
+    ("StackDisplayStyleGapMin", |c| c.stack_display_style_gap_min().value as i32),
+    ("StretchStackTopShiftUp", |c| c.stretch_stack_top_shift_up().value as i32),
+    ("StretchStackBottomShiftDown", |c| c.stretch_stack_bottom_shift_down().value as i32),
+    ("StretchStackGapAboveMin", |c| c.stretch_stack_gap_above_min().value as i32),
+    ("StretchStackGapBelowMin", |c| c.stretch_stack_gap_below_min().value as i32),
+    ("FractionNumeratorShiftUp", |c| c.fraction_numerator_shift_up().value as i32),
+    ("FractionNumeratorDisplayStyleShiftUp", |c| c.fraction_numerator_display_style_shift_up().value as i32),
+    ("FractionDenominatorShiftDown", |c| c.fraction_denominator_shift_down().value as i32),
+    ("FractionDenominatorDisplayStyleShiftDown", |c| c.fraction_denominator_display_style_shift_down().value as i32),
+    ("FractionNumeratorGapMin", |c| c.fraction_numerator_gap_min().value as i32),
+    ("FractionNumDisplayStyleGapMin", |c| c.fraction_num_display_style_gap_min().value as i32),
+    ("FractionRuleThickness", |c| c.fraction_rule_thickness().value as i32),
+    ("FractionDenominatorGapMin", |c| c.fraction_denominator_gap_min().value as i32),
+    ("FractionDenomDisplayStyleGapMin", |c| c.fraction_denom_display_style_gap_min().value as i32),
+    ("SkewedFractionHorizontalGap", |c| c.skewed_fraction_horizontal_gap().value as i32),
+    ("SkewedFractionVerticalGap", |c| c.skewed_fraction_vertical_gap().value as i32),
+    ("OverbarVerticalGap", |c| c.overbar_vertical_gap().value as i32),
+    ("OverbarRuleThickness", |c| c.overbar_rule_thickness().value as i32),
+    ("OverbarExtraAscender", |c| c.overbar_extra_ascender().value as i32),
+    ("UnderbarVerticalGap", |c| c.underbar_vertical_gap().value as i32),
+    ("UnderbarRuleThickness", |c| c.underbar_rule_thickness().value as i32),
+    ("UnderbarExtraDescender", |c| c.underbar_extra_descender().value as i32),
+    ("RadicalVerticalGap", |c| c.radical_vertical_gap().value as i32),
+    ("RadicalDisplayStyleVerticalGap", |c| c.radical_display_style_vertical_gap().value as i32),
+    ("RadicalRuleThickness", |c| c.radical_rule_thickness().value as i32),
+    ("RadicalExtraAscender", |c| c.radical_extra_ascender().value as i32),
+    ("RadicalKernBeforeDegree", |c| c.radical_kern_before_degree().value as i32),
+    ("RadicalKernAfterDegree", |c| c.radical_kern_after_degree().value as i32),
+    ("RadicalDegreeBottomRaisePercent", |c| c.radical_degree_bottom_raise_percent() as i32),
+];
+
+/// Read MATH constants and count coverage of the math Unicode blocks in the sorted `codepoints`
+pub fn detect_math_info(face: &Face, codepoints: &[u32]) -> MathInfo {
+    let math = face.tables().math;
+    let constants = math
+        .and_then(|math| math.constants)
+        .map(|constants| {
+            MATH_CONSTANTS
+                .iter()
+                .map(|(name, read)| MathConstant {
+                    name: name.to_string(),
+                    value: read(&constants),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let blocks = MATH_BLOCKS
+        .iter()
+        .map(|&(name, first, last, total)| MathBlockCoverage {
+            name: name.to_string(),
+            covered: count_in_range(codepoints, first, last),
+            total,
+        })
+        .collect();
+
+    MathInfo {
+        has_math_table: math.is_some(),
+        constants,
+        blocks,
+    }
+}
@@ -2,8 +2,52 @@ pub mod models;
 pub mod scanner;
 pub mod check;
 pub mod state;
+pub mod coverage;
+pub mod names;
+pub mod encoding;
+pub mod normalize;
+pub mod tables;
+pub mod health;
+pub mod lint;
+pub mod outline;
+pub mod bitmap;
+pub mod color;
+pub mod monospace;
+pub mod ligatures;
+pub mod features;
+pub mod vertical;
+pub mod math;
+pub mod classification;
+pub mod cjk_style;
+pub mod style_name;
+pub mod matching;
+pub mod stylesheet;
+pub mod font_face;
+pub mod subset;
+mod raw;
 
 pub use models::*;
 pub use scanner::*;
 pub use check::*;
 pub use state::*;
+pub use coverage::*;
+pub use names::*;
+pub use normalize::*;
+pub use tables::*;
+pub use health::*;
+pub use lint::*;
+pub use outline::*;
+pub use bitmap::*;
+pub use color::*;
+pub use monospace::*;
+pub use ligatures::*;
+pub use features::*;
+pub use vertical::*;
+pub use math::*;
+pub use classification::*;
+pub use cjk_style::*;
+pub use style_name::*;
+pub use matching::*;
+pub use stylesheet::*;
+pub use font_face::*;
+pub use subset::*;
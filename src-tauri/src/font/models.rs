@@ -1,4 +1,20 @@
+use super::bitmap::BitmapStrike;
+use super::classification::{FontCategory, FontClassification};
+use super::cjk_style::CjkStyleInfo;
+use super::color::ColorInfo;
+use super::coverage::Os2Coverage;
+use super::health::FontHealth;
+use super::ligatures::LigatureInfo;
+use super::lint::LintWarning;
+use super::math::MathInfo;
+use super::monospace::MonospaceInfo;
+use super::names::LocalizedNames;
+use super::normalize::NameRepair;
+use super::outline::RenderingInfo;
+use super::style_name::{FontSlope, StyleAttributes};
+use super::vertical::VerticalInfo;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FontInfo {
@@ -8,20 +24,61 @@ pub struct FontInfo {
     pub postscript_name: String, // OpenType Name ID 6 (POST_SCRIPT_NAME)
     pub style: String,           // OpenType Name ID 2 (SUBFAMILY)，默认 "Regular"
     pub path: String,            // 字体文件路径
+    pub face_index: u32,         // TTC 中的字体索引，单字体文件为 0
     pub file_size: u64,          // 由 fs::metadata 获取
     pub format: FontFormat,      // 由文件扩展名判定 (.ttf/.otf/.ttc)
     pub is_variable: bool,       // 由 ttf_parser::Face::is_variable() 判定
-    pub weight: u16,             // 字重值 (100-900)，由 OS/2 usWeightClass 或 fvar wght 轴获取
+    pub weight: u16,             // 字重值 (100-900)，由 fvar wght 轴、OS/2 usWeightClass 或样式名获取
     pub languages: Vec<String>,  // 由 Glyph 覆盖范围判定 (检测特定字符是否存在)
     pub scripts: Vec<String>,    // 由 Glyph 覆盖范围判定 (Latn/Hans/Jpan/Kore/Cyrl/Arab)
-    pub metadata: FontMetadata,  // OpenType Name Table 完整信息 (ID 0-20)
+    pub metadata: FontMetadata,  // OpenType Name Table 完整信息 (ID 0-22)
     pub status: FontStatus,      // 由 is_system_font() 判定系统字体，否则默认 Enabled
     pub created_at: i64,         // 扫描时的 Unix 时间戳
-    // Localized names - 中文本地化名称
-    pub family_zh: Option<String>,    // OpenType Name ID 1，language_id: 0x0804/0x1004/0x0404 (简体/新加坡/繁体)
-    pub full_name_zh: Option<String>, // OpenType Name ID 4，language_id: 0x0804/0x1004/0x0404
+    // Localized names - 所有语言的本地化名称，key 为 BCP 47 语言标签 (如 "zh-CN", "ja-JP")
+    pub localized_names: BTreeMap<String, LocalizedNames>, // Name ID 1/4/16，由 Windows language_id 或 name 表 lang tag 映射
     // CSS font-family 名称 - 浏览器匹配优先级: ID 16 > ID 1 > ID 21
     pub css_font_family: String,      // 优先 Name ID 16 (Typographic Family)，回退到 ID 1 (Family)
+    // OS/2 ulUnicodeRange / ulCodePageRange 与 cmap 实际覆盖的交叉检查，无 OS/2 表时为 None
+    pub os2_coverage: Option<Os2Coverage>,
+    // 规范化/乱码修复改动过的 name 记录，保留原始值供详情页显示
+    pub name_repairs: Vec<NameRepair>,
+    // 结构完整性检查 (表校验和、边界/重叠、loca/glyf、cmap)
+    pub health: FontHealth,
+    // 元数据一致性检查 (字重、italic/bold 位、PostScript 名称)
+    pub lint_warnings: Vec<LintWarning>,
+    // 轮廓格式 (glyf/CFF/CFF2)、hinting 程序、gasp、内嵌位图
+    pub rendering: RenderingInfo,
+    // 内嵌位图 strike (尺寸与图像格式)，小字号下会替代轮廓渲染
+    pub bitmap_strikes: Vec<BitmapStrike>,
+    // 彩色字体技术 (COLR/CPAL, SVG, sbix, CBDT) 与 emoji 判定
+    pub color: ColorInfo,
+    // 等宽检测 (post.isFixedPitch 与 ASCII advance 实测) 及终端适用性
+    pub monospace: MonospaceInfo,
+    // GSUB calt/liga/dlig 替换的编程连字序列
+    pub ligatures: LigatureInfo,
+    // GSUB/GPOS 特性标签 (去重排序)，按脚本/语言系统的完整列表由 get_layout_features 提供
+    pub features: Vec<String>,
+    // 竖排支持 (vhea/vmtx、CFF 的 VORG、GSUB vert/vrt2)
+    pub vertical: VerticalInfo,
+    // OpenType MATH 表常量及数学符号区段覆盖
+    pub math: MathInfo,
+    // 分类 (衬线/无衬线/手写/展示/等宽)，综合 PANOSE、sFamilyClass、名称关键字与字形测量
+    pub classification: FontClassification,
+    // CJK 风格 (宋/黑/楷/仿宋/圆)，综合所有本地化名称关键字与横画收笔形状；无汉字的字体为 None
+    pub cjk_style: CjkStyleInfo,
+    // 样式名 (英文、中日韩写法、W 数字) 推断的字重/宽度/倾斜与 OS/2 值的对照及冲突
+    pub style_attributes: StyleAttributes,
+    // fvar 变体轴 (wght/wdth/slnt/ital 等)，静态字体为空；CSS 字体匹配按轴范围计算
+    pub variation_axes: Vec<VariationAxis>,
+}
+
+/// A variation axis from the fvar table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariationAxis {
+    pub tag: String,  // 轴标签，如 "wght"
+    pub min: f32,
+    pub default: f32,
+    pub max: f32,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -63,6 +120,8 @@ pub struct FontMetadata {
     pub compatible_full: Option<String>,        // ID 18
     pub sample_text: Option<String>,            // ID 19
     pub postscript_cid: Option<String>,         // ID 20
+    pub wws_family: Option<String>,             // ID 21 (WWS Family)
+    pub wws_subfamily: Option<String>,          // ID 22
 }
 
 /// CSS Font Family - groups fonts with the same css_font_family name
@@ -70,7 +129,33 @@ pub struct FontMetadata {
 pub struct CssFontFamily {
     pub name: String,              // css_font_family 名称
     pub font_count: usize,         // 该字体家族下的字体数量
-    pub default_font_id: String,   // 默认字体ID (weight最接近400的字体)
+    pub default_font_id: String,   // 默认字体ID (正体、常规宽度中 weight 最接近 400 的字体)
+    pub category: FontCategory,    // 各字体分类按置信度投票的结果
+    pub category_confidence: f32,  // 胜出分类的置信度之和 / 字体数量
+    pub styles: Vec<FamilyStyle>,  // 样式矩阵 (宽度 × 字重 × 倾斜)，按此顺序排序
+    pub ribbi: RibbiMembers,       // 传统样式链接的 Regular/Italic/Bold/Bold Italic 成员
+    pub weights: Vec<u16>,         // 可用字重 (去重排序)
+    pub widths: Vec<u16>,          // 可用宽度等级 1-9
+    pub slopes: Vec<FontSlope>,    // 可用倾斜样式
+}
+
+/// One member of a family's style matrix
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FamilyStyle {
+    pub font_id: String,
+    pub style: String, // Name ID 2 或 17，用于显示
+    pub weight: u16,
+    pub width: u16,
+    pub slope: FontSlope,
+}
+
+/// Font IDs of the RIBBI members (Regular, Italic, Bold, Bold Italic)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RibbiMembers {
+    pub regular: Option<String>,
+    pub italic: Option<String>,
+    pub bold: Option<String>,
+    pub bold_italic: Option<String>,
 }
 
 impl Default for FontMetadata {
@@ -96,6 +181,8 @@ impl Default for FontMetadata {
             compatible_full: None,
             sample_text: None,
             postscript_cid: None,
+            wws_family: None,
+            wws_subfamily: None,
         }
     }
 }
@@ -0,0 +1,200 @@
+//! Fixed-pitch detection and terminal suitability (box drawing, Powerline, Nerd Fonts)
+use super::coverage::count_in_range;
+use super::raw::{self, read_u32};
+use serde::{Deserialize, Serialize};
+use ttf_parser::Face;
+
+/// Fixed-pitch flags and measured advance widths of a face
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MonospaceInfo {
+    pub post_fixed_pitch: bool,     // post.isFixedPitch 非 0
+    pub ascii_fixed_pitch: bool,    // 可打印 ASCII (U+0020-007E) 全部映射且 advance width 相同
+    pub advance_width: Option<u16>, // 等宽时的 advance width (font units)
+    pub is_monospace: bool,         // 以实测为准；不含完整 ASCII 时回退到 post.isFixedPitch
+    pub terminal: TerminalSupport,
+}
+
+/// Glyph coverage relevant to terminal emulators and shell prompts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TerminalSupport {
+    pub box_drawing: u32,     // U+2500-257F 覆盖字符数，满覆盖为 128
+    pub block_elements: u32,  // U+2580-259F 覆盖字符数，满覆盖为 32
+    pub powerline: bool,      // Powerline 核心符号 U+E0A0-E0A2, U+E0B0-E0B3
+    pub nerd_font_icons: u32, // Nerd Fonts 私用区图标数
+    pub is_nerd_font: bool,   // Powerline 加至少 3 组 Nerd 图标，私用区无其他大量字符
+    pub terminal_ready: bool, // 等宽且完整覆盖制表符与方块元素
+}
+
+const BOX_DRAWING: (u32, u32) = (0x2500, 0x257F);
+const BLOCK_ELEMENTS: (u32, u32) = (0x2580, 0x259F);
+const POWERLINE: &[u32] = &[0xE0A0, 0xE0A1, 0xE0A2, 0xE0B0, 0xE0B1, 0xE0B2, 0xE0B3];
+
+/// Private use ranges patched in by Nerd Fonts (v3): Pomicons, the Powerline Extra
+/// glyphs past `POWERLINE_RANGE`, Font Awesome Extension, Weather, Seti-UI, Devicons,
+/// Codicons, Font Awesome, Font Logos, Octicons and Material Design. They must not
+/// overlap `POWERLINE_RANGE`, or stray private use code points are undercounted.
+const NERD_FONT_RANGES: &[(u32, u32)] = &[
+    (0xE000, 0xE00A),
+    (0xE0D5, 0xE0D7),
+    (0xE200, 0xE2A9),
+    (0xE300, 0xE3E3),
+    (0xE5FA, 0xE6B7),
+    (0xE700, 0xE8EF),
+    (0xEA60, 0xEC1E),
+    (0xED00, 0xF2FF),
+    (0xF300, 0xF381),
+    (0xF400, 0xF533),
+    (0xF0001, 0xF1AF0),
+];
+
+/// Powerline symbols and Powerline Extra, which every Nerd Fonts patch adds
+const POWERLINE_RANGE: (u32, u32) = (0xE0A0, 0xE0D4);
+
+/// BMP private use area; HKSCS and GB18030-era CJK fonts map thousands of
+/// ideographs here, straddling the Nerd ranges and the gaps between them
+const BMP_PRIVATE_USE: (u32, u32) = (0xE000, 0xF8FF);
+
+/// Icon sets that must each have at least a few glyphs mapped
+const NERD_FONT_MIN_SETS: usize = 3;
+const NERD_FONT_MIN_SET_ICONS: u32 = 8;
+
+/// BMP private use code points outside the Powerline and Nerd ranges that a
+/// patched font may still carry (vendor logos and the like)
+const NERD_FONT_MAX_STRAY: u32 = 64;
+
+/// Detect fixed pitch from the post table and from ASCII advance widths; terminal
+/// coverage is counted in the sorted `codepoints`
+pub fn detect_monospace(face: &Face, codepoints: &[u32]) -> MonospaceInfo {
+    let post_fixed_pitch = raw::table(face, b"post")
+        .and_then(|data| read_u32(data, 12))
+        .is_some_and(|flag| flag != 0);
+
+    let advances: Option<Vec<u16>> = (0x20u8..=0x7E)
+        .map(|byte| face.glyph_index(byte as char).and_then(|id| face.glyph_hor_advance(id)))
+        .collect();
+    let advance_width = advances
+        .as_deref()
+        .and_then(common_advance);
+    let ascii_fixed_pitch = advance_width.is_some();
+    let is_monospace = if advances.is_some() { ascii_fixed_pitch } else { post_fixed_pitch };
+
+    let box_drawing = count_in_range(codepoints, BOX_DRAWING.0, BOX_DRAWING.1);
+    let block_elements = count_in_range(codepoints, BLOCK_ELEMENTS.0, BLOCK_ELEMENTS.1);
+    let nerd_font_icons = NERD_FONT_RANGES
+        .iter()
+        .map(|&(start, end)| count_in_range(codepoints, start, end))
+        .sum();
+
+    MonospaceInfo {
+        post_fixed_pitch,
+        ascii_fixed_pitch,
+        advance_width,
+        is_monospace,
+        terminal: TerminalSupport {
+            box_drawing,
+            block_elements,
+            powerline: POWERLINE.iter().all(|cp| codepoints.binary_search(cp).is_ok()),
+            nerd_font_icons,
+            is_nerd_font: is_nerd_font(codepoints),
+            terminal_ready: is_monospace
+                && box_drawing == BOX_DRAWING.1 - BOX_DRAWING.0 + 1
+                && block_elements == BLOCK_ELEMENTS.1 - BLOCK_ELEMENTS.0 + 1,
+        },
+    }
+}
+
+/// Powerline glyphs plus several Nerd icon sets, with the rest of the private
+/// use area left empty; a raw private use count also matches CJK fonts
+fn is_nerd_font(codepoints: &[u32]) -> bool {
+    if !POWERLINE.iter().all(|cp| codepoints.binary_search(cp).is_ok()) {
+        return false;
+    }
+    let icon_sets = NERD_FONT_RANGES
+        .iter()
+        .filter(|&&(start, end)| count_in_range(codepoints, start, end) >= NERD_FONT_MIN_SET_ICONS)
+        .count();
+    let known: u32 = NERD_FONT_RANGES
+        .iter()
+        .filter(|&&(start, _)| start <= BMP_PRIVATE_USE.1)
+        .map(|&(start, end)| count_in_range(codepoints, start, end))
+        .sum::<u32>()
+        + count_in_range(codepoints, POWERLINE_RANGE.0, POWERLINE_RANGE.1);
+    let stray = count_in_range(codepoints, BMP_PRIVATE_USE.0, BMP_PRIVATE_USE.1)
+        .saturating_sub(known);
+    icon_sets >= NERD_FONT_MIN_SETS && stray <= NERD_FONT_MAX_STRAY
+}
+
+/// The shared advance width, if every advance is the same and non-zero
+fn common_advance(advances: &[u16]) -> Option<u16> {
+    let (&first, rest) = advances.split_first()?;
+    (first != 0 && rest.iter().all(|&advance| advance == first)).then_some(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_advance() {
+        assert_eq!(common_advance(&[600, 600, 600]), Some(600));
+        assert_eq!(common_advance(&[600, 500, 600]), None);
+        assert_eq!(common_advance(&[0, 0]), None);
+        assert_eq!(common_advance(&[]), None);
+    }
+
+    #[test]
+    fn test_is_nerd_font() {
+        let powerline: Vec<u32> = (0xE0A0..=0xE0A2).chain(0xE0B0..=0xE0D4).collect();
+        let mut nerd: Vec<u32> = powerline
+            .iter()
+            .copied()
+            .chain(0xE5FA..=0xE6B7) // Seti-UI
+            .chain(0xE700..=0xE8EF) // Devicons
+            .chain(0xF400..=0xF533) // Octicons
+            .chain(0xF0001..=0xF1AF0) // Material Design
+            .collect();
+        nerd.sort_unstable();
+        assert!(is_nerd_font(&nerd));
+
+        // Powerline-patched fonts without icon sets
+        assert!(!is_nerd_font(&powerline));
+
+        // HKSCS-style CJK font mapping the whole BMP private use area
+        let hkscs: Vec<u32> = (0x4E00..=0x9FFF).chain(0xE000..=0xF848).collect();
+        assert!(!is_nerd_font(&hkscs));
+
+        // Icon font with the Nerd ranges but no Powerline glyphs
+        let icons: Vec<u32> = (0xE700..=0xE8EF)
+            .chain(0xF300..=0xF381)
+            .chain(0xF400..=0xF533)
+            .collect();
+        assert!(!is_nerd_font(&icons));
+    }
+
+    #[test]
+    fn test_nerd_font_ranges_are_sorted() {
+        for pair in NERD_FONT_RANGES.windows(2) {
+            assert!(pair[0].0 <= pair[0].1 && pair[0].1 < pair[1].0);
+        }
+        for &(start, end) in NERD_FONT_RANGES {
+            assert!(end < POWERLINE_RANGE.0 || start > POWERLINE_RANGE.1, "{:X} overlaps Powerline", start);
+        }
+    }
+
+    #[test]
+    fn test_powerline_extra_is_counted_once() {
+        // Full Powerline and Powerline Extra, three icon sets and 80 stray code points
+        let mut codepoints: Vec<u32> = (0xE0A0..=0xE0D4)
+            .chain(0xE100..0xE150)
+            .chain(0xE5FA..=0xE6B7)
+            .chain(0xE700..=0xE8EF)
+            .chain(0xF400..=0xF533)
+            .collect();
+        codepoints.sort_unstable();
+        assert!(!is_nerd_font(&codepoints));
+
+        // Powerline Extra alone is not an icon set
+        let powerline_extra: Vec<u32> = (0xE0A0..=0xE0D7).chain(0xE700..=0xE8EF).collect();
+        assert!(!is_nerd_font(&powerline_extra));
+    }
+}
@@ -0,0 +1,324 @@
+use super::encoding::{decode_name_bytes, mac_language_tag};
+use super::normalize::normalize_record;
+use super::raw;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use ttf_parser::name::Name;
+use ttf_parser::{name_id, Face, PlatformId};
+
+/// Localized names for a single language, keyed by BCP 47 tag in `FontInfo::localized_names`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LocalizedNames {
+    pub family: Option<String>,             // Name ID 1
+    pub full_name: Option<String>,          // Name ID 4
+    pub typographic_family: Option<String>, // Name ID 16
+}
+
+/// Windows language IDs (LCID) to BCP 47 tags
+/// see https://learn.microsoft.com/typography/opentype/spec/name#windows-language-ids
+const WINDOWS_LANGUAGE_TAGS: &[(u16, &str)] = &[
+    (0x0401, "ar-SA"),
+    (0x0402, "bg-BG"),
+    (0x0403, "ca-ES"),
+    (0x0404, "zh-TW"),
+    (0x0405, "cs-CZ"),
+    (0x0406, "da-DK"),
+    (0x0407, "de-DE"),
+    (0x0408, "el-GR"),
+    (0x0409, "en-US"),
+    (0x040A, "es-ES"),
+    (0x040B, "fi-FI"),
+    (0x040C, "fr-FR"),
+    (0x040D, "he-IL"),
+    (0x040E, "hu-HU"),
+    (0x040F, "is-IS"),
+    (0x0410, "it-IT"),
+    (0x0411, "ja-JP"),
+    (0x0412, "ko-KR"),
+    (0x0413, "nl-NL"),
+    (0x0414, "nb-NO"),
+    (0x0415, "pl-PL"),
+    (0x0416, "pt-BR"),
+    (0x0418, "ro-RO"),
+    (0x0419, "ru-RU"),
+    (0x041A, "hr-HR"),
+    (0x041B, "sk-SK"),
+    (0x041C, "sq-AL"),
+    (0x041D, "sv-SE"),
+    (0x041E, "th-TH"),
+    (0x041F, "tr-TR"),
+    (0x0420, "ur-PK"),
+    (0x0421, "id-ID"),
+    (0x0422, "uk-UA"),
+    (0x0423, "be-BY"),
+    (0x0424, "sl-SI"),
+    (0x0425, "et-EE"),
+    (0x0426, "lv-LV"),
+    (0x0427, "lt-LT"),
+    (0x0429, "fa-IR"),
+    (0x042A, "vi-VN"),
+    (0x042B, "hy-AM"),
+    (0x042D, "eu-ES"),
+    (0x042F, "mk-MK"),
+    (0x0436, "af-ZA"),
+    (0x0437, "ka-GE"),
+    (0x0439, "hi-IN"),
+    (0x043E, "ms-MY"),
+    (0x043F, "kk-KZ"),
+    (0x0441, "sw-KE"),
+    (0x0445, "bn-IN"),
+    (0x0446, "pa-IN"),
+    (0x0447, "gu-IN"),
+    (0x0449, "ta-IN"),
+    (0x044A, "te-IN"),
+    (0x044B, "kn-IN"),
+    (0x044C, "ml-IN"),
+    (0x044E, "mr-IN"),
+    (0x0450, "mn-MN"),
+    (0x0451, "bo-CN"),
+    (0x0452, "cy-GB"),
+    (0x0453, "km-KH"),
+    (0x0454, "lo-LA"),
+    (0x0456, "gl-ES"),
+    (0x045B, "si-LK"),
+    (0x0461, "ne-NP"),
+    (0x0464, "fil-PH"),
+    (0x0465, "dv-MV"),
+    (0x0478, "ii-CN"),
+    (0x0480, "ug-CN"),
+    (0x0801, "ar-IQ"),
+    (0x0804, "zh-CN"),
+    (0x0807, "de-CH"),
+    (0x0809, "en-GB"),
+    (0x080A, "es-MX"),
+    (0x080C, "fr-BE"),
+    (0x0810, "it-CH"),
+    (0x0813, "nl-BE"),
+    (0x0814, "nn-NO"),
+    (0x0816, "pt-PT"),
+    (0x0843, "uz-Cyrl-UZ"),
+    (0x0850, "mn-Mong-CN"),
+    (0x0C01, "ar-EG"),
+    (0x0C04, "zh-HK"),
+    (0x0C07, "de-AT"),
+    (0x0C09, "en-AU"),
+    (0x0C0A, "es-ES"),
+    (0x0C0C, "fr-CA"),
+    (0x1004, "zh-SG"),
+    (0x1009, "en-CA"),
+    (0x100C, "fr-CH"),
+    (0x1404, "zh-MO"),
+    (0x1409, "en-NZ"),
+    (0x1809, "en-IE"),
+    (0x4009, "en-IN"),
+];
+
+/// Map a Windows language ID to a BCP 47 tag
+pub fn windows_language_tag(language_id: u16) -> Option<&'static str> {
+    WINDOWS_LANGUAGE_TAGS
+        .binary_search_by_key(&language_id, |&(id, _)| id)
+        .ok()
+        .map(|index| WINDOWS_LANGUAGE_TAGS[index].1)
+}
+
+/// Resolve the BCP 47 tag for a name record.
+/// Language IDs >= 0x8000 refer to the name table's own language-tag records (format 1).
+pub fn record_language_tag(
+    platform_id: PlatformId,
+    language_id: u16,
+    lang_tags: &[String],
+) -> Option<String> {
+    if language_id >= 0x8000 {
+        return lang_tags.get((language_id - 0x8000) as usize).cloned();
+    }
+    match platform_id {
+        PlatformId::Windows => windows_language_tag(language_id).map(str::to_string),
+        PlatformId::Macintosh => mac_language_tag(language_id).map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Decode and normalize a name record (see `normalize::normalize_record`)
+pub fn decode_name(name: &Name) -> Option<String> {
+    let language = record_language_tag(name.platform_id, name.language_id, &[]);
+    normalize_record(name, language.as_deref()).map(|normalized| normalized.value)
+}
+
+/// Decode a name record of any supported encoding, including Macintosh scripts
+/// and Windows legacy code pages (ShiftJIS, PRC, Big5, Wansung, Johab), without normalization
+pub fn decode_name_raw(name: &Name) -> Option<String> {
+    if name.is_unicode() {
+        return name.to_string();
+    }
+    decode_name_bytes(name.platform_id as u16, name.encoding_id, name.name)
+}
+
+/// A single name table record, including records that cannot be decoded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameRecord {
+    pub platform_id: u16,
+    pub encoding_id: u16,
+    pub language_id: u16,
+    pub language: Option<String>, // BCP 47 标签，未知语言为 None
+    pub name_id: u16,
+    pub value: Option<String>,    // 解码结果 (未规范化)，不支持的编码为 None
+    pub raw_hex: String,          // 原始字节，十六进制
+}
+
+/// Read every record of the name table of a font file
+pub fn read_name_table(path: String, face_index: u32) -> Result<Vec<NameRecord>, String> {
+    raw::with_face(&path, face_index, name_table_records)
+}
+
+/// List every name record in table order
+pub fn name_table_records(face: &Face) -> Vec<NameRecord> {
+    let lang_tags = name_table_lang_tags(face);
+    face.names()
+        .into_iter()
+        .map(|name| NameRecord {
+            platform_id: name.platform_id as u16,
+            encoding_id: name.encoding_id,
+            language_id: name.language_id,
+            language: record_language_tag(name.platform_id, name.language_id, &lang_tags),
+            name_id: name.name_id,
+            value: decode_name_raw(&name),
+            raw_hex: to_hex(name.name),
+        })
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Read the language-tag records of a format 1 name table
+pub fn name_table_lang_tags(face: &Face) -> Vec<String> {
+    let mut tags = Vec::new();
+    let Some(data) = raw::table(face, b"name") else {
+        return tags;
+    };
+    if raw::read_u16(data, 0) != Some(1) {
+        return tags;
+    }
+    let (Some(count), Some(storage_offset)) = (raw::read_u16(data, 2), raw::read_u16(data, 4)) else {
+        return tags;
+    };
+
+    // langTagCount follows the 12-byte name records
+    let lang_tag_offset = 6 + count as usize * 12;
+    let lang_tag_count = raw::read_u16(data, lang_tag_offset).unwrap_or(0);
+    for i in 0..lang_tag_count as usize {
+        let record = lang_tag_offset + 2 + i * 4;
+        let (Some(length), Some(offset)) = (raw::read_u16(data, record), raw::read_u16(data, record + 2)) else {
+            break;
+        };
+        let start = storage_offset as usize + offset as usize;
+        let tag = data
+            .get(start..start + length as usize)
+            .map(decode_utf16_be)
+            .unwrap_or_default();
+        tags.push(tag);
+    }
+    tags
+}
+
+/// Collect family/full/typographic names for every language present in the name table
+pub fn extract_localized_names(face: &Face) -> BTreeMap<String, LocalizedNames> {
+    let lang_tags = name_table_lang_tags(face);
+    let mut localized: BTreeMap<String, LocalizedNames> = BTreeMap::new();
+
+    // Unicode records first, so legacy-encoded records only fill languages that are otherwise missing
+    let mut records: Vec<Name> = face.names().into_iter().collect();
+    records.sort_by_key(|name| !name.is_unicode());
+
+    for name in records {
+        if !matches!(
+            name.name_id,
+            name_id::FAMILY | name_id::FULL_NAME | name_id::TYPOGRAPHIC_FAMILY
+        ) {
+            continue;
+        }
+        let Some(tag) = record_language_tag(name.platform_id, name.language_id, &lang_tags) else {
+            continue;
+        };
+        let Some(value) = normalize_record(&name, Some(&tag))
+            .map(|normalized| normalized.value)
+            .filter(|s| !s.is_empty())
+        else {
+            continue;
+        };
+
+        let entry = localized.entry(tag).or_default();
+        let slot = match name.name_id {
+            name_id::FAMILY => &mut entry.family,
+            name_id::FULL_NAME => &mut entry.full_name,
+            _ => &mut entry.typographic_family,
+        };
+        // Keep the first record when a language appears more than once (e.g. 0x040A and 0x0C0A)
+        if slot.is_none() {
+            *slot = Some(value);
+        }
+    }
+
+    localized
+}
+
+fn decode_utf16_be(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_language_tag() {
+        assert_eq!(windows_language_tag(0x0409), Some("en-US"));
+        assert_eq!(windows_language_tag(0x0804), Some("zh-CN"));
+        assert_eq!(windows_language_tag(0x0404), Some("zh-TW"));
+        assert_eq!(windows_language_tag(0x0411), Some("ja-JP"));
+        assert_eq!(windows_language_tag(0x0412), Some("ko-KR"));
+        assert_eq!(windows_language_tag(0x7FFF), None);
+    }
+
+    #[test]
+    fn test_language_table_sorted() {
+        // binary_search requires the table to be sorted by language ID
+        for pair in WINDOWS_LANGUAGE_TAGS.windows(2) {
+            assert!(pair[0].0 < pair[1].0, "{:#06x} out of order", pair[1].0);
+        }
+    }
+
+    #[test]
+    fn test_to_hex() {
+        assert_eq!(to_hex(&[0x00, 0x41, 0xFF]), "00 41 FF");
+        assert_eq!(to_hex(&[]), "");
+    }
+
+    #[test]
+    fn test_record_language_tag() {
+        let lang_tags = vec!["zh-Hant".to_string(), "ja".to_string()];
+        assert_eq!(
+            record_language_tag(PlatformId::Windows, 0x0412, &lang_tags),
+            Some("ko-KR".to_string())
+        );
+        assert_eq!(
+            record_language_tag(PlatformId::Unicode, 0x8001, &lang_tags),
+            Some("ja".to_string())
+        );
+        assert_eq!(record_language_tag(PlatformId::Unicode, 0x8002, &lang_tags), None);
+        assert_eq!(
+            record_language_tag(PlatformId::Macintosh, 11, &lang_tags),
+            Some("ja".to_string())
+        );
+        assert_eq!(record_language_tag(PlatformId::Unicode, 0, &lang_tags), None);
+    }
+}
@@ -0,0 +1,250 @@
+//! Normalization and mojibake repair for name table strings
+use super::encoding::decode_name_bytes;
+use super::names::{decode_name_raw, name_table_lang_tags, record_language_tag};
+use serde::{Deserialize, Serialize};
+use ttf_parser::name::Name;
+use ttf_parser::Face;
+use unicode_normalization::UnicodeNormalization;
+
+/// A single change applied while normalizing a name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NameFix {
+    RepairedLegacyEncoding, // UTF-16 记录中实际存放的是 GBK/Big5/ShiftJIS/EUC-KR 字节
+    RepairedUtf8,           // UTF-8 字节被按 Latin-1 解码 (如 "CafÃ©")
+    TrimmedWhitespace,      // 首尾空白、NUL 填充
+    StrippedControlChars,   // 内部控制字符
+    NarrowedFullWidth,      // 与半角混排的全角 ASCII 转为半角
+    NfcNormalized,          // Unicode NFC 规范化
+}
+
+/// A name record whose decoded value was altered by normalization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameRepair {
+    pub name_id: u16,
+    pub platform_id: u16,
+    pub encoding_id: u16,
+    pub language_id: u16,
+    pub language: Option<String>, // BCP 47 标签
+    pub raw: String,              // 解码后、规范化前的原始值
+    pub value: String,            // 规范化后的值
+    pub fixes: Vec<NameFix>,
+}
+
+/// Decoded name with the normalization applied to it
+#[derive(Debug, Clone)]
+pub struct NormalizedName {
+    pub raw: String,
+    pub value: String,
+    pub fixes: Vec<NameFix>,
+}
+
+/// Decode and normalize a name record
+pub fn normalize_record(name: &Name, language: Option<&str>) -> Option<NormalizedName> {
+    let raw = decode_name_raw(name)?;
+    let mut fixes = Vec::new();
+    let mut value = raw.clone();
+
+    if name.is_unicode() {
+        if let Some(repaired) = repair_legacy_in_utf16(&value, language) {
+            value = repaired;
+            fixes.push(NameFix::RepairedLegacyEncoding);
+        }
+    }
+    if let Some(repaired) = repair_utf8_as_latin1(&value) {
+        value = repaired;
+        fixes.push(NameFix::RepairedUtf8);
+    }
+
+    let (value, text_fixes) = normalize_text(&value);
+    fixes.extend(text_fixes);
+
+    Some(NormalizedName { raw, value, fixes })
+}
+
+/// Collect every name record that normalization changed
+pub fn collect_name_repairs(face: &Face) -> Vec<NameRepair> {
+    let lang_tags = name_table_lang_tags(face);
+    let mut repairs = Vec::new();
+
+    for name in face.names() {
+        let language = record_language_tag(name.platform_id, name.language_id, &lang_tags);
+        let Some(normalized) = normalize_record(&name, language.as_deref()) else {
+            continue;
+        };
+        if normalized.fixes.is_empty() {
+            continue;
+        }
+        repairs.push(NameRepair {
+            name_id: name.name_id,
+            platform_id: name.platform_id as u16,
+            encoding_id: name.encoding_id,
+            language_id: name.language_id,
+            language,
+            raw: normalized.raw,
+            value: normalized.value,
+            fixes: normalized.fixes,
+        });
+    }
+
+    repairs
+}
+
+/// Trim, strip control characters, narrow mixed full-width ASCII and apply NFC
+pub fn normalize_text(text: &str) -> (String, Vec<NameFix>) {
+    let mut fixes = Vec::new();
+
+    let trimmed = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    if trimmed.len() != text.len() {
+        fixes.push(NameFix::TrimmedWhitespace);
+    }
+
+    let mut value: String = trimmed.chars().filter(|c| !c.is_control()).collect();
+    if value.len() != trimmed.len() {
+        fixes.push(NameFix::StrippedControlChars);
+    }
+
+    // Full-width names such as "ＭＳ ゴシック" are intentional; only narrow when mixed with half-width letters
+    let has_full_width = value.chars().any(|c| ('\u{FF01}'..='\u{FF5E}').contains(&c));
+    let has_half_width = value.chars().any(|c| c.is_ascii_alphanumeric());
+    if has_full_width && has_half_width {
+        value = value
+            .chars()
+            .map(|c| match c {
+                '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+                '\u{3000}' => ' ',
+                _ => c,
+            })
+            .collect();
+        fixes.push(NameFix::NarrowedFullWidth);
+    }
+
+    let nfc: String = value.nfc().collect();
+    if nfc != value {
+        value = nfc;
+        fixes.push(NameFix::NfcNormalized);
+    }
+
+    (value, fixes)
+}
+
+/// Detect legacy multi-byte text (GBK, Big5, ShiftJIS, EUC-KR) stored in a UTF-16 record.
+/// Such strings decode to runs of Hangul, Yi, private-use or compatibility ideographs. Only
+/// the code page implied by the record's language is tried, so records without a CJK
+/// language (and real Korean names in them) are never touched.
+fn repair_legacy_in_utf16(text: &str, language: Option<&str>) -> Option<String> {
+    // Windows encoding IDs: 2 ShiftJIS, 3 PRC, 4 Big5, 5 Wansung
+    let encoding = match language? {
+        tag if tag.starts_with("zh-TW") || tag.starts_with("zh-HK") || tag.starts_with("zh-MO") => 4,
+        tag if tag.starts_with("zh") => 3,
+        tag if tag.starts_with("ja") => 2,
+        tag if tag.starts_with("ko") => 5,
+        _ => return None,
+    };
+    let is_korean = encoding == 5;
+    if !text.chars().any(|c| is_mojibake_char(c, is_korean)) {
+        return None;
+    }
+
+    let bytes: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    decode_name_bytes(3, encoding, &bytes).filter(|decoded| decoded.chars().all(|c| is_cjk_name_char(c, is_korean)))
+}
+
+/// Characters that are almost never part of a real font name in this position
+fn is_mojibake_char(c: char, allow_hangul: bool) -> bool {
+    match c as u32 {
+        0xAC00..=0xD7AF => !allow_hangul, // Hangul Syllables
+        0xA000..=0xA4CF => true,          // Yi
+        0xE000..=0xF8FF => true,          // Private Use Area
+        0xF900..=0xFAFF => true,          // CJK Compatibility Ideographs
+        _ => false,
+    }
+}
+
+/// Characters a correctly decoded CJK font name consists of: printable ASCII, ideographs,
+/// kana, CJK punctuation and full-width forms (Hangul only for Korean). Half-width katakana
+/// and control characters mean the guessed code page was wrong.
+fn is_cjk_name_char(c: char, allow_hangul: bool) -> bool {
+    match c as u32 {
+        0x20..=0x7E | 0xB7 => true,
+        0x3000..=0x30FF => true,                                       // CJK punctuation, kana
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0x20000..=0x3134F => true, // CJK Unified Ideographs
+        0xFF01..=0xFF5E => true,                                       // Full-width ASCII
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => allow_hangul,
+        _ => false,
+    }
+}
+
+/// Detect UTF-8 bytes that were decoded as Latin-1 (e.g. "CafÃ©" for "Café")
+fn repair_utf8_as_latin1(text: &str) -> Option<String> {
+    if text.is_ascii() || text.chars().any(|c| c as u32 > 0xFF) {
+        return None;
+    }
+    let bytes: Vec<u8> = text.chars().map(|c| c as u8).collect();
+    String::from_utf8(bytes).ok().filter(|repaired| repaired != text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_text_trims_padding() {
+        let (value, fixes) = normalize_text("Arial\0\0  ");
+        assert_eq!(value, "Arial");
+        assert_eq!(fixes, vec![NameFix::TrimmedWhitespace]);
+
+        let (value, fixes) = normalize_text("Ari\u{0007}al");
+        assert_eq!(value, "Arial");
+        assert_eq!(fixes, vec![NameFix::StrippedControlChars]);
+    }
+
+    #[test]
+    fn test_normalize_text_full_width() {
+        // Mixed full-width and half-width letters are narrowed
+        let (value, fixes) = normalize_text("Noto Ｓａｎｓ");
+        assert_eq!(value, "Noto Sans");
+        assert_eq!(fixes, vec![NameFix::NarrowedFullWidth]);
+
+        // Purely full-width names are left alone
+        let (value, fixes) = normalize_text("ＭＳ ゴシック");
+        assert_eq!(value, "ＭＳ ゴシック");
+        assert!(fixes.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_text_nfc() {
+        // "e" + combining acute accent -> "é"
+        let (value, fixes) = normalize_text("Cafe\u{0301}");
+        assert_eq!(value, "Café");
+        assert_eq!(fixes, vec![NameFix::NfcNormalized]);
+    }
+
+    #[test]
+    fn test_repair_gbk_in_utf16() {
+        // "宋体" GBK bytes CB CE CC E5 read as UTF-16BE give two Hangul syllables
+        let mojibake: String = [0xCBCE_u16, 0xCCE5].iter().map(|&u| char::from_u32(u as u32).unwrap()).collect();
+        assert_eq!(repair_legacy_in_utf16(&mojibake, Some("zh-CN")), Some("宋体".to_string()));
+        // Real Korean names are not touched
+        assert_eq!(repair_legacy_in_utf16("맑은 고딕", Some("ko-KR")), None);
+        assert_eq!(repair_legacy_in_utf16("Arial", Some("en-US")), None);
+    }
+
+    #[test]
+    fn test_hangul_outside_cjk_records_is_kept() {
+        // English, untagged and Japanese records with a real Korean name stay as they are
+        assert_eq!(repair_legacy_in_utf16("맑은 고딕", Some("en-US")), None);
+        assert_eq!(repair_legacy_in_utf16("맑은 고딕", None), None);
+        // ShiftJIS would give half-width katakana and control characters
+        assert_eq!(repair_legacy_in_utf16("맑은 고딕", Some("ja-JP")), None);
+        // Private-use glyph names are not mojibake outside CJK records
+        assert_eq!(repair_legacy_in_utf16("Logo \u{F8FF}", Some("en-US")), None);
+        assert_eq!(repair_legacy_in_utf16("Logo \u{F8FF}", None), None);
+    }
+
+    #[test]
+    fn test_repair_utf8_as_latin1() {
+        assert_eq!(repair_utf8_as_latin1("CafÃ©"), Some("Café".to_string()));
+        assert_eq!(repair_utf8_as_latin1("Café"), None);
+        assert_eq!(repair_utf8_as_latin1("Arial"), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! Outline format, hinting and embedded bitmap detection
+use super::names::decode_name_raw;
+use super::raw::{self, read_i16, read_u16, read_u32};
+use serde::{Deserialize, Serialize};
+use ttf_parser::{name_id, Face};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OutlineFormat {
+    TrueType, // glyf
+    Cff,      // CFF (PostScript)
+    Cff2,     // CFF2 (可变 PostScript)
+    None,     // 无轮廓，仅位图 (如 sbix/CBDT 字体)
+}
+
+/// Rasterization-related attributes of a face
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenderingInfo {
+    pub outline_format: OutlineFormat,
+    pub has_fpgm: bool,             // 字体程序
+    pub has_prep: bool,             // 控制值程序
+    pub has_cvt: bool,              // 控制值表
+    pub hinted: bool,               // 含 TrueType 指令 (fpgm/prep 或字形指令)；仅有 cvt 不算
+    pub autohinted: bool,           // 版本字符串含 ttfautohint 签名
+    pub has_gasp: bool,
+    pub has_embedded_bitmaps: bool, // EBDT/EBLC, CBDT/CBLC, sbix
+}
+
+/// Bitmap tables: monochrome/grayscale strikes (EBDT, Apple bdat) and color bitmaps (CBDT, sbix)
+const BITMAP_TABLES: &[&[u8; 4]] = &[b"EBDT", b"EBLC", b"bdat", b"bloc", b"CBDT", b"CBLC", b"sbix"];
+
+/// Detect outline format, hinting programs, gasp and embedded bitmaps
+pub fn detect_rendering_info(face: &Face) -> RenderingInfo {
+    let has = |tag: &[u8; 4]| raw::table(face, tag).is_some();
+
+    let outline_format = if has(b"glyf") {
+        OutlineFormat::TrueType
+    } else if has(b"CFF2") {
+        OutlineFormat::Cff2
+    } else if has(b"CFF ") {
+        OutlineFormat::Cff
+    } else {
+        OutlineFormat::None
+    };
+
+    let has_fpgm = has(b"fpgm");
+    let has_prep = has(b"prep");
+    let has_cvt = has(b"cvt ");
+
+    RenderingInfo {
+        outline_format,
+        has_fpgm,
+        has_prep,
+        has_cvt,
+        hinted: has_fpgm || has_prep || has_glyph_instructions(face),
+        autohinted: is_autohinted(face),
+        has_gasp: has(b"gasp"),
+        has_embedded_bitmaps: BITMAP_TABLES.iter().any(|tag| has(tag)),
+    }
+}
+
+/// Whether any simple glyph carries TrueType instructions. A cvt table alone holds no
+/// program, so fonts without fpgm/prep are only hinted if their glyphs are.
+fn has_glyph_instructions(face: &Face) -> bool {
+    let (Some(glyf), Some(loca), Some(head)) = (raw::table(face, b"glyf"), raw::table(face, b"loca"), raw::table(face, b"head"))
+    else {
+        return false;
+    };
+    let long_offsets = read_i16(head, 50) == Some(1);
+    let offset = |i: usize| match long_offsets {
+        true => read_u32(loca, i * 4).map(|offset| offset as usize),
+        false => read_u16(loca, i * 2).map(|offset| offset as usize * 2),
+    };
+
+    (0..face.number_of_glyphs() as usize).any(|gid| {
+        let (Some(start), Some(end)) = (offset(gid), offset(gid + 1)) else {
+            return false;
+        };
+        let Some(glyph) = glyf.get(start..end) else {
+            return false;
+        };
+        // Composite glyphs are skipped; their components are simple glyphs
+        match read_i16(glyph, 0) {
+            Some(contours) if contours >= 0 => read_u16(glyph, 10 + contours as usize * 2).is_some_and(|length| length > 0),
+            _ => false,
+        }
+    })
+}
+
+/// ttfautohint appends "; ttfautohint (vX.Y)" to the version string unless run with --no-info
+fn is_autohinted(face: &Face) -> bool {
+    face.names()
+        .into_iter()
+        .filter(|name| name.name_id == name_id::VERSION)
+        .filter_map(|name| decode_name_raw(&name))
+        .any(|version| version.to_lowercase().contains("ttfautohint"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::health::tests::build_font;
+
+    /// Short loca and a glyf with one simple glyph whose instructionLength is `instructions`
+    fn glyph_tables(instructions: u16) -> Vec<(&'static [u8; 4], Vec<u8>)> {
+        let mut glyph = vec![0, 1, 0, 0, 0, 0, 0, 10, 0, 10, 0, 0];
+        glyph.extend_from_slice(&instructions.to_be_bytes());
+        glyph.resize((glyph.len() + instructions as usize + 2).div_ceil(2) * 2, 0);
+        let loca = [0u16, (glyph.len() / 2) as u16].iter().flat_map(|v| v.to_be_bytes()).collect();
+        vec![(b"glyf", glyph), (b"loca", loca)]
+    }
+
+    /// name table with a single Windows English Version string (Name ID 5)
+    fn version_name(version: &str) -> Vec<u8> {
+        let string: Vec<u8> = version.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        let mut name: Vec<u8> = [0u16, 1, 18, 3, 1, 0x0409, 5, string.len() as u16, 0]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        name.extend(string);
+        name
+    }
+
+    fn rendering(tables: &[(&[u8; 4], Vec<u8>)]) -> RenderingInfo {
+        let data = build_font(1, tables);
+        detect_rendering_info(&Face::parse(&data, 0).unwrap())
+    }
+
+    #[test]
+    fn test_hinting_detection() {
+        let unhinted = rendering(&glyph_tables(0));
+        assert_eq!(unhinted.outline_format, OutlineFormat::TrueType);
+        assert!(!unhinted.hinted);
+
+        // cvt without any program is not hinting
+        let mut tables = glyph_tables(0);
+        tables.push((b"cvt ", vec![0; 4]));
+        let cvt_only = rendering(&tables);
+        assert!(cvt_only.has_cvt && !cvt_only.hinted);
+
+        tables.push((b"prep", vec![0xB0, 0x01]));
+        assert!(rendering(&tables).hinted);
+
+        // Instructions inside the glyphs are enough
+        assert!(rendering(&glyph_tables(3)).hinted);
+    }
+
+    #[test]
+    fn test_autohint_detection() {
+        let mut tables = glyph_tables(3);
+        tables.push((b"name", version_name("Version 2.001; ttfautohint (v1.8.3)")));
+        assert!(rendering(&tables).autohinted);
+
+        tables.pop();
+        tables.push((b"name", version_name("Version 2.001")));
+        assert!(!rendering(&tables).autohinted);
+    }
+}
@@ -0,0 +1,59 @@
+//! Big-endian helpers for reading table fields that ttf-parser does not expose
+use std::fs;
+use ttf_parser::{Face, Tag};
+
+/// Read a font file and run `f` on the face at `face_index`
+pub(crate) fn with_face<T>(path: &str, face_index: u32, f: impl FnOnce(&Face) -> T) -> Result<T, String> {
+    let font_data = fs::read(path).map_err(|e| e.to_string())?;
+    let face = Face::parse(&font_data, face_index).map_err(|e| e.to_string())?;
+    Ok(f(&face))
+}
+
+/// Get the raw bytes of a table by its 4-byte tag (e.g. b"OS/2")
+pub(crate) fn table<'a>(face: &Face<'a>, tag: &[u8; 4]) -> Option<&'a [u8]> {
+    face.raw_face().table(Tag::from_bytes(tag))
+}
+
+pub(crate) fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+pub(crate) fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    read_u16(data, offset).map(|v| v as i16)
+}
+
+pub(crate) fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+pub(crate) fn read_i64(data: &[u8], offset: usize) -> Option<i64> {
+    let high = read_u32(data, offset)? as u64;
+    let low = read_u32(data, offset.checked_add(4)?)? as u64;
+    Some(((high << 32) | low) as i64)
+}
+
+/// Read a 16.16 fixed-point number
+pub(crate) fn read_fixed(data: &[u8], offset: usize) -> Option<f64> {
+    read_u32(data, offset).map(|v| v as i32 as f64 / 65536.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_big_endian() {
+        let data = [0x12, 0x34, 0x56, 0x78, 0xFF, 0xFE];
+        assert_eq!(read_u16(&data, 0), Some(0x1234));
+        assert_eq!(read_u32(&data, 0), Some(0x12345678));
+        assert_eq!(read_i16(&data, 4), Some(-2));
+        assert_eq!(read_i64(&[0, 0, 0, 1, 0, 0, 0, 2], 0), Some(0x1_0000_0002));
+        assert_eq!(read_fixed(&[0xFF, 0xF4, 0x80, 0x00], 0), Some(-11.5));
+        // Out of bounds reads return None instead of panicking
+        assert_eq!(read_u16(&data, 5), None);
+        assert_eq!(read_u32(&data, 3), None);
+        assert_eq!(read_u32(&data, usize::MAX), None);
+    }
+}
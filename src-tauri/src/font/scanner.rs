@@ -1,5 +1,21 @@
+use super::bitmap::extract_bitmap_strikes;
+use super::color::detect_color_info;
+use super::coverage::{check_os2_coverage, unicode_codepoints};
+use super::features::layout_feature_tags;
+use super::health::check_health;
+use super::ligatures::detect_programming_ligatures;
+use super::lint::{lint_duplicate_postscript_names, lint_face};
+use super::cjk_style::detect_cjk_style;
+use super::classification::classify_font;
+use super::math::detect_math_info;
 use super::models::*;
-use super::state::FontState;
+use super::monospace::detect_monospace;
+use super::names::{decode_name, extract_localized_names};
+use super::normalize::collect_name_repairs;
+use super::outline::detect_rendering_info;
+use super::state::{FamilyGrouping, FontState};
+use super::style_name::{infer_style_attributes, StyleAttributes};
+use super::vertical::detect_vertical_info;
 use std::fs;
 use std::path::PathBuf;
 use std::time::SystemTime;
@@ -30,6 +46,11 @@ impl FontScanner {
 
     /// Scan all fonts in the system and return FontState
     pub fn scan_all_fonts(&self) -> Result<FontState, String> {
+        self.scan_all_fonts_grouped(FamilyGrouping::default())
+    }
+
+    /// Scan all fonts in the system and group their families by `grouping`
+    pub fn scan_all_fonts_grouped(&self, grouping: FamilyGrouping) -> Result<FontState, String> {
         let mut fonts = Vec::new();
         let mut errors = Vec::new();
 
@@ -85,8 +106,11 @@ impl FontScanner {
             }
         }
 
+        // Cross-font lints
+        lint_duplicate_postscript_names(&mut fonts);
+
         // Create FontState which automatically aggregates CSS font families
-        let state = FontState::new(fonts);
+        let state = FontState::with_grouping(fonts, grouping);
         println!(
             "Aggregated into {} CSS font families",
             state.css_font_family_count()
@@ -133,7 +157,7 @@ impl FontScanner {
         for face_index in 0..face_count {
             match ttf_parser::Face::parse(&data, face_index) {
                 Ok(face) => {
-                    match self.create_font_info(&face, path, &metadata, format.clone()) {
+                    match self.create_font_info(&face, face_index, path, &metadata, format.clone()) {
                         Ok(font_info) => {
                             fonts.push(font_info);
                         }
@@ -163,6 +187,7 @@ impl FontScanner {
     fn create_font_info(
         &self,
         face: &ttf_parser::Face,
+        face_index: u32,
         path: &PathBuf,
         metadata: &std::fs::Metadata,
         format: FontFormat,
@@ -174,20 +199,8 @@ impl FontScanner {
         let style = Self::extract_name(face, ttf_parser::name_id::SUBFAMILY)
             .unwrap_or_else(|| "Regular".to_string());
 
-        // Extract Chinese (PRC) localized names - language_id = 0x0804
-        let mut family_zh = Self::extract_name_by_language(face, ttf_parser::name_id::FAMILY, 0x0804);
-        let mut full_name_zh = Self::extract_name_by_language(face, ttf_parser::name_id::FULL_NAME, 0x0804);
-        // If not found try language_id = 0x0c04 (Hong Kong)
-        if family_zh.is_none() {
-            family_zh = Self::extract_name_by_language(face, ttf_parser::name_id::FAMILY, 0x1004);
-            full_name_zh = Self::extract_name_by_language(face, ttf_parser::name_id::FULL_NAME, 0x1004);
-        }
-        // If still not found, try Taiwan (0x0404)
-        if family_zh.is_none() {
-            family_zh = Self::extract_name_by_language(face, ttf_parser::name_id::FAMILY, 0x0404);
-            full_name_zh = Self::extract_name_by_language(face, ttf_parser::name_id::FULL_NAME, 0x0404);
-        }
-
+        // Extract localized names for every language in the name table
+        let localized_names = extract_localized_names(face);
 
         // Generate unique ID
         let id_source = format!("{}-{}-{}", path.to_string_lossy(), family, style);
@@ -209,17 +222,69 @@ impl FontScanner {
         // Extract CSS font-family name (priority: ID 16 > ID 1 > ID 21)
         let css_font_family = Self::extract_css_font_family(face);
 
-        // Extract font weight (priority: fvar wght axis for variable fonts > OS/2 usWeightClass)
-        let weight = Self::extract_weight(face);
+        // Weight, width and slope from style names (English, CJK, W-numbers) reconciled with OS/2
+        let style_name = font_metadata.typographic_subfamily.as_deref().unwrap_or(&style);
+        let mut style_names = vec![family.as_str(), full_name.as_str()];
+        style_names.extend(
+            localized_names
+                .values()
+                .flat_map(|names| [&names.family, &names.full_name, &names.typographic_family])
+                .filter_map(|name| name.as_deref()),
+        );
+        let style_attributes = infer_style_attributes(face, style_name, &style_names);
+
+        // Extract font weight (priority: fvar wght axis for variable fonts > reconciled style weight)
+        let weight = Self::extract_weight(face, &style_attributes);
+
+        // Walk the cmap once; coverage, terminal and math detection all count in it
+        let codepoints = unicode_codepoints(face);
+
+        // Cross-check OS/2 declared ranges against cmap coverage
+        let os2_coverage = check_os2_coverage(face, &codepoints);
 
+        // Record name records altered by normalization / mojibake repair
+        let name_repairs = collect_name_repairs(face);
 
-        Ok(FontInfo {
+        // Verify checksums and table structure
+        let health = check_health(face);
+
+        // Outline format, hinting programs and embedded bitmaps
+        let rendering = detect_rendering_info(face);
+        let bitmap_strikes = extract_bitmap_strikes(face);
+
+        // Color font technologies
+        let color = detect_color_info(face);
+
+        // Fixed pitch and terminal glyph coverage
+        let monospace = detect_monospace(face, &codepoints);
+        let ligatures = detect_programming_ligatures(face);
+
+        // OpenType layout features (tnum, smcp, vert, ssXX ...)
+        let features = layout_feature_tags(face);
+        let vertical = detect_vertical_info(face);
+        let math = detect_math_info(face, &codepoints);
+
+        // Serif/sans/script/display/monospace category from PANOSE, sFamilyClass, names and glyph shape
+        let mut class_names = vec![family.as_str(), css_font_family.as_str()];
+        class_names.extend(
+            localized_names
+                .values()
+                .flat_map(|names| [names.family.as_deref(), names.typographic_family.as_deref()])
+                .flatten(),
+        );
+        let classification = classify_font(face, &class_names, &monospace);
+
+        // 宋/黑/楷/仿宋/圆 from every localized name and the stroke endings of 一/十
+        let cjk_style = detect_cjk_style(face, &style_names);
+
+        let mut font_info = FontInfo {
             id,
             family,
             full_name,
             postscript_name,
             style,
             path: path.to_string_lossy().to_string(),
+            face_index,
             file_size: metadata.len(),
             format,
             is_variable: face.is_variable(),
@@ -232,51 +297,64 @@ impl FontScanner {
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap()
                 .as_secs() as i64,
-            family_zh,
-            full_name_zh,
+            localized_names,
             css_font_family,
-        })
+            os2_coverage,
+            name_repairs,
+            health,
+            lint_warnings: Vec::new(),
+            rendering,
+            bitmap_strikes,
+            color,
+            monospace,
+            ligatures,
+            features,
+            vertical,
+            math,
+            classification,
+            cjk_style,
+            style_attributes,
+            variation_axes: Self::extract_variation_axes(face),
+        };
+
+        // Metadata consistency lints need the assembled FontInfo
+        font_info.lint_warnings = lint_face(face, &font_info);
+
+        Ok(font_info)
     }
 
     /// Extract name from font with multiple attempts
     /// name_id: OpenType Name ID
     /// see https://docs.rs/ttf-parser/latest/ttf_parser/name/struct.Name.html
-    fn extract_name(face: &ttf_parser::Face, ot_name_id: u16) -> Option<String> {
-        // Priority order: English, then any Unicode
-        let mut fallback = None;
+    pub(crate) fn extract_name(face: &ttf_parser::Face, ot_name_id: u16) -> Option<String> {
+        // Priority order: Windows English, Mac English, any Unicode, any legacy-encoded record
+        let mut best: Option<(u8, String)> = None;
 
         for face_name in face.names() {
-            if face_name.name_id == ot_name_id {
-                if face_name.is_unicode() {
-                    if let Some(s) = face_name.to_string() {
-                        // Prefer English (language_id == 0x0409)
-                        if face_name.language_id == 0x0409 {
-                            return Some(s);
-                        }
-                        // Store first Unicode name as fallback
-                        if fallback.is_none() {
-                            fallback = Some(s.clone());
-                        }
-                    }
-                }
+            if face_name.name_id != ot_name_id {
+                continue;
             }
-        }
-
-        fallback
-    }
-
-    /// Extract ot name for specific language
-    fn extract_name_by_language(face: &ttf_parser::Face, name_id: u16, language_id: u16) -> Option<String> {
-        for name in face.names() {
-            if name.name_id == name_id && name.language_id == language_id {
-                if name.is_unicode() {
-                    if let Some(s) = name.to_string() {
-                        return Some(s);
-                    }
-                }
+            let Some(s) = decode_name(&face_name) else {
+                continue;
+            };
+            let rank = if face_name.is_unicode() && face_name.language_id == 0x0409 {
+                return Some(s);
+            } else if face_name.platform_id == ttf_parser::PlatformId::Macintosh
+                && face_name.language_id == 0
+            {
+                1
+            } else if face_name.is_unicode() {
+                2
+            } else {
+                3
+            };
+            // Keep the first record of the best rank seen so far
+            if best.as_ref().is_none_or(|(best_rank, _)| rank < *best_rank) {
+                best = Some((rank, s));
             }
         }
-        None
+
+        best.map(|(_, s)| s)
     }
 
     /// Extract name with fallback to family name
@@ -311,9 +389,22 @@ impl FontScanner {
         "Unknown".to_string()
     }
 
+    /// Extract the fvar axes; hidden axes are kept since CSS can still set them
+    fn extract_variation_axes(face: &ttf_parser::Face) -> Vec<VariationAxis> {
+        face.variation_axes()
+            .into_iter()
+            .map(|axis| VariationAxis {
+                tag: String::from_utf8_lossy(&axis.tag.to_bytes()).into_owned(),
+                min: axis.min_value,
+                default: axis.def_value,
+                max: axis.max_value,
+            })
+            .collect()
+    }
+
     /// Extract font weight with Chrome matching priority
-    /// Priority: fvar wght axis (variable fonts) > OS/2 usWeightClass > fallback 400
-    fn extract_weight(face: &ttf_parser::Face) -> u16 {
+    /// Priority: fvar wght axis (variable fonts) > OS/2 usWeightClass / style name > fallback 400
+    fn extract_weight(face: &ttf_parser::Face, style_attributes: &StyleAttributes) -> u16 {
         // 1. For variable fonts, try fvar table wght axis default value
         if face.is_variable() {
             for axis in face.variation_axes() {
@@ -324,8 +415,8 @@ impl FontScanner {
             }
         }
 
-        // 2. For static fonts, use face.weight() which reads OS/2 usWeightClass
-        face.weight().to_number()
+        // 2. For static fonts, use OS/2 usWeightClass unless the style name clearly disagrees
+        style_attributes.weight
     }
 
     /// Extract font metadata from OpenType/TrueType naming table
@@ -357,12 +448,14 @@ impl FontScanner {
             license: Self::extract_name(face, ttf_parser::name_id::LICENSE),                    // 13
             license_url: Self::extract_name(face, ttf_parser::name_id::LICENSE_URL),            // 14
 
-            // Extended Name IDs (16-20) - May not be present in all fonts
+            // Extended Name IDs (16-22) - May not be present in all fonts
             typographic_family: Self::extract_name(face, 16),                                   // 16 - Preferred Family
             typographic_subfamily: Self::extract_name(face, 17),                                // 17 - Preferred Subfamily
             compatible_full: Self::extract_name(face, 18),                                      // 18 - Compatible Full Name
             sample_text: Self::extract_name(face, 19),                                          // 19 - Sample Text
             postscript_cid: Self::extract_name(face, 20),                                       // 20 - PostScript CID
+            wws_family: Self::extract_name(face, 21),                                           // 21 - WWS Family
+            wws_subfamily: Self::extract_name(face, 22),                                        // 22 - WWS Subfamily
         }
     }
 
@@ -1,34 +1,77 @@
-use super::models::{CssFontFamily, FontInfo};
+use super::classification::classify_family;
+use super::models::{CssFontFamily, FamilyStyle, FontInfo, RibbiMembers};
+use super::style_name::FontSlope;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::RwLock;
+
+/// Which name groups faces into families; Windows GDI, browsers and design apps each
+/// group the same files differently
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FamilyGrouping {
+    #[default]
+    Typographic, // ID 16 > ID 1：浏览器 CSS font-family 与设计软件
+    Wws,         // ID 21 > ID 16 > ID 1：DirectWrite 字重/字宽/倾斜 (WWS) 模型
+    Legacy,      // ID 1：Windows GDI，每个家族最多 RIBBI 四个样式
+}
 
 /// FontState - stores scanned fonts and aggregated CSS font family data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FontState {
     pub fonts: Vec<FontInfo>,
     pub css_font_families: Vec<CssFontFamily>,
+    pub grouping: FamilyGrouping, // css_font_families 的分组方式
 }
 
 impl FontState {
     /// Create a new FontState from a list of FontInfo
     /// Automatically aggregates CSS font families and determines default fonts
     pub fn new(fonts: Vec<FontInfo>) -> Self {
-        let css_font_families = Self::aggregate_css_font_families(&fonts);
+        Self::with_grouping(fonts, FamilyGrouping::default())
+    }
+
+    /// Create a FontState whose families are grouped by `grouping`
+    pub fn with_grouping(fonts: Vec<FontInfo>, grouping: FamilyGrouping) -> Self {
+        let css_font_families = Self::aggregate_css_font_families(&fonts, grouping);
         Self {
             fonts,
             css_font_families,
+            grouping,
         }
     }
 
-    /// Aggregate fonts by css_font_family name and determine default font for each family
-    /// Default font is the one with weight closest to 400
-    fn aggregate_css_font_families(fonts: &[FontInfo]) -> Vec<CssFontFamily> {
-        // Group fonts by css_font_family name
+    /// Re-aggregate the families with another grouping
+    pub fn regroup(&mut self, grouping: FamilyGrouping) {
+        if grouping != self.grouping {
+            self.css_font_families = Self::aggregate_css_font_families(&self.fonts, grouping);
+            self.grouping = grouping;
+        }
+    }
+
+    /// Family name of a font under `grouping`
+    pub fn family_key(font: &FontInfo, grouping: FamilyGrouping) -> &str {
+        let metadata = &font.metadata;
+        let legacy = Self::legacy_family(font);
+        match grouping {
+            FamilyGrouping::Typographic => &font.css_font_family,
+            FamilyGrouping::Wws => metadata
+                .wws_family
+                .as_deref()
+                .or(metadata.typographic_family.as_deref())
+                .unwrap_or(legacy),
+            FamilyGrouping::Legacy => legacy,
+        }
+    }
+
+    /// Aggregate fonts by their family name under `grouping`, build each family's style
+    /// matrix and RIBBI members, and determine the default font
+    fn aggregate_css_font_families(fonts: &[FontInfo], grouping: FamilyGrouping) -> Vec<CssFontFamily> {
+        // Group fonts by family name
         let mut family_map: HashMap<String, Vec<&FontInfo>> = HashMap::new();
 
         for font in fonts {
             family_map
-                .entry(font.css_font_family.clone())
+                .entry(Self::family_key(font, grouping).to_string())
                 .or_default()
                 .push(font);
         }
@@ -39,11 +82,21 @@ impl FontState {
             .map(|(name, font_list)| {
                 let font_count = font_list.len();
                 let default_font_id = Self::find_default_font(&font_list);
+                let (category, category_confidence) = classify_family(&font_list);
+                let styles = Self::build_style_matrix(&font_list);
+                let ribbi = Self::find_ribbi_members(&name, &font_list);
 
                 CssFontFamily {
                     name,
                     font_count,
                     default_font_id,
+                    category,
+                    category_confidence,
+                    weights: Self::unique_sorted(styles.iter().map(|s| s.weight)),
+                    widths: Self::unique_sorted(styles.iter().map(|s| s.width)),
+                    slopes: Self::unique_sorted(styles.iter().map(|s| s.slope)),
+                    styles,
+                    ribbi,
                 }
             })
             .collect();
@@ -54,19 +107,111 @@ impl FontState {
         css_font_families
     }
 
-    /// Find the default font ID (weight closest to 400)
+    /// Find the default font ID: upright, normal width, weight closest to 400
     fn find_default_font(fonts: &[&FontInfo]) -> String {
         fonts
             .iter()
             .min_by_key(|font| {
-                // Calculate distance from 400
-                // Use absolute difference
-                (font.weight as i32 - 400).unsigned_abs()
+                (
+                    Self::is_slanted(font),
+                    Self::width_distance(font),
+                    // Calculate distance from 400
+                    (font.weight as i32 - 400).unsigned_abs(),
+                )
             })
             .map(|font| font.id.clone())
             .unwrap_or_default()
     }
 
+    /// Every face of the family ordered by width, weight and slope
+    fn build_style_matrix(fonts: &[&FontInfo]) -> Vec<FamilyStyle> {
+        let mut styles: Vec<FamilyStyle> = fonts
+            .iter()
+            .map(|font| FamilyStyle {
+                font_id: font.id.clone(),
+                style: font
+                    .metadata
+                    .typographic_subfamily
+                    .clone()
+                    .unwrap_or_else(|| font.style.clone()),
+                weight: font.weight,
+                width: font.style_attributes.width,
+                slope: font.style_attributes.slope,
+            })
+            .collect();
+        styles.sort_by(|a, b| (a.width, a.weight, a.slope, &a.style).cmp(&(b.width, b.weight, b.slope, &b.style)));
+        styles
+    }
+
+    /// Pick the RIBBI members the way legacy style linking does: faces whose fsSelection
+    /// BOLD/ITALIC/REGULAR bits (or, without OS/2, Name ID 2) declare the slot, preferring
+    /// normal width and the legacy family (Name ID 1) named like the group. Slots nothing
+    /// links fall back to weight: Regular/Italic are the faces below 600 closest to 400,
+    /// Bold/Bold Italic the faces from 600 closest to 700.
+    fn find_ribbi_members(family_name: &str, fonts: &[&FontInfo]) -> RibbiMembers {
+        let pick = |slanted: bool, bold: bool| {
+            let target: u16 = if bold { 700 } else { 400 };
+            let linked = fonts
+                .iter()
+                .filter(|font| Self::linked_slot(font) == Some((slanted, bold)))
+                .min_by_key(|font| {
+                    (
+                        Self::width_distance(font),
+                        !Self::legacy_family(font).eq_ignore_ascii_case(family_name),
+                        font.weight.abs_diff(target),
+                    )
+                });
+            let heuristic = || {
+                fonts
+                    .iter()
+                    .filter(|font| Self::is_slanted(font) == slanted && (font.weight >= 600) == bold)
+                    .min_by_key(|font| (Self::width_distance(font), font.weight.abs_diff(target)))
+            };
+            linked.or_else(heuristic).map(|font| font.id.clone())
+        };
+
+        RibbiMembers {
+            regular: pick(false, false),
+            italic: pick(true, false),
+            bold: pick(false, true),
+            bold_italic: pick(true, true),
+        }
+    }
+
+    /// RIBBI slot as (italic, bold) from fsSelection, or from Name ID 2 without an OS/2 table
+    fn linked_slot(font: &FontInfo) -> Option<(bool, bool)> {
+        font.style_attributes.fs_selection_slot().or_else(|| {
+            match font.style.to_ascii_lowercase().as_str() {
+                "regular" => Some((false, false)),
+                "italic" => Some((true, false)),
+                "bold" => Some((false, true)),
+                "bold italic" => Some((true, true)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Name ID 1, the family legacy style linking works within
+    fn legacy_family(font: &FontInfo) -> &str {
+        font.metadata.family_name.as_deref().unwrap_or(&font.family)
+    }
+
+    fn is_slanted(font: &FontInfo) -> bool {
+        font.style_attributes.slope != FontSlope::Upright
+    }
+
+    /// Distance from the normal width class; 0 means unknown and counts as normal
+    fn width_distance(font: &FontInfo) -> u16 {
+        match font.style_attributes.width {
+            0 => 0,
+            width => width.abs_diff(5),
+        }
+    }
+
+    fn unique_sorted<T: Ord>(values: impl Iterator<Item = T>) -> Vec<T> {
+        values.collect::<BTreeSet<T>>().into_iter().collect()
+    }
+
     /// Get font by ID
     pub fn get_font(&self, id: &str) -> Option<&FontInfo> {
         self.fonts.iter().find(|f| f.id == id)
@@ -77,11 +222,11 @@ impl FontState {
         self.css_font_families.iter().find(|f| f.name == name)
     }
 
-    /// Get all fonts belonging to a CSS font family
+    /// Get all fonts belonging to a family under the current grouping
     pub fn get_fonts_by_css_family(&self, family_name: &str) -> Vec<&FontInfo> {
         self.fonts
             .iter()
-            .filter(|f| f.css_font_family == family_name)
+            .filter(|f| Self::family_key(f, self.grouping) == family_name)
             .collect()
     }
 
@@ -101,16 +246,56 @@ impl Default for FontState {
         Self {
             fonts: Vec::new(),
             css_font_families: Vec::new(),
+            grouping: FamilyGrouping::default(),
         }
     }
 }
 
+/// FontCache - keeps the most recent scan so commands can look fonts up by ID
+#[derive(Debug, Default)]
+pub struct FontCache {
+    state: RwLock<Option<FontState>>,
+}
+
+impl FontCache {
+    /// Replace the cached scan result
+    pub fn set(&self, state: FontState) {
+        *self.state.write().unwrap() = Some(state);
+    }
+
+    /// Run `f` on the cached scan result
+    pub fn read<T>(&self, f: impl FnOnce(&FontState) -> T) -> Result<T, String> {
+        let guard = self.state.read().unwrap();
+        let state = guard.as_ref().ok_or("Fonts have not been scanned yet")?;
+        Ok(f(state))
+    }
+
+    /// Grouping of the cached scan, so a rescan keeps the mode the user picked
+    pub fn grouping(&self) -> FamilyGrouping {
+        self.read(|state| state.grouping).unwrap_or_default()
+    }
+
+    /// Run `f` on the cached scan result mutably
+    pub fn update<T>(&self, f: impl FnOnce(&mut FontState) -> T) -> Result<T, String> {
+        let mut guard = self.state.write().unwrap();
+        let state = guard.as_mut().ok_or("Fonts have not been scanned yet")?;
+        Ok(f(state))
+    }
+
+    /// Get a copy of a cached font by ID
+    pub fn get_font(&self, id: &str) -> Result<FontInfo, String> {
+        self.read(|state| state.get_font(id).cloned())?
+            .ok_or_else(|| format!("Font not found: {}", id))
+    }
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::font::models::{FontFormat, FontMetadata, FontStatus};
+    use crate::font::outline::{OutlineFormat, RenderingInfo};
 
-    fn create_test_font(id: &str, css_font_family: &str, weight: u16) -> FontInfo {
+    pub(crate) fn create_test_font(id: &str, css_font_family: &str, weight: u16) -> FontInfo {
         FontInfo {
             id: id.to_string(),
             family: css_font_family.to_string(),
@@ -118,6 +303,7 @@ mod tests {
             postscript_name: format!("{}-{}", css_font_family, weight),
             style: "Regular".to_string(),
             path: "/test/path".to_string(),
+            face_index: 0,
             file_size: 1000,
             format: FontFormat::TrueType,
             is_variable: false,
@@ -127,9 +313,33 @@ mod tests {
             metadata: FontMetadata::default(),
             status: FontStatus::Enabled,
             created_at: 0,
-            family_zh: None,
-            full_name_zh: None,
+            localized_names: Default::default(),
             css_font_family: css_font_family.to_string(),
+            os2_coverage: None,
+            name_repairs: Vec::new(),
+            health: Default::default(),
+            lint_warnings: Vec::new(),
+            rendering: RenderingInfo {
+                outline_format: OutlineFormat::TrueType,
+                has_fpgm: false,
+                has_prep: false,
+                has_cvt: false,
+                hinted: false,
+                autohinted: false,
+                has_gasp: false,
+                has_embedded_bitmaps: false,
+            },
+            bitmap_strikes: Vec::new(),
+            color: Default::default(),
+            monospace: Default::default(),
+            ligatures: Default::default(),
+            features: Vec::new(),
+            vertical: Default::default(),
+            math: Default::default(),
+            classification: Default::default(),
+            cjk_style: Default::default(),
+            style_attributes: Default::default(),
+            variation_axes: Vec::new(),
         }
     }
 
@@ -177,6 +387,83 @@ mod tests {
         assert!(family.default_font_id == "1" || family.default_font_id == "2");
     }
 
+    fn create_styled_font(id: &str, weight: u16, width: u16, slope: FontSlope, style: &str) -> FontInfo {
+        let mut font = create_test_font(id, "Styled", weight);
+        font.style = style.to_string();
+        font.style_attributes.weight = weight;
+        font.style_attributes.width = width;
+        font.style_attributes.slope = slope;
+        font
+    }
+
+    #[test]
+    fn test_default_font_prefers_upright_normal_width() {
+        let fonts = vec![
+            create_styled_font("1", 400, 5, FontSlope::Italic, "Italic"),
+            create_styled_font("2", 400, 3, FontSlope::Upright, "Condensed"),
+            create_styled_font("3", 300, 5, FontSlope::Upright, "Light"),
+        ];
+
+        let state = FontState::new(fonts);
+        let family = state.get_css_font_family("Styled").unwrap();
+        assert_eq!(family.default_font_id, "3");
+    }
+
+    #[test]
+    fn test_style_matrix_and_ribbi() {
+        let fonts = vec![
+            create_styled_font("regular", 400, 5, FontSlope::Upright, "Regular"),
+            create_styled_font("italic", 400, 5, FontSlope::Italic, "Italic"),
+            create_styled_font("bold", 700, 5, FontSlope::Upright, "Bold"),
+            create_styled_font("bold-italic", 700, 5, FontSlope::Italic, "Bold Italic"),
+            create_styled_font("light", 300, 5, FontSlope::Upright, "Regular"),
+            create_styled_font("black", 900, 5, FontSlope::Upright, "Regular"),
+            create_styled_font("condensed-bold", 700, 3, FontSlope::Upright, "Bold"),
+        ];
+
+        let state = FontState::new(fonts);
+        let family = state.get_css_font_family("Styled").unwrap();
+
+        assert_eq!(family.ribbi.regular.as_deref(), Some("regular"));
+        assert_eq!(family.ribbi.italic.as_deref(), Some("italic"));
+        assert_eq!(family.ribbi.bold.as_deref(), Some("bold"));
+        assert_eq!(family.ribbi.bold_italic.as_deref(), Some("bold-italic"));
+
+        assert_eq!(family.weights, vec![300, 400, 700, 900]);
+        assert_eq!(family.widths, vec![3, 5]);
+        assert_eq!(family.slopes, vec![FontSlope::Upright, FontSlope::Italic]);
+        // Matrix order: width, then weight, then slope
+        assert_eq!(family.styles[0].font_id, "condensed-bold");
+        assert_eq!(family.styles[1].font_id, "light");
+        assert_eq!(family.styles.last().unwrap().font_id, "black");
+    }
+
+    #[test]
+    fn test_ribbi_from_fs_selection() {
+        let linked = |id: &str, weight: u16, legacy_family: &str, fs_selection: u16| {
+            let mut font = create_styled_font(id, weight, 5, FontSlope::Upright, "Regular");
+            font.family = legacy_family.to_string();
+            font.style_attributes.fs_selection = Some(fs_selection);
+            font
+        };
+        let fonts = vec![
+            // A light family whose Medium is style-linked as its Bold
+            linked("light", 300, "Styled", 1 << 6),
+            linked("medium", 500, "Styled", 1 << 5),
+            // Regular of another legacy family, closer to 400
+            linked("book", 400, "Styled Book", 1 << 6),
+            // No OS/2 and no RIBBI subfamily name: only the weight heuristic places it
+            create_styled_font("thin-italic", 100, 5, FontSlope::Italic, "Thin Italic"),
+        ];
+
+        let state = FontState::new(fonts);
+        let family = state.get_css_font_family("Styled").unwrap();
+        assert_eq!(family.ribbi.regular.as_deref(), Some("light"));
+        assert_eq!(family.ribbi.bold.as_deref(), Some("medium"));
+        assert_eq!(family.ribbi.italic.as_deref(), Some("thin-italic"));
+        assert_eq!(family.ribbi.bold_italic, None);
+    }
+
     #[test]
     fn test_get_fonts_by_css_family() {
         let fonts = vec![
@@ -190,4 +477,55 @@ mod tests {
 
         assert_eq!(roboto_fonts.len(), 2);
     }
+
+    /// A face of "Minion Pro Caption": ID 1 carries the weight, ID 16 the optical size and
+    /// ID 21 groups only by weight, width and slope
+    fn create_named_font(id: &str, legacy: &str, wws: Option<&str>) -> FontInfo {
+        let mut font = create_test_font(id, "Minion Pro", 400);
+        font.family = legacy.to_string();
+        font.metadata.family_name = Some(legacy.to_string());
+        font.metadata.typographic_family = Some("Minion Pro".to_string());
+        font.metadata.wws_family = wws.map(str::to_string);
+        font
+    }
+
+    #[test]
+    fn test_family_grouping_modes() {
+        let fonts = vec![
+            create_named_font("regular", "Minion Pro", None),
+            create_named_font("semibold", "Minion Pro SmBd", None),
+            create_named_font("caption", "Minion Pro Caption", Some("Minion Pro Caption")),
+            create_named_font("caption-semibold", "Minion Pro SmBd Caption", Some("Minion Pro Caption")),
+        ];
+
+        let mut state = FontState::new(fonts);
+        assert_eq!(state.grouping, FamilyGrouping::Typographic);
+        assert_eq!(state.css_font_family_count(), 1);
+        assert_eq!(state.get_fonts_by_css_family("Minion Pro").len(), 4);
+
+        state.regroup(FamilyGrouping::Wws);
+        let names: Vec<&str> = state.css_font_families.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["Minion Pro", "Minion Pro Caption"]);
+        assert_eq!(state.get_fonts_by_css_family("Minion Pro Caption").len(), 2);
+
+        state.regroup(FamilyGrouping::Legacy);
+        assert_eq!(state.css_font_family_count(), 4);
+        assert_eq!(state.get_fonts_by_css_family("Minion Pro SmBd").len(), 1);
+        assert_eq!(state.get_fonts_by_css_family("Minion Pro").len(), 1);
+    }
+
+    #[test]
+    fn test_font_cache_lookup() {
+        let cache = FontCache::default();
+        assert!(cache.get_font("1").is_err());
+        assert_eq!(cache.grouping(), FamilyGrouping::Typographic);
+
+        cache.set(FontState::new(vec![create_test_font("1", "Roboto", 400)]));
+        assert_eq!(cache.get_font("1").unwrap().css_font_family, "Roboto");
+        assert!(cache.get_font("2").is_err());
+
+        cache.update(|state| state.regroup(FamilyGrouping::Legacy)).unwrap();
+        assert_eq!(cache.read(|state| state.grouping).unwrap(), FamilyGrouping::Legacy);
+        assert_eq!(cache.grouping(), FamilyGrouping::Legacy);
+    }
 }
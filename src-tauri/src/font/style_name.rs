@@ -0,0 +1,419 @@
+//! Weight, width and slope inferred from style names (English, CJK, Japanese W-numbers),
+//! reconciled with the OS/2 values
+use super::raw::{self, read_u16};
+use serde::{Deserialize, Serialize};
+use ttf_parser::Face;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FontSlope {
+    #[default]
+    Upright,
+    Italic,
+    Oblique,
+}
+
+/// Style name and OS/2 values side by side with the reconciled result
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StyleAttributes {
+    pub weight: u16,                  // 协调后的字重；可变字体的 FontInfo.weight 另取 fvar wght 默认值
+    pub width: u16,                   // 协调后的宽度等级 1-9 (usWidthClass 尺度，5 为 Normal)
+    pub slope: FontSlope,
+    pub name_weight: Option<u16>,     // 由样式名/本地化名称推断 (如 "SemiBold"、"W6"、"中黑")；Regular 等不算
+    pub name_width: Option<u16>,      // 如 "ExtraCondensed" → 2
+    pub name_slope: Option<FontSlope>,
+    pub os2_weight: Option<u16>,      // OS/2 usWeightClass
+    pub os2_width: Option<u16>,       // OS/2 usWidthClass
+    pub os2_slope: Option<FontSlope>, // fsSelection ITALIC/OBLIQUE 位，无 OS/2 表为 None
+    pub fs_selection: Option<u16>,    // OS/2 fsSelection 原值，BOLD/ITALIC/REGULAR 位用于 RIBBI 链接
+    pub conflicts: Vec<String>,       // 样式名与 OS/2 不一致之处
+}
+
+// OS/2 fsSelection bits
+const FS_ITALIC: u16 = 1 << 0;
+const FS_BOLD: u16 = 1 << 5;
+const FS_REGULAR: u16 = 1 << 6;
+const FS_OBLIQUE: u16 = 1 << 9;
+
+/// Names and usWeightClass may differ by this much before they count as conflicting
+const WEIGHT_TOLERANCE: u16 = 100;
+
+/// Weight keywords found in style names, longest first so "semibold" wins over "bold".
+/// "Regular", "Normal", "Book" and "Roman" are absent: they say nothing about the weight.
+const WEIGHT_KEYWORDS: &[(&str, u16)] = &[
+    ("extralight", 200),
+    ("ultralight", 200),
+    ("extrablack", 950),
+    ("ultrablack", 950),
+    ("extrabold", 800),
+    ("ultrabold", 800),
+    ("semilight", 350),
+    ("demilight", 350),
+    ("semibold", 600),
+    ("demibold", 600),
+    ("hairline", 100),
+    ("medium", 500),
+    ("black", 900),
+    ("heavy", 900),
+    ("light", 300),
+    ("thin", 100),
+    ("bold", 700),
+    ("demi", 600),
+];
+
+/// Chinese, Japanese and Korean weight notations, matched in any name, most specific first.
+/// The 400 entries only keep "細" from matching 細明 (Mingliu) and are not used as evidence.
+const CJK_WEIGHT_KEYWORDS: &[(&str, u16)] = &[
+    ("极细", 100),
+    ("極細", 100),
+    ("纤细", 100),
+    ("纖細", 100),
+    ("特细", 200),
+    ("特細", 200),
+    ("细黑", 300),
+    ("細黑", 300),
+    ("中黑", 500),
+    ("中黒", 500),
+    ("中宋", 500),
+    ("中等", 500),
+    ("ミディアム", 500),
+    ("半粗", 600),
+    ("粗黑", 800),
+    ("大黑", 800),
+    ("大黒", 800),
+    ("特黑", 900),
+    ("特粗", 800),
+    ("超粗", 900),
+    ("極太", 900),
+    ("特太", 800),
+    ("常规", 400),
+    ("常規", 400),
+    ("標準", 400),
+    ("标准", 400),
+    ("レギュラー", 400),
+    ("보통", 400),
+    ("가는", 300),
+    ("굵게", 700),
+    ("太字", 700),
+    ("細明", 400),
+    ("细明", 400),
+    ("粗", 700),
+    ("细", 300),
+    ("細", 300),
+];
+
+/// Width keywords as usWidthClass, longest first so "extracondensed" wins over "condensed"
+const WIDTH_KEYWORDS: &[(&str, u16)] = &[
+    ("ultracondensed", 1),
+    ("extracondensed", 2),
+    ("semicondensed", 4),
+    ("ultraexpanded", 9),
+    ("extraexpanded", 8),
+    ("semiexpanded", 6),
+    ("compressed", 2),
+    ("condensed", 3),
+    ("extended", 7),
+    ("expanded", 7),
+    ("narrow", 3),
+    ("wide", 7),
+];
+
+const CJK_WIDTH_KEYWORDS: &[(&str, u16)] = &[
+    ("长体", 3),
+    ("長体", 3),
+    ("長體", 3),
+    ("コンデンス", 3),
+    ("平体", 7),
+    ("平體", 7),
+];
+
+const SLOPE_KEYWORDS: &[(&str, FontSlope)] = &[
+    ("italic", FontSlope::Italic),
+    ("kursiv", FontSlope::Italic),
+    ("oblique", FontSlope::Oblique),
+    ("slanted", FontSlope::Oblique),
+    ("inclined", FontSlope::Oblique),
+];
+
+const CJK_SLOPE_KEYWORDS: &[(&str, FontSlope)] = &[
+    ("斜体", FontSlope::Italic),
+    ("斜體", FontSlope::Italic),
+    ("イタリック", FontSlope::Italic),
+    ("이탤릭", FontSlope::Italic),
+];
+
+/// Infer weight, width and slope from `style` (typographic subfamily or Name ID 2) and
+/// `names` (family, full and localized names), then reconcile with OS/2
+pub fn infer_style_attributes(face: &Face, style: &str, names: &[&str]) -> StyleAttributes {
+    let os2 = raw::table(face, b"OS/2");
+    let os2_weight = os2.and_then(|data| read_u16(data, 4));
+    let os2_width = os2.and_then(|data| read_u16(data, 6));
+    let fs_selection = os2.and_then(|data| read_u16(data, 62));
+    let os2_slope = fs_selection.map(|fs_selection| {
+        if fs_selection & FS_OBLIQUE != 0 {
+            FontSlope::Oblique
+        } else if fs_selection & FS_ITALIC != 0 {
+            FontSlope::Italic
+        } else {
+            FontSlope::Upright
+        }
+    });
+
+    let all_names: Vec<&str> = std::iter::once(style).chain(names.iter().copied()).collect();
+    let name_weight = weight_from_names(style, &all_names);
+    // Widths are often part of the family ("Arial Narrow", "Roboto Condensed")
+    let name_width = find_keyword(WIDTH_KEYWORDS, style)
+        .or_else(|| find_word_keyword(WIDTH_KEYWORDS, &all_names))
+        .or_else(|| find_cjk_keyword(CJK_WIDTH_KEYWORDS, &all_names));
+    let name_slope = find_keyword(SLOPE_KEYWORDS, style).or_else(|| find_cjk_keyword(CJK_SLOPE_KEYWORDS, &all_names));
+
+    StyleAttributes {
+        fs_selection,
+        ..reconcile(name_weight, name_width, name_slope, os2_weight, os2_width, os2_slope)
+    }
+}
+
+impl StyleAttributes {
+    /// RIBBI slot as (italic, bold) from the fsSelection ITALIC, BOLD and REGULAR bits
+    pub fn fs_selection_slot(&self) -> Option<(bool, bool)> {
+        let fs_selection = self.fs_selection?;
+        if fs_selection & FS_REGULAR != 0 {
+            return Some((false, false));
+        }
+        Some((fs_selection & FS_ITALIC != 0, fs_selection & FS_BOLD != 0))
+    }
+}
+
+fn reconcile(
+    name_weight: Option<u16>,
+    name_width: Option<u16>,
+    name_slope: Option<FontSlope>,
+    os2_weight: Option<u16>,
+    os2_width: Option<u16>,
+    os2_slope: Option<FontSlope>,
+) -> StyleAttributes {
+    let mut conflicts = Vec::new();
+    // Some legacy fonts use a 1-9 scale; 0 and values past 1000 are simply wrong
+    let valid_os2_weight = match os2_weight {
+        Some(weight @ 1..=9) => {
+            conflicts.push(format!("usWeightClass {} uses the legacy 1-9 scale, read as {}", weight, weight * 100));
+            Some(weight * 100)
+        }
+        Some(weight @ 10..=1000) => Some(weight),
+        Some(weight) => {
+            conflicts.push(format!("usWeightClass {} is outside 1-1000", weight));
+            None
+        }
+        None => None,
+    };
+    let valid_os2_width = os2_width.filter(|w| (1..=9).contains(w));
+
+    // usWeightClass is finer grained, so it wins unless the name clearly disagrees.
+    // Regular-style names carry no weight, so they never override it.
+    let weight = match (name_weight, valid_os2_weight) {
+        (Some(name), Some(os2)) if name.abs_diff(os2) > WEIGHT_TOLERANCE => {
+            conflicts.push(format!("Style name suggests weight {}, but usWeightClass is {}", name, os2));
+            name
+        }
+        (_, Some(os2)) => os2,
+        (Some(name), None) => name,
+        (None, None) => 400,
+    };
+
+    let width = match (name_width, valid_os2_width) {
+        (Some(name), Some(os2)) if name != os2 => {
+            conflicts.push(format!("Style name suggests width class {}, but usWidthClass is {}", name, os2));
+            name
+        }
+        (Some(width), _) | (None, Some(width)) => width,
+        (None, None) => 5,
+    };
+
+    // Fonts older than OS/2 v4 have no OBLIQUE bit and mark oblique styles as italic
+    if let (Some(name), Some(os2)) = (name_slope, os2_slope) {
+        if (name == FontSlope::Upright) != (os2 == FontSlope::Upright) {
+            conflicts.push(format!("Style name is {:?}, but fsSelection says {:?}", name, os2));
+        }
+    }
+    let slope = name_slope.or(os2_slope).unwrap_or_default();
+
+    StyleAttributes {
+        weight,
+        width,
+        slope,
+        name_weight,
+        name_width,
+        name_slope,
+        os2_weight,
+        os2_width,
+        os2_slope,
+        fs_selection: None,
+        conflicts,
+    }
+}
+
+/// An English weight in the style wins, then English words, W-numbers and CJK notations
+/// in the family and full names ("Arial Black" styled "Regular"). A regular weight or no
+/// keyword is no evidence at all.
+fn weight_from_names(style: &str, names: &[&str]) -> Option<u16> {
+    weight_from_style_name(style)
+        .or_else(|| find_word_keyword(WEIGHT_KEYWORDS, names))
+        .or_else(|| names.iter().find_map(|name| weight_from_w_number(name)))
+        .or_else(|| find_cjk_keyword(CJK_WEIGHT_KEYWORDS, names).filter(|&weight| weight != 400))
+}
+
+/// Weight implied by a style name such as "Semi Bold Italic"
+fn weight_from_style_name(style: &str) -> Option<u16> {
+    find_keyword(WEIGHT_KEYWORDS, style)
+}
+
+/// Japanese W-numbers ("W3", "ヒラギノ角ゴ ProN W6"): W3 is 300, W6 is 600, W0/W1 are the thinnest
+fn weight_from_w_number(name: &str) -> Option<u16> {
+    name.split(|c: char| !c.is_ascii_alphanumeric()).find_map(|word| {
+        let digits = word.strip_prefix(['W', 'w'])?;
+        if digits.is_empty() || digits.len() > 2 {
+            return None;
+        }
+        let number: u16 = digits.parse().ok()?;
+        Some((number * 100).clamp(100, 950))
+    })
+}
+
+/// Match keywords against the lowercase ASCII letters of `text` ("Semi Bold" → "semibold")
+fn find_keyword<T: Copy>(keywords: &[(&str, T)], text: &str) -> Option<T> {
+    let compact: String = text
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect();
+    keywords
+        .iter()
+        .find(|(keyword, _)| compact.contains(keyword))
+        .map(|&(_, value)| value)
+}
+
+/// Match keywords against whole words of `names`, or two adjacent words ("Semi Bold"),
+/// so "Worldwide" is not "wide"
+fn find_word_keyword<T: Copy>(keywords: &[(&str, T)], names: &[&str]) -> Option<T> {
+    let words: Vec<Vec<String>> = names
+        .iter()
+        .map(|name| {
+            name.to_lowercase()
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .collect();
+    keywords
+        .iter()
+        .find(|(keyword, _)| {
+            words.iter().any(|words| {
+                words.iter().any(|word| word == keyword)
+                    || words.windows(2).any(|pair| pair.concat() == *keyword)
+            })
+        })
+        .map(|&(_, value)| value)
+}
+
+fn find_cjk_keyword<T: Copy>(keywords: &[(&str, T)], names: &[&str]) -> Option<T> {
+    keywords
+        .iter()
+        .find(|(keyword, _)| names.iter().any(|name| name.contains(keyword)))
+        .map(|&(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weight_from_style_name() {
+        assert_eq!(weight_from_style_name("Bold"), Some(700));
+        assert_eq!(weight_from_style_name("Semi Bold Italic"), Some(600));
+        assert_eq!(weight_from_style_name("ExtraLight"), Some(200));
+        assert_eq!(weight_from_style_name("Ultra-Bold"), Some(800));
+        assert_eq!(weight_from_style_name("Italic"), None);
+    }
+
+    #[test]
+    fn test_keywords_longest_first() {
+        // A keyword must not be a substring of a keyword listed before it
+        fn check<T>(keywords: &[(&str, T)]) {
+            for (i, (keyword, _)) in keywords.iter().enumerate() {
+                for (earlier, _) in &keywords[..i] {
+                    assert!(!keyword.contains(earlier), "{} shadowed by {}", keyword, earlier);
+                }
+            }
+        }
+        check(WEIGHT_KEYWORDS);
+        check(CJK_WEIGHT_KEYWORDS);
+        check(WIDTH_KEYWORDS);
+        check(CJK_WIDTH_KEYWORDS);
+    }
+
+    #[test]
+    fn test_weight_from_names() {
+        assert_eq!(weight_from_names("W6", &["W6", "ヒラギノ角ゴ ProN W6"]), Some(600));
+        assert_eq!(weight_from_names("Regular", &["Regular", "方正兰亭中黑_GBK"]), Some(500));
+        assert_eq!(weight_from_names("Regular", &["Regular", "华文细黑"]), Some(300));
+        assert_eq!(weight_from_names("Regular", &["Regular", "粗体"]), Some(700));
+        assert_eq!(weight_from_names("Heavy", &["Heavy"]), Some(900));
+        assert_eq!(weight_from_names("Regular", &["Regular", "Arial"]), None);
+        assert_eq!(weight_from_names("Book", &["Book", "Futura Book"]), None);
+        assert_eq!(weight_from_names("Regular", &["Regular", "Arial Black"]), Some(900));
+        assert_eq!(weight_from_names("Regular", &["Regular", "Roboto Semi Bold"]), Some(600));
+        assert_eq!(weight_from_names("Regular", &["Regular", "Times New Roman"]), None);
+        assert_eq!(weight_from_names("Regular", &["Regular", "Lighthouse"]), None);
+        // "Bold" in the style is more specific than a family-level notation
+        assert_eq!(weight_from_names("Bold", &["Bold", "思源黑体 细"]), Some(700));
+        assert_eq!(weight_from_names("Regular", &["Regular", "新細明體"]), None);
+        assert_eq!(weight_from_w_number("Windows"), None);
+    }
+
+    #[test]
+    fn test_width_and_slope_keywords() {
+        assert_eq!(find_keyword(WIDTH_KEYWORDS, "ExtraCondensed Bold"), Some(2));
+        assert_eq!(find_keyword(WIDTH_KEYWORDS, "SemiBold SemiCondensed"), Some(4));
+        assert_eq!(find_keyword(WIDTH_KEYWORDS, "Bold"), None);
+        assert_eq!(find_word_keyword(WIDTH_KEYWORDS, &["Arial Narrow Bold"]), Some(3));
+        assert_eq!(find_word_keyword(WIDTH_KEYWORDS, &["Worldwide Sans"]), None);
+        assert_eq!(find_keyword(SLOPE_KEYWORDS, "Bold Oblique"), Some(FontSlope::Oblique));
+        assert_eq!(find_cjk_keyword(CJK_SLOPE_KEYWORDS, &["粗斜体"]), Some(FontSlope::Italic));
+    }
+
+    #[test]
+    fn test_reconcile() {
+        // Legacy CJK bold with usWeightClass 400
+        let attributes = reconcile(Some(700), None, None, Some(400), Some(5), Some(FontSlope::Upright));
+        assert_eq!(attributes.weight, 700);
+        assert_eq!(attributes.width, 5);
+        assert_eq!(attributes.conflicts.len(), 1);
+
+        // Small differences keep the finer usWeightClass
+        let book = reconcile(Some(400), None, None, Some(350), None, None);
+        assert_eq!((book.weight, book.conflicts.len()), (350, 0));
+
+        let italic = reconcile(None, Some(3), Some(FontSlope::Italic), Some(300), Some(5), Some(FontSlope::Upright));
+        assert_eq!((italic.width, italic.slope), (3, FontSlope::Italic));
+        assert_eq!(italic.conflicts.len(), 2);
+
+        // Legacy 1-9 scale and out-of-range usWeightClass
+        let legacy = reconcile(None, None, None, Some(7), None, None);
+        assert_eq!((legacy.weight, legacy.conflicts.len()), (700, 1));
+        let zero = reconcile(None, None, None, Some(0), None, None);
+        assert_eq!((zero.weight, zero.conflicts.len()), (400, 1));
+        let named = reconcile(Some(700), None, None, Some(1200), None, None);
+        assert_eq!((named.weight, named.conflicts.len()), (700, 1));
+
+        // "Arial Black" styled Regular keeps its usWeightClass
+        let name_weight = weight_from_names("Regular", &["Regular", "Arial Black"]);
+        let black = reconcile(name_weight, None, None, Some(900), None, None);
+        assert_eq!((black.weight, black.conflicts.len()), (900, 0));
+        let name_weight = weight_from_names("Regular", &["Regular", "Arial"]);
+        let regular = reconcile(name_weight, None, None, Some(900), None, None);
+        assert_eq!((regular.weight, regular.conflicts.len()), (900, 0));
+
+        let oblique = reconcile(None, None, Some(FontSlope::Oblique), None, None, Some(FontSlope::Italic));
+        assert_eq!((oblique.slope, oblique.conflicts.len()), (FontSlope::Oblique, 0));
+    }
+}
@@ -0,0 +1,610 @@
+//! Font audit of a local stylesheet: every font-family stack with the weight/style contexts
+//! it is used in, the installed face each context resolves to, missing entries, and sample
+//! characters that fall through to later fallbacks
+use super::matching::{
+    is_generic_family, match_font, parse_font_family_list, parse_font_stretch, parse_font_style, parse_font_weight,
+    FontMatch, FontQuery,
+};
+use super::raw;
+use super::state::FontState;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// font-weight / font-style / font-stretch as written in the stylesheet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FontContext {
+    pub weight: String,
+    pub style: String,
+    pub stretch: String,
+}
+
+impl Default for FontContext {
+    fn default() -> Self {
+        Self {
+            weight: "normal".to_string(),
+            style: "normal".to_string(),
+            stretch: "normal".to_string(),
+        }
+    }
+}
+
+/// A font-family stack and where it is declared
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontStack {
+    pub families: Vec<String>,
+    pub selectors: Vec<String>,
+    pub contexts: Vec<FontContext>, // 声明该字体栈的规则中的字重/样式；根选择器另含其他规则单独设置的字重/样式
+}
+
+/// Font stacks and @font-face families of a stylesheet
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedStylesheet {
+    pub stacks: Vec<FontStack>,
+    pub web_fonts: Vec<String>,
+}
+
+/// Sample character the primary face lacks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharFallback {
+    pub character: char,
+    pub family: Option<String>,  // 覆盖该字符的后续栈条目；None 表示栈内已安装字体都没有，交给系统回退
+    pub font_id: Option<String>,
+}
+
+/// How one weight/style context of a stack resolves
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextAudit {
+    pub context: FontContext,
+    pub matched: Option<FontMatch>,    // 第一个本机已安装条目命中的字体
+    pub fallbacks: Vec<CharFallback>, // 主字体缺字的示例字符
+    pub error: Option<String>,        // 无法解析的字重/样式/宽度值
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontStackAudit {
+    pub families: Vec<String>,
+    pub selectors: Vec<String>,
+    pub missing: Vec<String>, // 本机未安装的条目 (不含通用家族和 @font-face 网络字体)
+    pub contexts: Vec<ContextAudit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StylesheetAudit {
+    pub path: String,
+    pub stacks: Vec<FontStackAudit>,
+    pub web_fonts: Vec<String>, // @font-face 声明的家族，由浏览器下载，不参与本机匹配
+}
+
+/// At-rules whose blocks contain ordinary style rules
+const GROUPING_AT_RULES: &[&str] = &["media", "supports", "layer", "container", "scope", "document", "-moz-document"];
+
+/// `font` shorthand values naming system fonts instead of a family list
+const SYSTEM_FONTS: &[&str] = &["caption", "icon", "menu", "message-box", "small-caption", "status-bar"];
+
+/// Selectors whose font-family every element inherits
+const ROOT_SELECTORS: &[&str] = &["html", "body", ":root", "*"];
+
+/// Read a CSS file, or the `<style>` blocks of an HTML file, and audit its font stacks
+/// against the scanned fonts with the characters of `sample`
+pub fn audit_stylesheet(state: &FontState, path: &str, sample: &str) -> Result<StylesheetAudit, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let is_html = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "html" | "htm" | "xhtml"));
+    let css = if is_html { extract_style_blocks(&text) } else { text };
+    let sheet = parse_stylesheet(&css);
+
+    let mut chars: Vec<char> = Vec::new();
+    for c in sample.chars().filter(|c| !c.is_whitespace()) {
+        if !chars.contains(&c) {
+            chars.push(c);
+        }
+    }
+
+    let mut coverage = HashMap::new();
+    let stacks = sheet
+        .stacks
+        .iter()
+        .map(|stack| audit_stack(state, stack, &sheet.web_fonts, &chars, &mut coverage))
+        .collect();
+
+    Ok(StylesheetAudit {
+        path: path.to_string(),
+        stacks,
+        web_fonts: sheet.web_fonts,
+    })
+}
+
+fn audit_stack(
+    state: &FontState,
+    stack: &FontStack,
+    web_fonts: &[String],
+    chars: &[char],
+    coverage: &mut HashMap<String, HashSet<char>>,
+) -> FontStackAudit {
+    let local: Vec<&String> = stack
+        .families
+        .iter()
+        .filter(|family| !is_generic_family(family) && !web_fonts.iter().any(|web| web.eq_ignore_ascii_case(family)))
+        .collect();
+    let missing = local
+        .iter()
+        .filter(|family| {
+            let query = FontQuery::parse(family, "normal", "normal", "normal");
+            query.is_ok_and(|query| match_font(state, &query).is_none())
+        })
+        .map(|family| family.to_string())
+        .collect();
+
+    let contexts = stack
+        .contexts
+        .iter()
+        .map(|context| audit_context(state, &local, context, chars, coverage))
+        .collect();
+
+    FontStackAudit {
+        families: stack.families.clone(),
+        selectors: stack.selectors.clone(),
+        missing,
+        contexts,
+    }
+}
+
+/// Match every installed entry of the stack; the first match is the face used, later ones
+/// cover characters it lacks. Stacks without any installed entry report no fallbacks since
+/// everything goes to the generic/system fallback.
+fn audit_context(
+    state: &FontState,
+    families: &[&String],
+    context: &FontContext,
+    chars: &[char],
+    coverage: &mut HashMap<String, HashSet<char>>,
+) -> ContextAudit {
+    let parsed = parse_font_weight(&context.weight).and_then(|weight| {
+        Ok((weight, parse_font_style(&context.style)?, parse_font_stretch(&context.stretch)?))
+    });
+    let (weight, style, stretch) = match parsed {
+        Ok(values) => values,
+        Err(error) => {
+            return ContextAudit {
+                context: context.clone(),
+                matched: None,
+                fallbacks: Vec::new(),
+                error: Some(error),
+            }
+        }
+    };
+
+    let matches: Vec<FontMatch> = families
+        .iter()
+        .filter_map(|family| {
+            let query = FontQuery {
+                families: vec![family.to_string()],
+                weight,
+                style,
+                stretch,
+            };
+            match_font(state, &query)
+        })
+        .collect();
+
+    let mut covers = |font_match: &FontMatch, c: char| {
+        coverage
+            .entry(font_match.font_id.clone())
+            .or_insert_with(|| covered_chars(state, &font_match.font_id, chars))
+            .contains(&c)
+    };
+    let fallbacks = if matches.is_empty() {
+        Vec::new()
+    } else {
+        chars
+            .iter()
+            .filter_map(|&c| match matches.iter().position(|m| covers(m, c)) {
+                Some(0) => None,
+                Some(index) => Some(CharFallback {
+                    character: c,
+                    family: Some(matches[index].family.clone()),
+                    font_id: Some(matches[index].font_id.clone()),
+                }),
+                None => Some(CharFallback {
+                    character: c,
+                    family: None,
+                    font_id: None,
+                }),
+            })
+            .collect()
+    };
+
+    ContextAudit {
+        context: context.clone(),
+        matched: matches.into_iter().next(),
+        fallbacks,
+        error: None,
+    }
+}
+
+/// Sample characters mapped by the face; unreadable files cover nothing
+fn covered_chars(state: &FontState, font_id: &str, chars: &[char]) -> HashSet<char> {
+    state
+        .get_font(font_id)
+        .and_then(|font| {
+            raw::with_face(&font.path, font.face_index, |face| {
+                chars.iter().copied().filter(|&c| face.glyph_index(c).is_some()).collect()
+            })
+            .ok()
+        })
+        .unwrap_or_default()
+}
+
+/// Concatenate the contents of every `<style>` element
+fn extract_style_blocks(html: &str) -> String {
+    // ASCII lowercasing keeps byte offsets valid for `html`
+    let lowered = html.to_ascii_lowercase();
+    let mut css = String::new();
+    let mut pos = 0;
+    while let Some(start) = lowered[pos..].find("<style") {
+        let tag_start = pos + start;
+        let Some(tag_end) = lowered[tag_start..].find('>') else {
+            break;
+        };
+        let content_start = tag_start + tag_end + 1;
+        let content_end = lowered[content_start..]
+            .find("</style")
+            .map_or(html.len(), |end| content_start + end);
+        css.push_str(&html[content_start..content_end]);
+        css.push('\n');
+        pos = content_end;
+    }
+    css
+}
+
+/// Collect the font stacks of a stylesheet
+pub fn parse_stylesheet(css: &str) -> ParsedStylesheet {
+    let mut sheet = ParsedStylesheet::default();
+    let mut inherited: Vec<FontContext> = Vec::new();
+    parse_rules(&strip_comments(css), &mut sheet, &mut inherited);
+
+    for stack in &mut sheet.stacks {
+        if stack.selectors.iter().any(|selector| ROOT_SELECTORS.contains(&selector.as_str())) {
+            for context in &inherited {
+                push_unique(&mut stack.contexts, context.clone());
+            }
+        }
+    }
+    sheet
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = rest[start + 2..].find("*/").map_or("", |end| &rest[start + 2 + end + 2..]);
+    }
+    out.push_str(rest);
+    out
+}
+
+fn parse_rules(css: &str, sheet: &mut ParsedStylesheet, inherited: &mut Vec<FontContext>) {
+    let mut rest = css;
+    while let Some((index, delimiter)) = find_top_level(rest, &['{', ';']) {
+        if delimiter == ';' {
+            // Statement at-rules such as @import and @charset
+            rest = &rest[index + 1..];
+            continue;
+        }
+
+        let prelude = rest[..index].trim().trim_start_matches('}').trim();
+        let end = block_end(rest, index + 1);
+        let body = &rest[index + 1..end];
+        rest = &rest[(end + 1).min(rest.len())..];
+
+        if let Some(at_rule) = prelude.strip_prefix('@') {
+            let name = at_rule
+                .split(|c: char| c.is_whitespace() || c == '(')
+                .next()
+                .unwrap_or_default()
+                .to_lowercase();
+            if GROUPING_AT_RULES.contains(&name.as_str()) {
+                parse_rules(body, sheet, inherited);
+            } else if name == "font-face" {
+                for (property, value) in parse_declarations(body) {
+                    if property == "font-family" {
+                        for family in parse_font_family_list(&value) {
+                            if !sheet.web_fonts.iter().any(|web| web.eq_ignore_ascii_case(&family)) {
+                                sheet.web_fonts.push(family);
+                            }
+                        }
+                    }
+                }
+            }
+            continue;
+        }
+
+        add_style_rule(prelude, body, sheet, inherited);
+    }
+}
+
+/// Font properties set by one style rule; the `font` shorthand resets the others
+#[derive(Debug, Default)]
+struct RuleFont {
+    family: Option<String>,
+    weight: Option<String>,
+    style: Option<String>,
+    stretch: Option<String>,
+}
+
+fn add_style_rule(prelude: &str, body: &str, sheet: &mut ParsedStylesheet, inherited: &mut Vec<FontContext>) {
+    let mut font = RuleFont::default();
+    for (property, value) in parse_declarations(body) {
+        let value = normalize_value(&value);
+        if is_unresolved(&value) {
+            continue;
+        }
+        match property.as_str() {
+            "font-family" => font.family = Some(value),
+            "font-weight" => font.weight = Some(value),
+            "font-style" => font.style = Some(value),
+            "font-stretch" | "font-width" => font.stretch = Some(value),
+            "font" => {
+                if let Some(shorthand) = parse_font_shorthand(&value) {
+                    font = shorthand;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let context = FontContext {
+        weight: font.weight.clone().unwrap_or_else(|| "normal".to_string()),
+        style: font.style.clone().unwrap_or_else(|| "normal".to_string()),
+        stretch: font.stretch.clone().unwrap_or_else(|| "normal".to_string()),
+    };
+    let Some(family) = font.family else {
+        if font.weight.is_some() || font.style.is_some() || font.stretch.is_some() {
+            push_unique(inherited, context);
+        }
+        return;
+    };
+    let families = parse_font_family_list(&family);
+    if families.is_empty() {
+        return;
+    }
+
+    let index = match sheet.stacks.iter().position(|stack| same_families(&stack.families, &families)) {
+        Some(index) => index,
+        None => {
+            sheet.stacks.push(FontStack {
+                families,
+                selectors: Vec::new(),
+                contexts: Vec::new(),
+            });
+            sheet.stacks.len() - 1
+        }
+    };
+    let stack = &mut sheet.stacks[index];
+    for selector in split_top_level(prelude, ',') {
+        let selector = selector.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !selector.is_empty() {
+            push_unique(&mut stack.selectors, selector);
+        }
+    }
+    push_unique(&mut stack.contexts, context);
+}
+
+/// Parse the `font` shorthand: [style || variant || weight || stretch] size[/line-height] family
+fn parse_font_shorthand(value: &str) -> Option<RuleFont> {
+    if SYSTEM_FONTS.contains(&value) {
+        return None;
+    }
+    let tokens: Vec<&str> = split_top_level(value, ' ').into_iter().filter(|t| !t.is_empty()).collect();
+    let mut font = RuleFont::default();
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        match token {
+            "normal" | "small-caps" => {}
+            "italic" => font.style = Some(token.to_string()),
+            "oblique" => {
+                let angle = tokens.get(i + 1).filter(|next| next.ends_with("deg"));
+                font.style = Some(match angle {
+                    Some(angle) => {
+                        i += 1;
+                        format!("oblique {}", angle)
+                    }
+                    None => token.to_string(),
+                });
+            }
+            "bold" | "bolder" | "lighter" => font.weight = Some(token.to_string()),
+            _ if token.parse::<f32>().is_ok() => font.weight = Some(token.to_string()),
+            _ if !token.ends_with('%') && parse_font_stretch(token).is_ok() => font.stretch = Some(token.to_string()),
+            _ => break,
+        }
+        i += 1;
+    }
+
+    // tokens[i] is the font size, optionally followed by "/ line-height"
+    let mut family_start = i + 1;
+    if tokens.get(i).is_some_and(|size| !size.contains('/')) {
+        match tokens.get(family_start) {
+            Some(&"/") => family_start += 2,
+            Some(next) if next.starts_with('/') => family_start += 1,
+            _ => {}
+        }
+    }
+    let family = tokens.get(family_start..)?.join(" ");
+    if family.is_empty() {
+        return None;
+    }
+    font.family = Some(family);
+    Some(font)
+}
+
+/// `property: value` pairs of a declaration block, properties lowercased and `!important` dropped
+fn parse_declarations(body: &str) -> Vec<(String, String)> {
+    split_top_level(body, ';')
+        .into_iter()
+        .filter_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            let value = value.trim();
+            let value = value.strip_suffix("!important").unwrap_or(value).trim();
+            Some((property.trim().to_lowercase(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Lowercase keywords and collapse whitespace, keeping quoted family names as written
+fn normalize_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut quote: Option<char> = None;
+    for c in value.split_whitespace().collect::<Vec<_>>().join(" ").chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            _ => {}
+        }
+        if quote.is_none() { out.extend(c.to_lowercase()) } else { out.push(c) }
+    }
+    out
+}
+
+/// Values the audit cannot resolve without the document: custom properties and CSS-wide keywords
+fn is_unresolved(value: &str) -> bool {
+    value.contains("var(") || matches!(value, "inherit" | "initial" | "unset" | "revert" | "revert-layer")
+}
+
+/// Font family lists are equal when every entry matches case-insensitively
+fn same_families(a: &[String], b: &[String]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.eq_ignore_ascii_case(b))
+}
+
+fn push_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+/// Split at `separator` outside quotes, parentheses and brackets
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut rest = text;
+    while let Some((index, _)) = find_top_level(rest, &[separator]) {
+        parts.push(&text[start..start + index]);
+        start += index + separator.len_utf8();
+        rest = &text[start..];
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Byte index of the first delimiter outside quotes and parentheses
+fn find_top_level(text: &str, delimiters: &[char]) -> Option<(usize, char)> {
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    for (index, c) in text.char_indices() {
+        match (c, quote) {
+            (c, Some(q)) if c == q => quote = None,
+            (_, Some(_)) => {}
+            ('"' | '\'', None) => quote = Some(c),
+            ('(' | '[', None) => depth += 1,
+            (')' | ']', None) => depth = depth.saturating_sub(1),
+            (c, None) if depth == 0 && delimiters.contains(&c) => return Some((index, c)),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Byte index of the `}` closing a block whose content starts at `start`
+fn block_end(text: &str, start: usize) -> usize {
+    let mut depth = 1usize;
+    let mut quote: Option<char> = None;
+    for (index, c) in text[start..].char_indices() {
+        match (c, quote) {
+            (c, Some(q)) if c == q => quote = None,
+            (_, Some(_)) => {}
+            ('"' | '\'', None) => quote = Some(c),
+            ('{', None) => depth += 1,
+            ('}', None) => {
+                depth -= 1;
+                if depth == 0 {
+                    return start + index;
+                }
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(weight: &str, style: &str, stretch: &str) -> FontContext {
+        FontContext {
+            weight: weight.to_string(),
+            style: style.to_string(),
+            stretch: stretch.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_stylesheet() {
+        let css = r#"
+            @import url("reset.css");
+            @font-face { font-family: "Brand Sans"; src: url(brand.woff2); }
+            /* body { font-family: Ignored; } */
+            body, html { font-family: "Brand Sans", Arial, sans-serif; }
+            h1 { font-weight: 700; }
+            em { font-style: italic !important; }
+            @media (min-width: 600px) {
+                .title { font: italic 600 condensed 2rem/1.2 'Noto Serif CJK SC', serif; }
+            }
+            code { font-family: var(--mono); }
+            .lead { FONT-FAMILY: "brand sans", arial, SANS-SERIF; font-weight: 300; }
+        "#;
+        let sheet = parse_stylesheet(css);
+
+        assert_eq!(sheet.web_fonts, vec!["Brand Sans"]);
+        assert_eq!(sheet.stacks.len(), 2);
+
+        let body = &sheet.stacks[0];
+        assert_eq!(body.families, vec!["Brand Sans", "arial", "sans-serif"]);
+        assert_eq!(body.selectors, vec!["body", "html", ".lead"]);
+        assert_eq!(
+            body.contexts,
+            vec![
+                FontContext::default(),
+                context("300", "normal", "normal"),
+                context("700", "normal", "normal"),
+                context("normal", "italic", "normal"),
+            ]
+        );
+
+        let title = &sheet.stacks[1];
+        assert_eq!(title.families, vec!["Noto Serif CJK SC", "serif"]);
+        assert_eq!(title.contexts, vec![context("600", "italic", "condensed")]);
+    }
+
+    #[test]
+    fn test_font_shorthand() {
+        let font = parse_font_shorthand("oblique 10deg bold 12px / 1.5 \"A, B\", c").unwrap();
+        assert_eq!(font.style.as_deref(), Some("oblique 10deg"));
+        assert_eq!(font.weight.as_deref(), Some("bold"));
+        assert_eq!(font.family.as_deref(), Some("\"A, B\", c"));
+
+        assert!(parse_font_shorthand("menu").is_none());
+        assert!(parse_font_shorthand("12px").is_none());
+    }
+
+    #[test]
+    fn test_extract_style_blocks() {
+        let html = "<html><STYLE type=\"text/css\">p { font-family: A }</STYLE><p style=\"x\">é</p><style>q{}</style>";
+        assert_eq!(extract_style_blocks(html), "p { font-family: A }\nq{}\n");
+    }
+}
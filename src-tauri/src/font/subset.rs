@@ -0,0 +1,1091 @@
+//! Font subsetting for web delivery: keep the glyphs a text corpus or set of Unicode ranges
+//! needs, plus everything GSUB can substitute them with.
+//!
+//! Glyph IDs are retained and dropped glyphs are emptied instead of renumbered, so GSUB, GPOS,
+//! GDEF, hmtx and the other glyph-indexed tables stay valid without being rewritten.
+use super::coverage::unicode_codepoints;
+use super::models::FontInfo;
+use super::outline::OutlineFormat;
+use super::raw::{self, read_i16, read_u16, read_u32};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use ttf_parser::gsub::{SingleSubstitution, SubstitutionSubtable};
+use ttf_parser::colr::{ClipBox, CompositeMode, Paint, Painter};
+use ttf_parser::{Face, GlyphId, Permissions, RgbaColor, Transform};
+
+/// What to keep in a subset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubsetOptions {
+    pub text: String,             // 需要保留的文本语料
+    pub unicode_ranges: String,   // 额外保留的 Unicode 范围，如 "U+0000-00FF, U+4E00-9FFF"
+    pub drop_hinting: bool,       // 去掉 TrueType 指令及 fpgm/prep/cvt 等表
+    pub drop_unused_tables: bool, // 只保留网页渲染需要的表，并去掉 post 中的字形名
+}
+
+/// Summary of a subset run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubsetReport {
+    pub output_path: String,
+    pub original_size: u64,
+    pub subset_size: u64,
+    pub codepoint_count: usize, // 新 cmap 中的字符数
+    pub glyph_count: usize,     // 保留的字形数，含布局闭包和复合字形部件
+    pub missing: Vec<String>,   // 文本中字体不支持的字符
+    pub dropped_tables: Vec<String>,
+}
+
+/// A subset font file and its report (output_path is left empty)
+#[derive(Debug, Clone)]
+pub struct SubsetFont {
+    pub data: Vec<u8>,
+    pub report: SubsetReport,
+}
+
+/// TrueType hinting programs and the tables that only make sense with them
+const HINTING_TABLES: &[&[u8; 4]] = &[b"fpgm", b"prep", b"cvt ", b"cvar", b"hdmx", b"LTSH", b"VDMX"];
+
+/// Tables browsers use; everything else goes with `drop_unused_tables`. Bitmap and SVG glyph
+/// tables are copied whole: emoji and bitmap fonts draw nothing without them.
+const WEB_TABLES: &[&[u8; 4]] = &[
+    b"cmap", b"head", b"hhea", b"hmtx", b"maxp", b"name", b"OS/2", b"post", b"glyf", b"loca", b"CFF ", b"GDEF",
+    b"GSUB", b"GPOS", b"BASE", b"MATH", b"kern", b"vhea", b"vmtx", b"VORG", b"fvar", b"avar", b"gvar", b"HVAR",
+    b"VVAR", b"MVAR", b"STAT", b"COLR", b"CPAL", b"gasp", b"fpgm", b"prep", b"cvt ", b"cvar", b"CBDT", b"CBLC",
+    b"sbix", b"SVG ", b"EBDT", b"EBLC",
+];
+
+/// Type 2 charstring that draws nothing, used for dropped CFF glyphs
+const EMPTY_CHARSTRING: &[u8] = &[0x0E];
+
+/// Subset the font file at `path` and write the result to `output_path`, which must not be
+/// the font file itself
+pub fn subset_font_file(
+    path: &str,
+    face_index: u32,
+    options: &SubsetOptions,
+    output_path: &str,
+) -> Result<SubsetReport, String> {
+    let same_file = fs::canonicalize(output_path)
+        .is_ok_and(|output| fs::canonicalize(path).is_ok_and(|source| source == output));
+    if same_file {
+        return Err("The output path is the font file itself; choose another path".to_string());
+    }
+    let font_data = fs::read(path).map_err(|e| e.to_string())?;
+    let subset = subset_face(&font_data, face_index, options)?;
+    fs::write(output_path, &subset.data).map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    Ok(SubsetReport {
+        output_path: output_path.to_string(),
+        ..subset.report
+    })
+}
+
+/// Subset one face of a font file (collections yield a standalone font)
+pub fn subset_face(data: &[u8], face_index: u32, options: &SubsetOptions) -> Result<SubsetFont, String> {
+    let face = Face::parse(data, face_index).map_err(|e| e.to_string())?;
+    check_embedding(&face)?;
+
+    let tables = face.tables();
+    if raw::table(&face, b"CFF2").is_some() {
+        return Err("CFF2 outlines are not supported for subsetting".to_string());
+    }
+    let is_cff = raw::table(&face, b"CFF ").is_some();
+    if !is_cff && tables.glyf.is_none() {
+        return Err("Font has no glyf or CFF outlines to subset".to_string());
+    }
+
+    // Characters to keep: the text corpus plus the requested ranges
+    let text: BTreeSet<char> = options.text.chars().filter(|c| !c.is_control()).collect();
+    let ranges = parse_unicode_ranges(&options.unicode_ranges)?;
+    if text.is_empty() && ranges.is_empty() {
+        return Err("Nothing to keep: give a text corpus or Unicode ranges".to_string());
+    }
+    let requested = |cp: u32| {
+        char::from_u32(cp).is_some_and(|c| text.contains(&c)) || ranges.iter().any(|&(start, end)| (start..=end).contains(&cp))
+    };
+    let mapping: BTreeMap<u32, u16> = unicode_codepoints(&face)
+        .into_iter()
+        .filter(|&cp| requested(cp))
+        .filter_map(|cp| Some((cp, face.glyph_index(char::from_u32(cp)?)?.0)))
+        .collect();
+    let missing: Vec<String> = text
+        .iter()
+        .filter(|c| !mapping.contains_key(&(**c as u32)))
+        .map(|c| c.to_string())
+        .collect();
+    if mapping.is_empty() {
+        return Err("None of the requested characters are in the font".to_string());
+    }
+
+    let num_glyphs = face.number_of_glyphs();
+    let glyf = if is_cff { None } else { GlyfTable::parse(&face, num_glyphs) };
+    let keep = glyph_closure(&face, glyf.as_ref(), mapping.values().copied());
+
+    let mut output: Vec<([u8; 4], Vec<u8>)> = Vec::new();
+    let mut dropped_tables: Vec<String> = Vec::new();
+    for record in face.raw_face().table_records {
+        let tag = record.tag.to_bytes();
+        let Some(table) = face.raw_face().table(record.tag) else {
+            continue;
+        };
+        let is_hinting = HINTING_TABLES.contains(&&tag);
+        // DSIG no longer matches the modified font
+        if &tag == b"DSIG"
+            || (options.drop_hinting && is_hinting)
+            || (options.drop_unused_tables && !WEB_TABLES.contains(&&tag))
+        {
+            dropped_tables.push(record.tag.to_string());
+            continue;
+        }
+
+        let subset_table = match &tag {
+            b"glyf" | b"loca" => continue,
+            b"CFF " => subset_cff(table, &keep)?,
+            b"cmap" => build_cmap(&mapping),
+            b"gvar" => subset_gvar(table, &keep).ok_or("Malformed gvar table")?,
+            b"maxp" => subset_maxp(table, options.drop_hinting),
+            b"OS/2" => subset_os2(table, &mapping),
+            b"post" if options.drop_unused_tables && table.len() >= 32 => {
+                // Version 3.0: no glyph names
+                let mut post = table[..32].to_vec();
+                post[..4].copy_from_slice(&0x0003_0000u32.to_be_bytes());
+                post
+            }
+            _ => table.to_vec(),
+        };
+        output.push((tag, subset_table));
+    }
+
+    if let Some(glyf) = &glyf {
+        let (glyf_data, loca, long_offsets) = glyf.subset(&keep, options.drop_hinting);
+        output.push((*b"glyf", glyf_data));
+        output.push((*b"loca", loca));
+        if let Some((_, head)) = output.iter_mut().find(|(tag, _)| tag == b"head") {
+            if head.len() >= 54 {
+                head[50..52].copy_from_slice(&(long_offsets as i16).to_be_bytes());
+            }
+        }
+    }
+
+    let sfnt_version = if is_cff { 0x4F54_544F } else { 0x0001_0000 };
+    let font = build_sfnt(sfnt_version, output);
+    Ok(SubsetFont {
+        report: SubsetReport {
+            output_path: String::new(),
+            original_size: data.len() as u64,
+            subset_size: font.len() as u64,
+            codepoint_count: mapping.len(),
+            glyph_count: keep.len(),
+            missing,
+            dropped_tables,
+        },
+        data: font,
+    })
+}
+
+/// Where a subset of `font` goes when no output path is given: the user's Downloads folder,
+/// or the temp directory
+pub fn default_subset_path(font: &FontInfo) -> String {
+    let dir = std::env::var_os("USERPROFILE")
+        .map(|home| PathBuf::from(home).join("Downloads"))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(std::env::temp_dir);
+    let stem = if font.postscript_name.is_empty() {
+        Path::new(&font.path).file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+    } else {
+        font.postscript_name.clone()
+    };
+    let is_cff = font.rendering.outline_format == OutlineFormat::Cff;
+    let file_name = format!("{}-subset.{}", stem, if is_cff { "otf" } else { "ttf" });
+    dir.join(file_name).to_string_lossy().into_owned()
+}
+
+/// Refuse fonts whose OS/2 fsType forbids embedding a subset of their outlines
+fn check_embedding(face: &Face) -> Result<(), String> {
+    let Some(os2) = face.tables().os2 else {
+        return Ok(());
+    };
+    if os2.permissions() == Some(Permissions::Restricted) {
+        return Err("Font license restricts embedding (fsType: Restricted License)".to_string());
+    }
+    if !os2.is_subsetting_allowed() {
+        return Err("Font license does not allow subsetting (fsType: No Subsetting)".to_string());
+    }
+    if !os2.is_outline_embedding_allowed() {
+        return Err("Font license only allows embedding bitmaps (fsType: Bitmap Embedding Only)".to_string());
+    }
+    Ok(())
+}
+
+/// Parse CSS unicode-range syntax: "U+26, U+0-7F, U+4??"
+fn parse_unicode_ranges(value: &str) -> Result<Vec<(u32, u32)>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|range| !range.is_empty())
+        .map(|range| {
+            let invalid = || format!("Invalid Unicode range: {}", range);
+            let body = range
+                .strip_prefix("U+")
+                .or_else(|| range.strip_prefix("u+"))
+                .ok_or_else(invalid)?;
+            let hex = |digits: &str| u32::from_str_radix(digits, 16).map_err(|_| invalid());
+            let (start, end) = match body.split_once('-') {
+                Some((start, end)) => (hex(start)?, hex(end)?),
+                None if body.contains('?') => (hex(&body.replace('?', "0"))?, hex(&body.replace('?', "F"))?),
+                None => (hex(body)?, hex(body)?),
+            };
+            if start > end || end > 0x10FFFF {
+                return Err(invalid());
+            }
+            Ok((start, end))
+        })
+        .collect()
+}
+
+/// .notdef and the mapped glyphs, closed over GSUB substitutions, COLR layers and composite
+/// glyph components
+fn glyph_closure(face: &Face, glyf: Option<&GlyfTable>, glyphs: impl Iterator<Item = u16>) -> BTreeSet<u16> {
+    let mut keep: BTreeSet<u16> = std::iter::once(0).chain(glyphs).collect();
+    loop {
+        let before = keep.len();
+        gsub_closure(face, &mut keep);
+        colr_closure(face, &mut keep);
+        if let Some(glyf) = glyf {
+            let components: Vec<u16> = keep.iter().flat_map(|&gid| glyf.components(gid)).collect();
+            keep.extend(components);
+        }
+        if keep.len() == before {
+            return keep;
+        }
+    }
+}
+
+/// Add every glyph a GSUB lookup can produce from the kept glyphs. Contextual lookups only
+/// call other lookups, which are visited directly, so this over-approximates what shaping
+/// can actually reach.
+fn gsub_closure(face: &Face, keep: &mut BTreeSet<u16>) {
+    let Some(gsub) = face.tables().gsub else {
+        return;
+    };
+    for lookup in gsub.lookups {
+        for subtable in lookup.subtables.into_iter::<SubstitutionSubtable>() {
+            let mut added: Vec<GlyphId> = Vec::new();
+            for &gid in keep.iter() {
+                let glyph = GlyphId(gid);
+                let Some(index) = subtable.coverage().get(glyph) else {
+                    continue;
+                };
+                match &subtable {
+                    SubstitutionSubtable::Single(SingleSubstitution::Format1 { delta, .. }) => {
+                        added.push(GlyphId((gid as i32 + *delta as i32) as u16));
+                    }
+                    SubstitutionSubtable::Single(SingleSubstitution::Format2 { substitutes, .. }) => {
+                        added.extend(substitutes.get(index));
+                    }
+                    SubstitutionSubtable::Multiple(multiple) => {
+                        added.extend(multiple.sequences.get(index).into_iter().flat_map(|s| s.substitutes));
+                    }
+                    SubstitutionSubtable::Alternate(alternate) => {
+                        added.extend(alternate.alternate_sets.get(index).into_iter().flat_map(|s| s.alternates));
+                    }
+                    SubstitutionSubtable::Ligature(ligature) => {
+                        let ligatures = ligature.ligature_sets.get(index).into_iter().flatten();
+                        added.extend(
+                            ligatures
+                                .filter(|ligature| ligature.components.into_iter().all(|c| keep.contains(&c.0)))
+                                .map(|ligature| ligature.glyph),
+                        );
+                    }
+                    SubstitutionSubtable::ReverseChainSingle(reverse) => {
+                        added.extend(reverse.substitutes.get(index));
+                    }
+                    SubstitutionSubtable::Context(_) | SubstitutionSubtable::ChainContext(_) => {}
+                }
+            }
+            keep.extend(added.into_iter().map(|glyph| glyph.0));
+        }
+    }
+}
+
+/// Add the glyphs kept color glyphs paint: COLR v0 layers, and the PaintGlyph outlines of
+/// COLR v1 paint graphs, following PaintColrLayers and PaintColrGlyph
+fn colr_closure(face: &Face, keep: &mut BTreeSet<u16>) {
+    if face.tables().colr.is_none() {
+        return;
+    }
+    let mut painter = PaintedGlyphs::default();
+    for &gid in keep.iter() {
+        if face.is_color_glyph(GlyphId(gid)) {
+            face.paint_color_glyph(GlyphId(gid), 0, RgbaColor::new(0, 0, 0, 255), &mut painter);
+        }
+    }
+    keep.extend(painter.glyphs);
+}
+
+/// Painter that only records the outline glyphs a color glyph is drawn with
+#[derive(Default)]
+struct PaintedGlyphs {
+    glyphs: Vec<u16>,
+}
+
+impl<'a> Painter<'a> for PaintedGlyphs {
+    fn outline_glyph(&mut self, glyph_id: GlyphId) {
+        self.glyphs.push(glyph_id.0);
+    }
+    fn paint(&mut self, _: Paint<'a>) {}
+    fn push_clip(&mut self) {}
+    fn push_clip_box(&mut self, _: ClipBox) {}
+    fn pop_clip(&mut self) {}
+    fn push_layer(&mut self, _: CompositeMode) {}
+    fn pop_layer(&mut self) {}
+    fn push_transform(&mut self, _: Transform) {}
+    fn pop_transform(&mut self) {}
+}
+
+/// glyf data split by loca
+struct GlyfTable<'a> {
+    data: &'a [u8],
+    offsets: Vec<usize>,
+}
+
+impl<'a> GlyfTable<'a> {
+    fn parse(face: &Face<'a>, num_glyphs: u16) -> Option<Self> {
+        let data = raw::table(face, b"glyf")?;
+        let loca = raw::table(face, b"loca")?;
+        let long_offsets = read_i16(raw::table(face, b"head")?, 50)? == 1;
+        let offsets = (0..=num_glyphs as usize)
+            .map(|i| match long_offsets {
+                true => read_u32(loca, i * 4).map(|offset| offset as usize),
+                false => read_u16(loca, i * 2).map(|offset| offset as usize * 2),
+            })
+            .collect::<Option<Vec<usize>>>()?;
+        Some(Self { data, offsets })
+    }
+
+    fn glyph(&self, gid: u16) -> &'a [u8] {
+        let (Some(&start), Some(&end)) = (self.offsets.get(gid as usize), self.offsets.get(gid as usize + 1)) else {
+            return &[];
+        };
+        self.data.get(start..end).unwrap_or(&[])
+    }
+
+    fn components(&self, gid: u16) -> Vec<u16> {
+        composite_components(self.glyph(gid))
+            .map(|(components, _)| components.into_iter().map(|(_, gid)| gid).collect())
+            .unwrap_or_default()
+    }
+
+    /// New glyf and loca tables with only the kept glyphs; returns whether loca is long
+    fn subset(&self, keep: &BTreeSet<u16>, drop_hinting: bool) -> (Vec<u8>, Vec<u8>, bool) {
+        let mut glyf: Vec<u8> = Vec::new();
+        let mut offsets: Vec<usize> = vec![0];
+        for gid in 0..self.offsets.len().saturating_sub(1) as u16 {
+            if keep.contains(&gid) {
+                let glyph = self.glyph(gid);
+                match drop_hinting {
+                    true => glyf.extend(strip_instructions(glyph)),
+                    false => glyf.extend_from_slice(glyph),
+                }
+                // Short loca offsets count 2-byte words
+                if glyf.len() % 2 == 1 {
+                    glyf.push(0);
+                }
+            }
+            offsets.push(glyf.len());
+        }
+
+        let long_offsets = glyf.len() > 0x1FFFE;
+        let loca = offsets
+            .iter()
+            .flat_map(|&offset| match long_offsets {
+                true => (offset as u32).to_be_bytes().to_vec(),
+                false => ((offset / 2) as u16).to_be_bytes().to_vec(),
+            })
+            .collect();
+        (glyf, loca, long_offsets)
+    }
+}
+
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+
+/// (flags offset, glyph ID) of each component of a composite glyph, and where the component
+/// records end. None for simple and empty glyphs.
+fn composite_components(glyph: &[u8]) -> Option<(Vec<(usize, u16)>, usize)> {
+    if read_i16(glyph, 0)? >= 0 {
+        return None;
+    }
+    let mut components = Vec::new();
+    let mut pos = 10;
+    loop {
+        let flags = read_u16(glyph, pos)?;
+        components.push((pos, read_u16(glyph, pos + 2)?));
+        pos += 4 + if flags & ARG_1_AND_2_ARE_WORDS != 0 { 4 } else { 2 };
+        pos += match flags {
+            f if f & WE_HAVE_A_SCALE != 0 => 2,
+            f if f & WE_HAVE_AN_X_AND_Y_SCALE != 0 => 4,
+            f if f & WE_HAVE_A_TWO_BY_TWO != 0 => 8,
+            _ => 0,
+        };
+        if flags & MORE_COMPONENTS == 0 {
+            return Some((components, pos.min(glyph.len())));
+        }
+    }
+}
+
+/// Remove the TrueType instructions of a glyph
+fn strip_instructions(glyph: &[u8]) -> Vec<u8> {
+    if let Some((components, end)) = composite_components(glyph) {
+        let mut stripped = glyph[..end].to_vec();
+        for (flags_offset, _) in components {
+            stripped[flags_offset] &= !(WE_HAVE_INSTRUCTIONS >> 8) as u8;
+        }
+        return stripped;
+    }
+    let Some(contours) = read_i16(glyph, 0).filter(|&contours| contours >= 0) else {
+        return glyph.to_vec();
+    };
+    let length_offset = 10 + contours as usize * 2;
+    let Some(length) = read_u16(glyph, length_offset) else {
+        return glyph.to_vec();
+    };
+    let rest = length_offset + 2 + length as usize;
+    if rest > glyph.len() {
+        return glyph.to_vec();
+    }
+    let mut stripped = glyph[..length_offset].to_vec();
+    stripped.extend_from_slice(&[0, 0]);
+    stripped.extend_from_slice(&glyph[rest..]);
+    stripped
+}
+
+/// maxp with the TrueType program limits cleared when hinting is dropped
+fn subset_maxp(maxp: &[u8], drop_hinting: bool) -> Vec<u8> {
+    let mut maxp = maxp.to_vec();
+    if drop_hinting && read_u32(&maxp, 0) == Some(0x0001_0000) && maxp.len() >= 32 {
+        // maxZones 1, then maxTwilightPoints..maxSizeOfInstructions 0
+        maxp[14..16].copy_from_slice(&1u16.to_be_bytes());
+        maxp[16..28].fill(0);
+    }
+    maxp
+}
+
+/// OS/2 with usFirstCharIndex/usLastCharIndex matching the new cmap
+fn subset_os2(os2: &[u8], mapping: &BTreeMap<u32, u16>) -> Vec<u8> {
+    let mut os2 = os2.to_vec();
+    if let (Some((&first, _)), Some((&last, _))) = (mapping.first_key_value(), mapping.last_key_value()) {
+        if os2.len() >= 68 {
+            os2[64..66].copy_from_slice(&(first.min(0xFFFF) as u16).to_be_bytes());
+            os2[66..68].copy_from_slice(&(last.min(0xFFFF) as u16).to_be_bytes());
+        }
+    }
+    os2
+}
+
+/// cmap with a Windows BMP format 4 subtable, plus format 12 when needed for
+/// supplementary planes or when format 4 would overflow
+fn build_cmap(mapping: &BTreeMap<u32, u16>) -> Vec<u8> {
+    let format4 = cmap_format4(mapping);
+    let needs_format12 = format4.is_none() || mapping.keys().any(|&cp| cp > 0xFFFF);
+    let mut subtables: Vec<(u16, Vec<u8>)> = Vec::new();
+    if let Some(format4) = format4 {
+        subtables.push((1, format4));
+    }
+    if needs_format12 {
+        subtables.push((10, cmap_format12(mapping)));
+    }
+
+    let mut cmap: Vec<u8> = Vec::new();
+    push_u16(&mut cmap, 0);
+    push_u16(&mut cmap, subtables.len() as u16);
+    let mut offset = 4 + subtables.len() * 8;
+    for (encoding, subtable) in &subtables {
+        push_u16(&mut cmap, 3);
+        push_u16(&mut cmap, *encoding);
+        push_u32(&mut cmap, offset as u32);
+        offset += subtable.len();
+    }
+    for (_, subtable) in subtables {
+        cmap.extend(subtable);
+    }
+    cmap
+}
+
+/// Format 4: one segment per run of consecutive code points, using idDelta when the glyph IDs
+/// are consecutive too and glyphIdArray otherwise. None when the table would exceed 64 KB.
+fn cmap_format4(mapping: &BTreeMap<u32, u16>) -> Option<Vec<u8>> {
+    let mut runs: Vec<Vec<(u16, u16)>> = Vec::new();
+    for (&cp, &gid) in mapping.range(..0xFFFF) {
+        match runs.last_mut() {
+            Some(run) if run.last().is_some_and(|&(last, _)| last as u32 + 1 == cp) => run.push((cp as u16, gid)),
+            _ => runs.push(vec![(cp as u16, gid)]),
+        }
+    }
+
+    let seg_count = runs.len() + 1;
+    let mut ends: Vec<u16> = Vec::new();
+    let mut starts: Vec<u16> = Vec::new();
+    let mut deltas: Vec<u16> = Vec::new();
+    let mut range_offsets: Vec<u16> = Vec::new();
+    let mut glyph_ids: Vec<u16> = Vec::new();
+    for (i, run) in runs.iter().enumerate() {
+        let (start, first_gid) = run[0];
+        starts.push(start);
+        ends.push(run[run.len() - 1].0);
+        let delta = first_gid.wrapping_sub(start);
+        if run.iter().all(|&(cp, gid)| cp.wrapping_add(delta) == gid) {
+            deltas.push(delta);
+            range_offsets.push(0);
+        } else {
+            deltas.push(0);
+            range_offsets.push(u16::try_from(2 * (seg_count - i + glyph_ids.len())).ok()?);
+            glyph_ids.extend(run.iter().map(|&(_, gid)| gid));
+        }
+    }
+    // Required final segment
+    starts.push(0xFFFF);
+    ends.push(0xFFFF);
+    deltas.push(1);
+    range_offsets.push(0);
+
+    let length = u16::try_from(16 + seg_count * 8 + glyph_ids.len() * 2).ok()?;
+    let search_range = 2 * (1u16 << (seg_count as u16).ilog2());
+    let mut table: Vec<u8> = Vec::new();
+    for value in [4, length, 0, seg_count as u16 * 2, search_range] {
+        push_u16(&mut table, value);
+    }
+    push_u16(&mut table, search_range.ilog2() as u16 - 1);
+    push_u16(&mut table, seg_count as u16 * 2 - search_range);
+    ends.iter().for_each(|&v| push_u16(&mut table, v));
+    push_u16(&mut table, 0);
+    for values in [starts, deltas, range_offsets, glyph_ids] {
+        values.iter().for_each(|&v| push_u16(&mut table, v));
+    }
+    Some(table)
+}
+
+/// Format 12: groups where both code points and glyph IDs are consecutive
+fn cmap_format12(mapping: &BTreeMap<u32, u16>) -> Vec<u8> {
+    let mut groups: Vec<(u32, u32, u32)> = Vec::new();
+    for (&cp, &gid) in mapping {
+        match groups.last_mut() {
+            Some((start, end, first_gid)) if *end + 1 == cp && *first_gid + (cp - *start) == gid as u32 => *end = cp,
+            _ => groups.push((cp, cp, gid as u32)),
+        }
+    }
+    let mut table: Vec<u8> = Vec::new();
+    push_u16(&mut table, 12);
+    push_u16(&mut table, 0);
+    push_u32(&mut table, 16 + groups.len() as u32 * 12);
+    push_u32(&mut table, 0);
+    push_u32(&mut table, groups.len() as u32);
+    for (start, end, gid) in groups {
+        [start, end, gid].iter().for_each(|&v| push_u32(&mut table, v));
+    }
+    table
+}
+
+/// gvar without the variation data of dropped glyphs
+fn subset_gvar(gvar: &[u8], keep: &BTreeSet<u16>) -> Option<Vec<u8>> {
+    let shared_tuples_offset = read_u32(gvar, 8)? as usize;
+    let glyph_count = read_u16(gvar, 12)? as usize;
+    let long_offsets = read_u16(gvar, 14)? & 1 != 0;
+    let data_offset = read_u32(gvar, 16)? as usize;
+    let axis_count = read_u16(gvar, 4)? as usize;
+    let shared_tuple_count = read_u16(gvar, 6)? as usize;
+    let offset = |i: usize| match long_offsets {
+        true => read_u32(gvar, 20 + i * 4).map(|offset| offset as usize),
+        false => read_u16(gvar, 20 + i * 2).map(|offset| offset as usize * 2),
+    };
+
+    let mut data: Vec<u8> = Vec::new();
+    let mut offsets: Vec<usize> = vec![0];
+    for gid in 0..glyph_count {
+        if keep.contains(&(gid as u16)) {
+            let (start, end) = (data_offset + offset(gid)?, data_offset + offset(gid + 1)?);
+            data.extend_from_slice(gvar.get(start..end)?);
+            if data.len() % 2 == 1 {
+                data.push(0);
+            }
+        }
+        offsets.push(data.len());
+    }
+
+    let offsets_size = (glyph_count + 1) * if long_offsets { 4 } else { 2 };
+    let shared_tuples = gvar.get(shared_tuples_offset..shared_tuples_offset + shared_tuple_count * axis_count * 2)?;
+    let mut table = gvar[..20].to_vec();
+    let new_shared_offset = 20 + offsets_size;
+    let new_data_offset = new_shared_offset + shared_tuples.len();
+    table[8..12].copy_from_slice(&(new_shared_offset as u32).to_be_bytes());
+    table[16..20].copy_from_slice(&(new_data_offset as u32).to_be_bytes());
+    for offset in offsets {
+        match long_offsets {
+            true => push_u32(&mut table, offset as u32),
+            false => push_u16(&mut table, (offset / 2) as u16),
+        }
+    }
+    table.extend_from_slice(shared_tuples);
+    table.extend(data);
+    Some(table)
+}
+
+/// A Top/Font/Private DICT entry: operator (escaped operators as 1200 + second byte),
+/// integer operands (reals read as 0) and the raw bytes of the entry
+struct DictEntry<'a> {
+    operator: u16,
+    operands: Vec<i32>,
+    raw: &'a [u8],
+}
+
+const CHARSET: u16 = 15;
+const ENCODING: u16 = 16;
+const CHAR_STRINGS: u16 = 17;
+const PRIVATE: u16 = 18;
+const SUBRS: u16 = 19;
+const FD_ARRAY: u16 = 1236;
+const FD_SELECT: u16 = 1237;
+
+fn parse_dict(data: &[u8]) -> Option<Vec<DictEntry<'_>>> {
+    let mut entries = Vec::new();
+    let (mut pos, mut entry_start) = (0, 0);
+    let mut operands = Vec::new();
+    while pos < data.len() {
+        let b0 = data[pos];
+        match b0 {
+            0..=21 => {
+                let operator = if b0 == 12 {
+                    pos += 1;
+                    1200 + *data.get(pos)? as u16
+                } else {
+                    b0 as u16
+                };
+                pos += 1;
+                entries.push(DictEntry { operator, operands: std::mem::take(&mut operands), raw: &data[entry_start..pos] });
+                entry_start = pos;
+            }
+            28 => {
+                operands.push(read_i16(data, pos + 1)? as i32);
+                pos += 3;
+            }
+            29 => {
+                operands.push(read_u32(data, pos + 1)? as i32);
+                pos += 5;
+            }
+            30 => {
+                // Real number: nibbles up to the 0xF terminator
+                pos += 1;
+                while data.get(pos)? & 0x0F != 0x0F && data.get(pos)? >> 4 != 0x0F {
+                    pos += 1;
+                }
+                pos += 1;
+                operands.push(0);
+            }
+            32..=246 => {
+                operands.push(b0 as i32 - 139);
+                pos += 1;
+            }
+            247..=250 => {
+                operands.push((b0 as i32 - 247) * 256 + *data.get(pos + 1)? as i32 + 108);
+                pos += 2;
+            }
+            251..=254 => {
+                operands.push(-(b0 as i32 - 251) * 256 - *data.get(pos + 1)? as i32 - 108);
+                pos += 2;
+            }
+            _ => return None,
+        }
+    }
+    Some(entries)
+}
+
+/// Write a DICT, re-encoding the operands of `offset_operators` as 5-byte integers mapped
+/// through `map` so the DICT size doesn't depend on the new offsets
+fn write_dict(entries: &[DictEntry], offset_operators: &[u16], map: &dyn Fn(u16, &[i32]) -> Vec<i32>) -> Vec<u8> {
+    let mut dict = Vec::new();
+    for entry in entries {
+        if !offset_operators.contains(&entry.operator) {
+            dict.extend_from_slice(entry.raw);
+            continue;
+        }
+        for operand in map(entry.operator, &entry.operands) {
+            dict.push(29);
+            dict.extend_from_slice(&operand.to_be_bytes());
+        }
+        if entry.operator >= 1200 {
+            dict.extend_from_slice(&[12, (entry.operator - 1200) as u8]);
+        } else {
+            dict.push(entry.operator as u8);
+        }
+    }
+    dict
+}
+
+fn dict_operands<'a>(entries: &'a [DictEntry], operator: u16) -> Option<&'a [i32]> {
+    entries.iter().find(|entry| entry.operator == operator).map(|entry| entry.operands.as_slice())
+}
+
+/// Items of the CFF INDEX at `offset` and the INDEX's total size
+fn read_index(data: &[u8], offset: usize) -> Option<(Vec<&[u8]>, usize)> {
+    let count = read_u16(data, offset)? as usize;
+    if count == 0 {
+        return Some((Vec::new(), 2));
+    }
+    let off_size = *data.get(offset + 2)? as usize;
+    if !(1..=4).contains(&off_size) {
+        return None;
+    }
+    let read_offset = |i: usize| {
+        let start = offset + 3 + i * off_size;
+        let bytes = data.get(start..start + off_size)?;
+        Some(bytes.iter().fold(0usize, |value, &b| value << 8 | b as usize))
+    };
+    let data_start = offset + 3 + (count + 1) * off_size - 1;
+    let offsets = (0..=count).map(read_offset).collect::<Option<Vec<usize>>>()?;
+    let items = offsets
+        .windows(2)
+        .map(|pair| data.get(data_start + pair[0]..data_start + pair[1]))
+        .collect::<Option<Vec<&[u8]>>>()?;
+    Some((items, data_start + offsets[count] - offset))
+}
+
+fn write_index(items: &[&[u8]]) -> Vec<u8> {
+    let mut index = Vec::new();
+    push_u16(&mut index, items.len() as u16);
+    if items.is_empty() {
+        return index;
+    }
+    let total: usize = items.iter().map(|item| item.len()).sum::<usize>() + 1;
+    let off_size = match total {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        0x1_0000..=0xFF_FFFF => 3,
+        _ => 4,
+    };
+    index.push(off_size as u8);
+    let mut offset = 1usize;
+    index.extend_from_slice(&(offset as u32).to_be_bytes()[4 - off_size..]);
+    for item in items {
+        offset += item.len();
+        index.extend_from_slice(&(offset as u32).to_be_bytes()[4 - off_size..]);
+    }
+    for item in items {
+        index.extend_from_slice(item);
+    }
+    index
+}
+
+/// CFF with the charstrings of dropped glyphs replaced by an empty outline. The Top DICT
+/// INDEX, CharStrings INDEX and FDArray are rewritten in place and every offset that points
+/// past them is shifted; charset, FDSelect, Private DICTs and subroutines are copied as is.
+fn subset_cff(cff: &[u8], keep: &BTreeSet<u16>) -> Result<Vec<u8>, String> {
+    let malformed = || "Malformed CFF table".to_string();
+    let header_size = *cff.get(2).ok_or_else(malformed)? as usize;
+    let (_, name_size) = read_index(cff, header_size).ok_or_else(malformed)?;
+    let top_start = header_size + name_size;
+    let (top_dicts, top_size) = read_index(cff, top_start).ok_or_else(malformed)?;
+    let [top_dict] = top_dicts.as_slice() else {
+        return Err("CFF tables with more than one font are not supported".to_string());
+    };
+    let top = parse_dict(top_dict).ok_or_else(malformed)?;
+
+    let char_strings_offset = match dict_operands(&top, CHAR_STRINGS) {
+        Some(&[offset]) => offset as usize,
+        _ => return Err(malformed()),
+    };
+    let (char_strings, char_strings_size) = read_index(cff, char_strings_offset).ok_or_else(malformed)?;
+    let new_char_strings: Vec<&[u8]> = char_strings
+        .iter()
+        .enumerate()
+        .map(|(gid, charstring)| if keep.contains(&(gid as u16)) { *charstring } else { EMPTY_CHARSTRING })
+        .collect();
+    let new_char_strings = write_index(&new_char_strings);
+
+    let fd_array = match dict_operands(&top, FD_ARRAY) {
+        Some(&[offset]) => {
+            let (fonts, size) = read_index(cff, offset as usize).ok_or_else(malformed)?;
+            let fonts = fonts.into_iter().map(parse_dict).collect::<Option<Vec<_>>>().ok_or_else(malformed)?;
+            Some((offset as usize, size, fonts))
+        }
+        _ => None,
+    };
+
+    // Replaced regions (original start, original size, new size), sorted by position
+    let top_dict_size = write_dict(&top, &[CHARSET, ENCODING, CHAR_STRINGS, PRIVATE, FD_ARRAY, FD_SELECT], &|_, o| o.to_vec()).len();
+    let mut regions = vec![
+        (top_start, top_size, write_index(&[&vec![0; top_dict_size]]).len()),
+        (char_strings_offset, char_strings_size, new_char_strings.len()),
+    ];
+    if let Some((offset, size, fonts)) = &fd_array {
+        let dicts: Vec<Vec<u8>> = fonts.iter().map(|font| write_dict(font, &[PRIVATE], &|_, o| o.to_vec())).collect();
+        regions.push((*offset, *size, write_index(&dicts.iter().map(Vec::as_slice).collect::<Vec<_>>()).len()));
+    }
+    regions.sort_unstable();
+    if regions.windows(2).any(|pair| pair[0].0 + pair[0].1 > pair[1].0) {
+        return Err(malformed());
+    }
+    let relocate = |offset: i32| -> i32 {
+        let shift: isize = regions
+            .iter()
+            .filter(|&&(start, size, _)| start + size <= offset as usize)
+            .map(|&(_, size, new_size)| new_size as isize - size as isize)
+            .sum();
+        (offset as isize + shift) as i32
+    };
+    // Subrs are addressed relative to their Private DICT, so nothing may move in between
+    let check_private = |private: &[i32]| -> Result<(), String> {
+        let &[size, offset] = private else {
+            return Err(malformed());
+        };
+        let dict = cff.get(offset as usize..(offset + size) as usize).ok_or_else(malformed)?;
+        let subrs = parse_dict(dict).and_then(|dict| dict_operands(&dict, SUBRS).map(|o| o.to_vec()));
+        if let Some(&[subrs]) = subrs.as_deref() {
+            if relocate(offset + subrs) - relocate(offset) != subrs {
+                return Err("Unsupported CFF layout: subroutines are separated from their Private DICT".to_string());
+            }
+        }
+        Ok(())
+    };
+    let map_offsets = |operator: u16, operands: &[i32]| -> Vec<i32> {
+        match (operator, operands) {
+            (PRIVATE, &[size, offset]) => vec![size, relocate(offset)],
+            // charset 0-2 and Encoding 0-1 name predefined tables
+            (CHARSET, &[offset]) if offset <= 2 => vec![offset],
+            (ENCODING, &[offset]) if offset <= 1 => vec![offset],
+            _ => operands.iter().map(|&offset| relocate(offset)).collect(),
+        }
+    };
+
+    if let Some(private) = dict_operands(&top, PRIVATE) {
+        check_private(private)?;
+    }
+    let new_top = write_dict(&top, &[CHARSET, ENCODING, CHAR_STRINGS, PRIVATE, FD_ARRAY, FD_SELECT], &map_offsets);
+    let mut replacements: BTreeMap<usize, Vec<u8>> = BTreeMap::new();
+    replacements.insert(top_start, write_index(&[&new_top]));
+    replacements.insert(char_strings_offset, new_char_strings);
+    if let Some((offset, _, fonts)) = &fd_array {
+        let mut dicts: Vec<Vec<u8>> = Vec::new();
+        for font in fonts {
+            if let Some(private) = dict_operands(font, PRIVATE) {
+                check_private(private)?;
+            }
+            dicts.push(write_dict(font, &[PRIVATE], &map_offsets));
+        }
+        replacements.insert(*offset, write_index(&dicts.iter().map(Vec::as_slice).collect::<Vec<_>>()));
+    }
+
+    let mut output = Vec::with_capacity(cff.len());
+    let mut pos = 0;
+    for (start, size, _) in &regions {
+        output.extend_from_slice(&cff[pos..*start]);
+        output.extend(replacements.remove(start).unwrap_or_default());
+        pos = start + size;
+    }
+    output.extend_from_slice(cff.get(pos..).ok_or_else(malformed)?);
+    Ok(output)
+}
+
+/// Assemble an sfnt from its tables and fix up the checksums
+fn build_sfnt(sfnt_version: u32, mut tables: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    tables.sort_by_key(|table| table.0);
+    let num_tables = tables.len() as u16;
+    let entry_selector = num_tables.max(1).ilog2() as u16;
+    let search_range = (1u16 << entry_selector) * 16;
+
+    let mut font: Vec<u8> = Vec::new();
+    push_u32(&mut font, sfnt_version);
+    for value in [num_tables, search_range, entry_selector, num_tables * 16 - search_range] {
+        push_u16(&mut font, value);
+    }
+
+    let mut offset = 12 + tables.len() * 16;
+    let mut head_offset = None;
+    for (tag, table) in tables.iter_mut() {
+        if tag == b"head" && table.len() >= 12 {
+            table[8..12].fill(0);
+            head_offset = Some(offset);
+        }
+        font.extend_from_slice(tag);
+        push_u32(&mut font, checksum(table));
+        push_u32(&mut font, offset as u32);
+        push_u32(&mut font, table.len() as u32);
+        offset += table.len().div_ceil(4) * 4;
+    }
+    for (_, table) in &tables {
+        font.extend_from_slice(table);
+        font.resize(font.len().div_ceil(4) * 4, 0);
+    }
+
+    if let Some(head_offset) = head_offset {
+        let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&font));
+        font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    }
+    font
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+fn push_u16(data: &mut Vec<u8>, value: u16) {
+    data.extend_from_slice(&value.to_be_bytes());
+}
+
+fn push_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend_from_slice(&value.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::state::tests::create_test_font;
+
+    #[test]
+    fn test_default_subset_path_extension() {
+        let mut font = create_test_font("1", "Source Sans", 400);
+        font.path = "/fonts/SourceSans.ttf".to_string();
+        font.rendering.outline_format = OutlineFormat::Cff;
+        assert!(default_subset_path(&font).ends_with("Source Sans-400-subset.otf"));
+        font.path = "/fonts/SourceSans.otf".to_string();
+        font.rendering.outline_format = OutlineFormat::TrueType;
+        assert!(default_subset_path(&font).ends_with("Source Sans-400-subset.ttf"));
+    }
+
+    #[test]
+    fn test_subset_refuses_to_overwrite_source() {
+        let path = std::env::temp_dir().join("fontscape-subset-source.ttf");
+        fs::write(&path, b"not a font").unwrap();
+        let path = path.to_string_lossy().into_owned();
+        let options = SubsetOptions { text: "A".to_string(), ..Default::default() };
+        let error = subset_font_file(&path, 0, &options, &path).unwrap_err();
+        assert!(error.contains("font file itself"), "{}", error);
+        assert_eq!(fs::read(&path).unwrap(), b"not a font");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_unicode_ranges() {
+        assert_eq!(parse_unicode_ranges(""), Ok(vec![]));
+        assert_eq!(
+            parse_unicode_ranges("U+20-7E, u+4E00, U+4??"),
+            Ok(vec![(0x20, 0x7E), (0x4E00, 0x4E00), (0x400, 0x4FF)])
+        );
+        assert!(parse_unicode_ranges("U+7E-20").is_err());
+        assert!(parse_unicode_ranges("U+110000").is_err());
+        assert!(parse_unicode_ranges("4E00").is_err());
+    }
+
+    #[test]
+    fn test_cmap_subtables() {
+        let mapping: BTreeMap<u32, u16> = [(0x41, 10), (0x42, 11), (0x43, 20), (0x4E00, 30), (0x1F600, 40)].into();
+        let format4 = cmap_format4(&mapping).unwrap();
+        let subtable = ttf_parser::cmap::Subtable4::parse(&format4).unwrap();
+        for (&cp, &gid) in mapping.range(..0xFFFF) {
+            assert_eq!(subtable.glyph_index(cp), Some(GlyphId(gid)));
+        }
+        assert_eq!(subtable.glyph_index(0x44), None);
+
+        let format12 = cmap_format12(&mapping);
+        let subtable = ttf_parser::cmap::Subtable12::parse(&format12).unwrap();
+        for (&cp, &gid) in &mapping {
+            assert_eq!(subtable.glyph_index(cp), Some(GlyphId(gid)));
+        }
+        // BMP plus format 12 for the emoji
+        assert_eq!(read_u16(&build_cmap(&mapping), 2), Some(2));
+    }
+
+    #[test]
+    fn test_strip_instructions() {
+        // One contour, 3 bytes of instructions, then flags/coordinates
+        let simple = [0, 1, 0, 0, 0, 0, 0, 10, 0, 10, 0, 0, 0, 3, 0xB0, 0x01, 0x2A, 0x01, 0x05];
+        assert_eq!(strip_instructions(&simple), [0, 1, 0, 0, 0, 0, 0, 10, 0, 10, 0, 0, 0, 0, 0x01, 0x05]);
+
+        // Two components, the last with instructions
+        let composite = [
+            0xFF, 0xFF, 0, 0, 0, 0, 0, 10, 0, 10, // header
+            0x00, 0x20, 0, 5, 0, 0, // MORE_COMPONENTS, glyph 5, byte args
+            0x01, 0x00, 0, 7, 0, 0, // WE_HAVE_INSTRUCTIONS, glyph 7
+            0, 2, 0xB0, 0x01, // instructions
+        ];
+        let (components, end) = composite_components(&composite).unwrap();
+        assert_eq!(components, vec![(10, 5), (16, 7)]);
+        assert_eq!(end, 22);
+        let stripped = strip_instructions(&composite);
+        assert_eq!(stripped.len(), 22);
+        assert_eq!(read_u16(&stripped, 16), Some(0));
+    }
+
+    #[test]
+    fn test_cff_index_and_dict() {
+        let items: [&[u8]; 3] = [b"ab", b"", b"cde"];
+        let index = write_index(&items);
+        assert_eq!(read_index(&index, 0), Some((items.to_vec(), index.len())));
+        assert_eq!(read_index(&write_index(&[]), 0), Some((vec![], 2)));
+
+        // 17 (CharStrings) as a 1-byte operand, then Private (size 28, offset 1000)
+        let dict = [139 + 17, 17, 28, 0x00, 0x1C, 250, 124, 18];
+        let entries = parse_dict(&dict).unwrap();
+        assert_eq!(dict_operands(&entries, CHAR_STRINGS), Some(&[17][..]));
+        assert_eq!(dict_operands(&entries, PRIVATE), Some(&[28, 1000][..]));
+        let rewritten = write_dict(&entries, &[PRIVATE], &|_, operands| vec![operands[0], operands[1] + 1]);
+        assert_eq!(dict_operands(&parse_dict(&rewritten).unwrap(), PRIVATE), Some(&[28, 1001][..]));
+    }
+
+    #[test]
+    fn test_subset_cff() {
+        struct Bounds;
+        impl ttf_parser::OutlineBuilder for Bounds {
+            fn move_to(&mut self, _: f32, _: f32) {}
+            fn line_to(&mut self, _: f32, _: f32) {}
+            fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) {}
+            fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) {}
+            fn close(&mut self) {}
+        }
+        let int = |value: i32| [&[29u8][..], &value.to_be_bytes()].concat();
+
+        // Squares of 100 and 50 units: rmoveto, two rlineto, endchar
+        let char_strings = write_index(&[
+            EMPTY_CHARSTRING,
+            &[139, 139, 21, 239, 139, 5, 139, 239, 5, 14],
+            &[139, 139, 21, 189, 139, 5, 139, 189, 5, 14],
+        ]);
+        // Private DICT (Subrs right after it) follows the CharStrings, so it moves
+        let private = [int(6), vec![19]].concat();
+        let subrs = write_index(&[&[11]]);
+        let header_and_names = [&[1u8, 0, 4, 1][..], &write_index(&[b"Test"])].concat();
+        let top_size = write_index(&[&[0; 17]]).len();
+        let char_strings_offset = (header_and_names.len() + top_size + 4) as i32;
+        let private_offset = char_strings_offset + char_strings.len() as i32;
+        let top = [int(char_strings_offset), vec![17], int(private.len() as i32), int(private_offset), vec![18]].concat();
+        let cff = [header_and_names, write_index(&[&top]), write_index(&[]), write_index(&[]), char_strings, private, subrs]
+            .concat();
+        assert!(ttf_parser::cff::Table::parse(&cff).is_some());
+
+        let subset = subset_cff(&cff, &[0, 2].into()).unwrap();
+        assert_eq!(subset.len(), cff.len() - 9);
+        let table = ttf_parser::cff::Table::parse(&subset).unwrap();
+        assert_eq!(table.number_of_glyphs(), 3);
+        assert!(table.outline(GlyphId(1), &mut Bounds).is_err());
+        let bounds = table.outline(GlyphId(2), &mut Bounds).unwrap();
+        assert_eq!((bounds.x_max, bounds.y_max), (50, 50));
+        let top = parse_dict(read_index(&subset, 13).unwrap().0[0]).unwrap();
+        assert_eq!(dict_operands(&top, PRIVATE), Some(&[6, private_offset - 9][..]));
+    }
+
+    #[test]
+    fn test_checksum_padding() {
+        assert_eq!(checksum(&[0, 0, 0, 1, 0, 0, 0, 2]), 3);
+        assert_eq!(checksum(&[1]), 0x0100_0000);
+        let font = build_sfnt(0x0001_0000, vec![(*b"head", vec![0; 54]), (*b"abcd", vec![1])]);
+        assert_eq!(font.len() % 4, 0);
+        assert_eq!(checksum(&font), 0xB1B0_AFBA);
+    }
+}
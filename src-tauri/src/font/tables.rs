@@ -0,0 +1,448 @@
+//! sfnt table directory and parsed views of common tables, for inspecting problem fonts
+use super::raw::{self, read_fixed, read_i16, read_i64, read_u16, read_u32};
+use super::scanner::FontScanner;
+use serde::{Deserialize, Serialize};
+use ttf_parser::Face;
+
+/// An entry of the sfnt table directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableRecord {
+    pub tag: String,
+    pub offset: u32,
+    pub length: u32,
+    pub checksum: u32, // 表目录中记录的校验和
+}
+
+/// Table directory plus parsed views of the tables engineers look at most
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontTables {
+    pub tables: Vec<TableRecord>,
+    pub head: Option<HeadTable>,
+    pub hhea: Option<HheaTable>,
+    pub os2: Option<Os2Table>,
+    pub post: Option<PostTable>,
+    pub maxp: Option<MaxpTable>,
+    pub fvar: Option<FvarTable>,
+    pub gasp: Option<GaspTable>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadTable {
+    pub version: f64,
+    pub font_revision: f64,
+    pub checksum_adjustment: u32,
+    pub magic_number: u32,
+    pub flags: u16,
+    pub units_per_em: u16,
+    pub created: i64,  // 自 1904-01-01 起的秒数
+    pub modified: i64, // 自 1904-01-01 起的秒数
+    pub x_min: i16,
+    pub y_min: i16,
+    pub x_max: i16,
+    pub y_max: i16,
+    pub mac_style: u16,
+    pub lowest_rec_ppem: u16,
+    pub font_direction_hint: i16,
+    pub index_to_loc_format: i16,
+    pub glyph_data_format: i16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HheaTable {
+    pub version: f64,
+    pub ascender: i16,
+    pub descender: i16,
+    pub line_gap: i16,
+    pub advance_width_max: u16,
+    pub min_left_side_bearing: i16,
+    pub min_right_side_bearing: i16,
+    pub x_max_extent: i16,
+    pub caret_slope_rise: i16,
+    pub caret_slope_run: i16,
+    pub caret_offset: i16,
+    pub metric_data_format: i16,
+    pub number_of_h_metrics: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Os2Table {
+    pub version: u16,
+    pub x_avg_char_width: i16,
+    pub us_weight_class: u16,
+    pub us_width_class: u16,
+    pub fs_type: u16,
+    pub y_subscript_x_size: i16,
+    pub y_subscript_y_size: i16,
+    pub y_subscript_x_offset: i16,
+    pub y_subscript_y_offset: i16,
+    pub y_superscript_x_size: i16,
+    pub y_superscript_y_size: i16,
+    pub y_superscript_x_offset: i16,
+    pub y_superscript_y_offset: i16,
+    pub y_strikeout_size: i16,
+    pub y_strikeout_position: i16,
+    pub s_family_class: i16,
+    pub panose: Vec<u8>,
+    pub ul_unicode_range: Vec<u32>,
+    pub ach_vend_id: String,
+    pub fs_selection: u16,
+    pub us_first_char_index: u16,
+    pub us_last_char_index: u16,
+    pub s_typo_ascender: Option<i16>, // Apple 的 68 字节版本 0 表中没有
+    pub s_typo_descender: Option<i16>,
+    pub s_typo_line_gap: Option<i16>,
+    pub us_win_ascent: Option<u16>,
+    pub us_win_descent: Option<u16>,
+    pub ul_code_page_range: Option<Vec<u32>>, // version >= 1
+    pub sx_height: Option<i16>,               // version >= 2
+    pub s_cap_height: Option<i16>,
+    pub us_default_char: Option<u16>,
+    pub us_break_char: Option<u16>,
+    pub us_max_context: Option<u16>,
+    pub us_lower_optical_point_size: Option<u16>, // version >= 5
+    pub us_upper_optical_point_size: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostTable {
+    pub version: f64,
+    pub italic_angle: f64,
+    pub underline_position: i16,
+    pub underline_thickness: i16,
+    pub is_fixed_pitch: u32,
+    pub min_mem_type42: u32,
+    pub max_mem_type42: u32,
+    pub min_mem_type1: u32,
+    pub max_mem_type1: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaxpTable {
+    pub version: f64,
+    pub num_glyphs: u16,
+    pub truetype: Option<MaxpTrueType>, // 仅 version 1.0 (TrueType 轮廓)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaxpTrueType {
+    pub max_points: u16,
+    pub max_contours: u16,
+    pub max_composite_points: u16,
+    pub max_composite_contours: u16,
+    pub max_zones: u16,
+    pub max_twilight_points: u16,
+    pub max_storage: u16,
+    pub max_function_defs: u16,
+    pub max_instruction_defs: u16,
+    pub max_stack_elements: u16,
+    pub max_size_of_instructions: u16,
+    pub max_component_elements: u16,
+    pub max_component_depth: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FvarTable {
+    pub axes: Vec<FvarAxis>,
+    pub instances: Vec<FvarInstance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FvarAxis {
+    pub tag: String,
+    pub min_value: f64,
+    pub default_value: f64,
+    pub max_value: f64,
+    pub flags: u16,
+    pub name_id: u16,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FvarInstance {
+    pub subfamily_name_id: u16,
+    pub name: Option<String>,
+    pub coordinates: Vec<f64>,
+    pub postscript_name_id: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GaspTable {
+    pub version: u16,
+    pub ranges: Vec<GaspRange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GaspRange {
+    pub range_max_ppem: u16,
+    pub behavior: u16, // 0x1 GRIDFIT, 0x2 DOGRAY, 0x4 SYMMETRIC_GRIDFIT, 0x8 SYMMETRIC_SMOOTHING
+}
+
+/// Inspect the tables of a font file
+pub fn inspect_tables(path: String, face_index: u32) -> Result<FontTables, String> {
+    raw::with_face(&path, face_index, font_tables)
+}
+
+/// Table directory and parsed views of head, hhea, OS/2, post, maxp, fvar and gasp
+pub fn font_tables(face: &Face) -> FontTables {
+    let tables = face
+        .raw_face()
+        .table_records
+        .into_iter()
+        .map(|record| TableRecord {
+            tag: record.tag.to_string(),
+            offset: record.offset,
+            length: record.length,
+            checksum: record.check_sum,
+        })
+        .collect();
+
+    FontTables {
+        tables,
+        head: raw::table(face, b"head").and_then(parse_head),
+        hhea: raw::table(face, b"hhea").and_then(parse_hhea),
+        os2: raw::table(face, b"OS/2").and_then(parse_os2),
+        post: raw::table(face, b"post").and_then(parse_post),
+        maxp: raw::table(face, b"maxp").and_then(parse_maxp),
+        fvar: raw::table(face, b"fvar").and_then(|data| parse_fvar(face, data)),
+        gasp: raw::table(face, b"gasp").and_then(parse_gasp),
+    }
+}
+
+/// Versions such as 0x00005000 (maxp 0.5) and 0x00025000 (post 2.5) use the minor
+/// version nibble rather than a real 16.16 fraction
+fn read_version(data: &[u8], offset: usize) -> Option<f64> {
+    let v = read_u32(data, offset)?;
+    Some((v >> 16) as f64 + ((v >> 12) & 0xF) as f64 / 10.0)
+}
+
+fn parse_head(data: &[u8]) -> Option<HeadTable> {
+    Some(HeadTable {
+        version: read_fixed(data, 0)?,
+        font_revision: read_fixed(data, 4)?,
+        checksum_adjustment: read_u32(data, 8)?,
+        magic_number: read_u32(data, 12)?,
+        flags: read_u16(data, 16)?,
+        units_per_em: read_u16(data, 18)?,
+        created: read_i64(data, 20)?,
+        modified: read_i64(data, 28)?,
+        x_min: read_i16(data, 36)?,
+        y_min: read_i16(data, 38)?,
+        x_max: read_i16(data, 40)?,
+        y_max: read_i16(data, 42)?,
+        mac_style: read_u16(data, 44)?,
+        lowest_rec_ppem: read_u16(data, 46)?,
+        font_direction_hint: read_i16(data, 48)?,
+        index_to_loc_format: read_i16(data, 50)?,
+        glyph_data_format: read_i16(data, 52)?,
+    })
+}
+
+fn parse_hhea(data: &[u8]) -> Option<HheaTable> {
+    Some(HheaTable {
+        version: read_version(data, 0)?,
+        ascender: read_i16(data, 4)?,
+        descender: read_i16(data, 6)?,
+        line_gap: read_i16(data, 8)?,
+        advance_width_max: read_u16(data, 10)?,
+        min_left_side_bearing: read_i16(data, 12)?,
+        min_right_side_bearing: read_i16(data, 14)?,
+        x_max_extent: read_i16(data, 16)?,
+        caret_slope_rise: read_i16(data, 18)?,
+        caret_slope_run: read_i16(data, 20)?,
+        caret_offset: read_i16(data, 22)?,
+        metric_data_format: read_i16(data, 32)?,
+        number_of_h_metrics: read_u16(data, 34)?,
+    })
+}
+
+fn parse_os2(data: &[u8]) -> Option<Os2Table> {
+    let version = read_u16(data, 0)?;
+    let since = |min_version: u16, value: Option<u16>| value.filter(|_| version >= min_version);
+
+    Some(Os2Table {
+        version,
+        x_avg_char_width: read_i16(data, 2)?,
+        us_weight_class: read_u16(data, 4)?,
+        us_width_class: read_u16(data, 6)?,
+        fs_type: read_u16(data, 8)?,
+        y_subscript_x_size: read_i16(data, 10)?,
+        y_subscript_y_size: read_i16(data, 12)?,
+        y_subscript_x_offset: read_i16(data, 14)?,
+        y_subscript_y_offset: read_i16(data, 16)?,
+        y_superscript_x_size: read_i16(data, 18)?,
+        y_superscript_y_size: read_i16(data, 20)?,
+        y_superscript_x_offset: read_i16(data, 22)?,
+        y_superscript_y_offset: read_i16(data, 24)?,
+        y_strikeout_size: read_i16(data, 26)?,
+        y_strikeout_position: read_i16(data, 28)?,
+        s_family_class: read_i16(data, 30)?,
+        panose: data.get(32..42)?.to_vec(),
+        ul_unicode_range: (0..4).map(|i| read_u32(data, 42 + i * 4)).collect::<Option<_>>()?,
+        ach_vend_id: String::from_utf8_lossy(data.get(58..62)?).into_owned(),
+        fs_selection: read_u16(data, 62)?,
+        us_first_char_index: read_u16(data, 64)?,
+        us_last_char_index: read_u16(data, 66)?,
+        s_typo_ascender: read_i16(data, 68),
+        s_typo_descender: read_i16(data, 70),
+        s_typo_line_gap: read_i16(data, 72),
+        us_win_ascent: read_u16(data, 74),
+        us_win_descent: read_u16(data, 76),
+        ul_code_page_range: (version >= 1)
+            .then(|| (0..2).map(|i| read_u32(data, 78 + i * 4)).collect::<Option<_>>())
+            .flatten(),
+        sx_height: since(2, read_u16(data, 86)).map(|v| v as i16),
+        s_cap_height: since(2, read_u16(data, 88)).map(|v| v as i16),
+        us_default_char: since(2, read_u16(data, 90)),
+        us_break_char: since(2, read_u16(data, 92)),
+        us_max_context: since(2, read_u16(data, 94)),
+        us_lower_optical_point_size: since(5, read_u16(data, 96)),
+        us_upper_optical_point_size: since(5, read_u16(data, 98)),
+    })
+}
+
+fn parse_post(data: &[u8]) -> Option<PostTable> {
+    Some(PostTable {
+        version: read_version(data, 0)?,
+        italic_angle: read_fixed(data, 4)?,
+        underline_position: read_i16(data, 8)?,
+        underline_thickness: read_i16(data, 10)?,
+        is_fixed_pitch: read_u32(data, 12)?,
+        min_mem_type42: read_u32(data, 16)?,
+        max_mem_type42: read_u32(data, 20)?,
+        min_mem_type1: read_u32(data, 24)?,
+        max_mem_type1: read_u32(data, 28)?,
+    })
+}
+
+fn parse_maxp(data: &[u8]) -> Option<MaxpTable> {
+    let version = read_u32(data, 0)?;
+    let truetype = if version == 0x0001_0000 {
+        let field = |i: usize| read_u16(data, 6 + i * 2);
+        Some(MaxpTrueType {
+            max_points: field(0)?,
+            max_contours: field(1)?,
+            max_composite_points: field(2)?,
+            max_composite_contours: field(3)?,
+            max_zones: field(4)?,
+            max_twilight_points: field(5)?,
+            max_storage: field(6)?,
+            max_function_defs: field(7)?,
+            max_instruction_defs: field(8)?,
+            max_stack_elements: field(9)?,
+            max_size_of_instructions: field(10)?,
+            max_component_elements: field(11)?,
+            max_component_depth: field(12)?,
+        })
+    } else {
+        None
+    };
+
+    Some(MaxpTable {
+        version: read_version(data, 0)?,
+        num_glyphs: read_u16(data, 4)?,
+        truetype,
+    })
+}
+
+fn parse_fvar(face: &Face, data: &[u8]) -> Option<FvarTable> {
+    let axes_offset = read_u16(data, 4)? as usize;
+    let axis_count = read_u16(data, 8)? as usize;
+    let axis_size = read_u16(data, 10)? as usize;
+    let instance_count = read_u16(data, 12)? as usize;
+    let instance_size = read_u16(data, 14)? as usize;
+
+    let mut axes = Vec::with_capacity(axis_count);
+    for i in 0..axis_count {
+        let record = axes_offset + i * axis_size;
+        let name_id = read_u16(data, record + 18)?;
+        axes.push(FvarAxis {
+            tag: String::from_utf8_lossy(data.get(record..record + 4)?).into_owned(),
+            min_value: read_fixed(data, record + 4)?,
+            default_value: read_fixed(data, record + 8)?,
+            max_value: read_fixed(data, record + 12)?,
+            flags: read_u16(data, record + 16)?,
+            name_id,
+            name: FontScanner::extract_name(face, name_id),
+        });
+    }
+
+    // Instance records follow the axes; postScriptNameID is present when the record has room for it
+    let instances_offset = axes_offset + axis_count * axis_size;
+    let mut instances = Vec::with_capacity(instance_count);
+    for i in 0..instance_count {
+        let record = instances_offset + i * instance_size;
+        let subfamily_name_id = read_u16(data, record)?;
+        let coordinates = (0..axis_count)
+            .map(|axis| read_fixed(data, record + 4 + axis * 4))
+            .collect::<Option<_>>()?;
+        let postscript_name_id = (instance_size >= 6 + axis_count * 4)
+            .then(|| read_u16(data, record + 4 + axis_count * 4))
+            .flatten();
+        instances.push(FvarInstance {
+            subfamily_name_id,
+            name: FontScanner::extract_name(face, subfamily_name_id),
+            coordinates,
+            postscript_name_id,
+        });
+    }
+
+    Some(FvarTable { axes, instances })
+}
+
+fn parse_gasp(data: &[u8]) -> Option<GaspTable> {
+    let num_ranges = read_u16(data, 2)? as usize;
+    let ranges = (0..num_ranges)
+        .map(|i| {
+            Some(GaspRange {
+                range_max_ppem: read_u16(data, 4 + i * 4)?,
+                behavior: read_u16(data, 6 + i * 4)?,
+            })
+        })
+        .collect::<Option<_>>()?;
+
+    Some(GaspTable {
+        version: read_u16(data, 0)?,
+        ranges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_version() {
+        assert_eq!(read_version(&[0x00, 0x00, 0x50, 0x00], 0), Some(0.5));
+        assert_eq!(read_version(&[0x00, 0x02, 0x50, 0x00], 0), Some(2.5));
+        assert_eq!(read_version(&[0x00, 0x01, 0x00, 0x00], 0), Some(1.0));
+    }
+
+    #[test]
+    fn test_parse_short_os2() {
+        // Apple's 68-byte version 0 table ends after usLastCharIndex
+        let mut data = vec![0u8; 68];
+        data[4..6].copy_from_slice(&700u16.to_be_bytes());
+        let os2 = parse_os2(&data).unwrap();
+        assert_eq!(os2.us_weight_class, 700);
+        assert_eq!(os2.s_typo_ascender, None);
+        assert_eq!(os2.us_win_descent, None);
+        assert_eq!(os2.ul_code_page_range, None);
+
+        data.resize(78, 0);
+        data[74..76].copy_from_slice(&1900u16.to_be_bytes());
+        assert_eq!(parse_os2(&data).unwrap().us_win_ascent, Some(1900));
+        assert!(parse_os2(&data[..60]).is_none());
+    }
+
+    #[test]
+    fn test_parse_gasp() {
+        let data = [0x00, 0x01, 0x00, 0x02, 0x00, 0x08, 0x00, 0x02, 0xFF, 0xFF, 0x00, 0x0F];
+        let gasp = parse_gasp(&data).unwrap();
+        assert_eq!(gasp.version, 1);
+        assert_eq!(gasp.ranges.len(), 2);
+        assert_eq!(gasp.ranges[1].range_max_ppem, 0xFFFF);
+        assert_eq!(gasp.ranges[1].behavior, 0x0F);
+        // Truncated range array
+        assert!(parse_gasp(&data[..8]).is_none());
+    }
+}
@@ -0,0 +1,56 @@
+//! Vertical writing support: vertical metrics, CFF vertical origins and vert/vrt2 alternates
+use super::raw;
+use serde::{Deserialize, Serialize};
+use ttf_parser::{Face, Tag};
+
+/// Tables and features needed for vertical CJK layout
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VerticalInfo {
+    pub has_vhea: bool,
+    pub has_vmtx: bool,
+    pub has_vorg: bool,        // CFF 字形的垂直原点 (TrueType 轮廓不需要)
+    pub needs_vorg: bool,      // CFF/CFF2 轮廓
+    pub has_vert: bool,        // GSUB vert: 竖排替换 (标点、括号等)
+    pub has_vrt2: bool,        // GSUB vrt2: 竖排替换并旋转
+    pub vertical_ready: bool,  // vhea+vmtx、vert/vrt2 齐全，CFF 字体另需 VORG
+    pub missing: Vec<String>,  // 缺少的表或特性，如 ["vmtx", "vert/vrt2"]
+}
+
+/// Detect vertical metrics, VORG and vertical substitution features
+pub fn detect_vertical_info(face: &Face) -> VerticalInfo {
+    let has = |tag: &[u8; 4]| raw::table(face, tag).is_some();
+    let has_feature = |tag: &[u8; 4]| {
+        face.tables()
+            .gsub
+            .is_some_and(|gsub| gsub.features.into_iter().any(|feature| feature.tag == Tag::from_bytes(tag)))
+    };
+
+    let has_vhea = has(b"vhea");
+    let has_vmtx = has(b"vmtx");
+    let has_vorg = has(b"VORG");
+    let needs_vorg = !has(b"glyf") && (has(b"CFF ") || has(b"CFF2"));
+    let has_vert = has_feature(b"vert");
+    let has_vrt2 = has_feature(b"vrt2");
+
+    let missing: Vec<String> = [
+        (!has_vhea, "vhea"),
+        (!has_vmtx, "vmtx"),
+        (needs_vorg && !has_vorg, "VORG"),
+        (!has_vert && !has_vrt2, "vert/vrt2"),
+    ]
+    .into_iter()
+    .filter(|(missing, _)| *missing)
+    .map(|(_, name)| name.to_string())
+    .collect();
+
+    VerticalInfo {
+        has_vhea,
+        has_vmtx,
+        has_vorg,
+        needs_vorg,
+        has_vert,
+        has_vrt2,
+        vertical_ready: missing.is_empty(),
+        missing,
+    }
+}
@@ -3,15 +3,28 @@
 
 // Use the library crate
 use fontscape::commands::*;
+use fontscape::font::FontCache;
 
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .manage(FontCache::default())
         .invoke_handler(tauri::generate_handler![
             scan_fonts,
             refresh_fonts,
             toggle_font,
-            check_glyphs_in_font
+            check_glyphs_in_font,
+            get_name_table,
+            inspect_font_tables,
+            lint_fonts,
+            get_bitmap_glyphs,
+            get_color_palettes,
+            get_layout_features,
+            set_family_grouping,
+            match_font_face,
+            audit_stylesheet_fonts,
+            get_font_face_css,
+            subset_font
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -4,7 +4,24 @@ use std::path::PathBuf;
 
 // Import the font module from the main crate
 use fontscape::font::scanner::FontScanner;
-use fontscape::font::models::FontFormat;
+use fontscape::font::matching::{match_font, CssFontStyle, FontQuery};
+use fontscape::font::models::{FontFormat, FontInfo};
+use fontscape::font::cjk_style::CjkStyle;
+use fontscape::font::classification::FontCategory;
+use fontscape::font::font_face::generate_font_face_css;
+use fontscape::font::outline::OutlineFormat;
+use fontscape::font::state::{FamilyGrouping, FontState};
+use fontscape::font::style_name::FontSlope;
+use fontscape::font::stylesheet::audit_stylesheet;
+use fontscape::font::subset::{subset_face, SubsetOptions};
+use fontscape::font::tables::inspect_tables;
+
+/// Chinese family name, following the PRC > Singapore > Taiwan fallback chain
+fn chinese_family(font: &FontInfo) -> Option<&str> {
+    ["zh-CN", "zh-SG", "zh-TW"]
+        .iter()
+        .find_map(|tag| font.localized_names.get(*tag)?.family.as_deref())
+}
 
 /// Integration test: Parse the LXGWWenKaiGBScreen font file
 /// This is a Chinese font that should have both English and Chinese localized names
@@ -34,13 +51,13 @@ fn test_font_info() {
 
         // Test localized names
         println!("  English family name: {}", font.family);
-        println!("  Chinese family name: {:?}", font.family_zh);
+        println!("  Chinese family name: {:?}", chinese_family(font));
         println!("  English full name: {}", font.full_name);
-        println!("  Chinese full name: {:?}", font.full_name_zh);
+        println!("  Localized names: {:?}", font.localized_names);
 
         // This font should have Chinese localized names
         assert!(
-            font.family_zh.is_some() || font.family.contains("LXGW"),
+            chinese_family(font).is_some() || font.family.contains("LXGW"),
             "Should have Chinese family name or English family name"
         );
 
@@ -211,12 +228,12 @@ fn test_localized_names() {
         .find(|f| f.family.contains("Microsoft YaHei"))
     {
         println!("  English name: {}", font.family);
-        println!("  Chinese name: {:?}", font.family_zh);
+        println!("  Chinese name: {:?}", chinese_family(font));
 
         // Should have English name
         assert!(!font.family.is_empty(), "Should have English family name");
 
-        if let Some(chinese_name) = &font.family_zh {
+        if let Some(chinese_name) = chinese_family(font) {
             assert!(
                 !chinese_name.is_empty(),
                 "Chinese name should not be empty if present"
@@ -228,13 +245,576 @@ fn test_localized_names() {
     // Check how many fonts have localized Chinese names
     let fonts_with_chinese_names = all_fonts
         .iter()
-        .filter(|f| f.family_zh.is_some())
+        .filter(|f| chinese_family(f).is_some())
         .count();
 
     println!("  Found {} fonts with Chinese localized names", fonts_with_chinese_names);
     println!("✓ Localized name extraction working");
 }
 
+/// Integration test: Japanese and Korean localized names are extracted
+#[test]
+fn test_japanese_korean_localized_names() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\msgothic.ttc");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: msgothic.ttc not found");
+        return;
+    }
+
+    println!("📖 Testing: Japanese localized names");
+
+    let scanner = FontScanner::new();
+    let all_fonts = scanner.scan_all_fonts().unwrap().fonts;
+
+    let gothic = all_fonts
+        .iter()
+        .find(|f| f.path.contains("msgothic.ttc"))
+        .expect("Should find a font from msgothic.ttc");
+
+    let japanese = gothic
+        .localized_names
+        .get("ja-JP")
+        .and_then(|names| names.family.as_deref());
+    println!("  English name: {}", gothic.family);
+    println!("  Japanese name: {:?}", japanese);
+    assert!(japanese.is_some(), "MS Gothic should have a ja-JP family name");
+
+    // Korean fonts, if installed, should expose ko-KR names
+    if let Some(malgun) = all_fonts.iter().find(|f| f.family == "Malgun Gothic") {
+        println!("  Korean names: {:?}", malgun.localized_names.get("ko-KR"));
+        assert!(malgun.localized_names.contains_key("ko-KR"));
+    }
+
+    println!("✓ Japanese/Korean localized names extracted");
+}
+
+/// Integration test: Table directory and parsed table views
+#[test]
+fn test_table_inspector() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\arial.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: arial.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: Table inspector on Arial");
+
+    let tables = inspect_tables(font_path.to_string_lossy().to_string(), 0).unwrap();
+    let tags: Vec<&str> = tables.tables.iter().map(|t| t.tag.as_str()).collect();
+    println!("  Tables: {}", tags.join(", "));
+
+    for tag in ["head", "hhea", "maxp", "OS/2", "post", "cmap", "glyf", "loca"] {
+        assert!(tags.contains(&tag), "Arial should have a {} table", tag);
+    }
+
+    let head = tables.head.expect("head should be parsed");
+    assert_eq!(head.magic_number, 0x5F0F3CF5);
+    assert_eq!(head.units_per_em, 2048);
+    assert_eq!(tables.os2.expect("OS/2 should be parsed").us_weight_class, 400);
+    assert!(tables.maxp.expect("maxp should be parsed").truetype.is_some());
+    assert!(tables.fvar.is_none());
+
+    println!("✓ Table inspector working");
+}
+
+/// Integration test: Outline format and hinting detection
+#[test]
+fn test_rendering_info() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\arial.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: arial.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: Rendering info of Arial");
+
+    let scanner = FontScanner::new();
+    let all_fonts = scanner.scan_all_fonts().unwrap().fonts;
+    let arial = all_fonts
+        .iter()
+        .find(|f| f.path.to_lowercase().ends_with("arial.ttf"))
+        .expect("Should find arial.ttf");
+
+    println!("  Rendering: {:?}", arial.rendering);
+    assert_eq!(arial.rendering.outline_format, OutlineFormat::TrueType);
+    assert!(arial.rendering.hinted, "Arial ships with TrueType instructions");
+    assert!(arial.rendering.has_gasp);
+
+    println!("✓ Rendering info detected");
+}
+
+/// Integration test: Consolas is fixed pitch and Arial is not
+#[test]
+fn test_monospace_detection() {
+    let consolas_path = PathBuf::from(r"C:\Windows\Fonts\consola.ttf");
+
+    if !consolas_path.exists() {
+        println!("⚠️  Skipping test: consola.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: Monospace detection");
+
+    let scanner = FontScanner::new();
+    let all_fonts = scanner.scan_all_fonts().unwrap().fonts;
+    let find = |file: &str| {
+        all_fonts
+            .iter()
+            .find(|f| f.path.to_lowercase().ends_with(file))
+            .unwrap_or_else(|| panic!("Should find {}", file))
+    };
+
+    let consolas = find("consola.ttf");
+    println!("  Consolas: {:?}", consolas.monospace);
+    assert!(consolas.monospace.ascii_fixed_pitch);
+    assert!(consolas.monospace.is_monospace);
+    assert!(consolas.monospace.terminal.box_drawing > 0);
+
+    let arial = find("arial.ttf");
+    assert!(!arial.monospace.is_monospace, "Arial is proportional");
+
+    println!("✓ Monospace detection works");
+}
+
+/// Integration test: MS Gothic ships vertical metrics and vert/vrt2, Arial does not
+#[test]
+fn test_vertical_writing() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\msgothic.ttc");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: msgothic.ttc not found");
+        return;
+    }
+
+    println!("📖 Testing: Vertical writing support");
+
+    let scanner = FontScanner::new();
+    let all_fonts = scanner.scan_all_fonts().unwrap().fonts;
+    let gothic = all_fonts
+        .iter()
+        .find(|f| f.path.contains("msgothic.ttc"))
+        .expect("Should find a font from msgothic.ttc");
+
+    println!("  MS Gothic: {:?}", gothic.vertical);
+    assert!(gothic.vertical.has_vhea && gothic.vertical.has_vmtx);
+    assert!(gothic.vertical.has_vert || gothic.vertical.has_vrt2);
+    assert!(gothic.vertical.vertical_ready, "missing: {:?}", gothic.vertical.missing);
+
+    if let Some(arial) = all_fonts.iter().find(|f| f.path.to_lowercase().ends_with("arial.ttf")) {
+        assert!(!arial.vertical.vertical_ready);
+    }
+
+    println!("✓ Vertical writing support detected");
+}
+
+/// Integration test: Cambria Math carries a MATH table and the math alphanumerics
+#[test]
+fn test_math_detection() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\cambria.ttc");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: cambria.ttc not found");
+        return;
+    }
+
+    println!("📖 Testing: MATH table detection");
+
+    let scanner = FontScanner::new();
+    let all_fonts = scanner.scan_all_fonts().unwrap().fonts;
+    let cambria_math = all_fonts
+        .iter()
+        .find(|f| f.path.contains("cambria.ttc") && f.math.has_math_table)
+        .expect("Cambria Math should have a MATH table");
+
+    println!("  {}: {:?}", cambria_math.family, cambria_math.math.blocks);
+    assert!(!cambria_math.math.constants.is_empty());
+    let axis_height = cambria_math.math.constants.iter().find(|c| c.name == "AxisHeight").unwrap();
+    assert!(axis_height.value > 0);
+    assert!(cambria_math.math.blocks.iter().any(|b| b.name == "Mathematical Alphanumeric Symbols" && b.covered > 0));
+
+    if let Some(arial) = all_fonts.iter().find(|f| f.path.to_lowercase().ends_with("arial.ttf")) {
+        assert!(!arial.math.has_math_table);
+    }
+
+    println!("✓ MATH table detected");
+}
+
+/// Integration test: serif/sans/monospace classification of core Windows fonts
+#[test]
+fn test_font_classification() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\times.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: times.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: Font classification");
+
+    let scanner = FontScanner::new();
+    let all_fonts = scanner.scan_all_fonts().unwrap().fonts;
+    let find = |file: &str| all_fonts.iter().find(|f| f.path.to_lowercase().ends_with(file));
+
+    let times = find("times.ttf").expect("Should find times.ttf");
+    println!("  Times New Roman: {:?}", times.classification);
+    assert_eq!(times.classification.category, FontCategory::Serif);
+    assert!(times.classification.confidence >= 0.5);
+
+    if let Some(arial) = find("arial.ttf") {
+        println!("  Arial: {:?}", arial.classification);
+        assert_eq!(arial.classification.category, FontCategory::SansSerif);
+    }
+    if let Some(consolas) = find("consola.ttf") {
+        assert_eq!(consolas.classification.category, FontCategory::Monospace);
+    }
+
+    let state = FontState::new(all_fonts);
+    let family = state.get_css_font_family("Times New Roman").expect("Times New Roman family");
+    assert_eq!(family.category, FontCategory::Serif);
+
+    println!("✓ Fonts classified");
+}
+
+/// Integration test: CJK styles of the bundled Chinese fonts (YaHei, SimSun, KaiTi, FangSong)
+#[test]
+fn test_cjk_style_classification() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\msyh.ttc");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: msyh.ttc not found");
+        return;
+    }
+
+    println!("📖 Testing: CJK style classification");
+
+    let scanner = FontScanner::new();
+    let all_fonts = scanner.scan_all_fonts().unwrap().fonts;
+    let find = |file: &str| all_fonts.iter().find(|f| f.path.to_lowercase().ends_with(file));
+
+    let yahei = find("msyh.ttc").expect("Should find msyh.ttc");
+    println!("  Microsoft YaHei: {:?}", yahei.cjk_style);
+    assert_eq!(yahei.cjk_style.style, Some(CjkStyle::Hei));
+
+    for (file, style) in [
+        ("simsun.ttc", CjkStyle::Song),
+        ("simkai.ttf", CjkStyle::Kai),
+        ("simfang.ttf", CjkStyle::Fangsong),
+    ] {
+        if let Some(font) = find(file) {
+            println!("  {}: {:?}", font.family, font.cjk_style);
+            assert_eq!(font.cjk_style.style, Some(style), "{}", file);
+        }
+    }
+
+    if let Some(arial) = find("arial.ttf") {
+        assert_eq!(arial.cjk_style.style, None);
+    }
+
+    println!("✓ CJK styles classified");
+}
+
+/// Integration test: weight/width/slope from style names agree with OS/2 for Arial's styles
+#[test]
+fn test_style_attributes() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\arialbi.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: arialbi.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: Style name inference");
+
+    let scanner = FontScanner::new();
+    let all_fonts = scanner.scan_all_fonts().unwrap().fonts;
+    let find = |file: &str| all_fonts.iter().find(|f| f.path.to_lowercase().ends_with(file));
+
+    let bold_italic = find("arialbi.ttf").expect("Should find arialbi.ttf");
+    println!("  Arial Bold Italic: {:?}", bold_italic.style_attributes);
+    assert_eq!(bold_italic.style_attributes.name_weight, Some(700));
+    assert_eq!(bold_italic.style_attributes.slope, FontSlope::Italic);
+    assert!(bold_italic.style_attributes.conflicts.is_empty());
+    assert_eq!(bold_italic.weight, 700);
+
+    if let Some(narrow) = find("arialn.ttf") {
+        assert_eq!(narrow.style_attributes.width, 3);
+    }
+
+    println!("✓ Style attributes inferred");
+}
+
+/// Integration test: Arial's four styles are linked as RIBBI and Regular is the default
+#[test]
+fn test_family_style_linking() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\arialbi.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: arialbi.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: Family style matrix and RIBBI");
+
+    let scanner = FontScanner::new();
+    let all_fonts = scanner.scan_all_fonts().unwrap().fonts;
+    let id_of = |file: &str| {
+        all_fonts
+            .iter()
+            .find(|f| f.path.to_lowercase().ends_with(file))
+            .map(|f| f.id.clone())
+    };
+    let (regular, bold_italic) = (id_of("arial.ttf"), id_of("arialbi.ttf"));
+
+    let state = FontState::new(all_fonts);
+    let arial = state.get_css_font_family("Arial").expect("Arial family");
+    println!("  Arial: weights {:?}, slopes {:?}, {:?}", arial.weights, arial.slopes, arial.ribbi);
+
+    assert_eq!(arial.ribbi.regular, regular);
+    assert_eq!(arial.ribbi.bold_italic, bold_italic);
+    assert_eq!(Some(&arial.default_font_id), regular.as_ref());
+    assert!(arial.weights.contains(&400) && arial.weights.contains(&700));
+
+    println!("✓ Family styles linked");
+}
+
+/// Integration test: Typographic, WWS and legacy (GDI) family grouping
+#[test]
+fn test_family_grouping_modes() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\seguisb.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: seguisb.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: Segoe UI Semibold family grouping");
+
+    let scanner = FontScanner::new();
+    let mut state = scanner.scan_all_fonts().unwrap();
+    let semibold_in = |state: &FontState| {
+        state
+            .css_font_families
+            .iter()
+            .find(|family| {
+                state
+                    .get_fonts_by_css_family(&family.name)
+                    .iter()
+                    .any(|f| f.path.to_lowercase().ends_with("seguisb.ttf"))
+            })
+            .map(|family| family.name.clone())
+    };
+
+    assert_eq!(state.grouping, FamilyGrouping::Typographic);
+    assert_eq!(semibold_in(&state).as_deref(), Some("Segoe UI"));
+
+    state.regroup(FamilyGrouping::Wws);
+    assert_eq!(semibold_in(&state).as_deref(), Some("Segoe UI"));
+
+    // GDI keeps the non-RIBBI weight as its own family
+    state.regroup(FamilyGrouping::Legacy);
+    assert_eq!(semibold_in(&state).as_deref(), Some("Segoe UI Semibold"));
+
+    println!("✓ Family grouping modes");
+}
+
+/// Integration test: CSS Fonts 4 matching with weight fallback and synthesis
+#[test]
+fn test_css_font_matching() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\arialbi.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: arialbi.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: CSS font matching for Arial");
+
+    let state = FontScanner::new().scan_all_fonts().unwrap();
+    let path_of = |family: &str, weight: &str, style: &str| {
+        let query = FontQuery::parse(family, weight, style, "normal").unwrap();
+        let matched = match_font(&state, &query).expect("Arial should match");
+        let font = state.get_font(&matched.font_id).unwrap();
+        println!("  {} {} {} -> {}", family, weight, style, font.full_name);
+        (font.path.to_lowercase(), matched)
+    };
+
+    // 600 looks heavier first and finds Bold Italic without synthesis
+    let (path, matched) = path_of("\"No Such Font\", Arial", "600", "italic");
+    assert!(path.ends_with("arialbi.ttf"));
+    assert_eq!(matched.style, CssFontStyle::Italic);
+    assert!(!matched.synthetic_bold && !matched.synthetic_italic);
+
+    // 300 looks lighter first, then falls back to Regular
+    let (path, _) = path_of("arial", "300", "normal");
+    assert!(path.ends_with("arial.ttf"));
+
+    println!("✓ CSS font matching");
+}
+
+/// Integration test: font stacks of a stylesheet resolved against installed fonts
+#[test]
+fn test_stylesheet_audit() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\arialbd.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: arialbd.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: Stylesheet font audit");
+
+    let css_path = std::env::temp_dir().join("fontscape_audit_test.css");
+    std::fs::write(
+        &css_path,
+        "body { font-family: \"Not Installed\", Arial, sans-serif; }\nh1 { font-weight: bold; }\n",
+    )
+    .unwrap();
+
+    let state = FontScanner::new().scan_all_fonts().unwrap();
+    let audit = audit_stylesheet(&state, css_path.to_str().unwrap(), "Aa永").unwrap();
+    std::fs::remove_file(&css_path).ok();
+
+    assert_eq!(audit.stacks.len(), 1);
+    let stack = &audit.stacks[0];
+    assert_eq!(stack.missing, vec!["Not Installed"]);
+
+    let bold = stack.contexts.iter().find(|c| c.context.weight == "bold").expect("h1 context");
+    let matched = bold.matched.as_ref().expect("Arial Bold");
+    let font = state.get_font(&matched.font_id).unwrap();
+    println!("  bold -> {}, fallbacks {:?}", font.full_name, bold.fallbacks);
+    assert!(font.path.to_lowercase().ends_with("arialbd.ttf"));
+    // Arial has no ideographs and nothing later in the stack is installed
+    assert!(bold.fallbacks.iter().any(|f| f.character == '永' && f.family.is_none()));
+
+    println!("✓ Stylesheet audit");
+}
+
+/// Integration test: @font-face rules for every member of a family
+#[test]
+fn test_font_face_css_generation() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\arialbi.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: arialbi.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: @font-face generation for Arial");
+
+    let state = FontScanner::new().scan_all_fonts().unwrap();
+    let css = generate_font_face_css(&state, "Arial").unwrap();
+    println!("{}", css.lines().take(16).collect::<Vec<_>>().join("\n"));
+
+    assert!(css.matches("@font-face").count() >= 4);
+    assert!(css.contains("local(\"Arial Bold Italic\")"));
+    assert!(css.contains("url(\"arialbi.ttf\") format(\"truetype\")"));
+    assert!(css.contains("font-weight: 700;") && css.contains("font-style: italic;"));
+    assert!(css.contains("unicode-range: U+20-"));
+    assert!(css.contains("ascent-override:"));
+    assert!(generate_font_face_css(&state, "No Such Family").is_err());
+
+    println!("✓ @font-face generated");
+}
+
+/// Integration test: Subset a CJK collection face for web delivery
+#[test]
+fn test_font_subsetting() {
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\msyh.ttc");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: msyh.ttc not found");
+        return;
+    }
+
+    println!("📖 Testing: Subsetting Microsoft YaHei");
+
+    let data = std::fs::read(&font_path).unwrap();
+    let options = SubsetOptions {
+        text: "永和九年，岁在癸丑 fi".to_string(),
+        unicode_ranges: "U+20-7E".to_string(),
+        drop_hinting: true,
+        drop_unused_tables: true,
+    };
+    let subset = subset_face(&data, 0, &options).unwrap();
+    println!("  {:?}", subset.report);
+
+    // A standalone face that maps the same glyphs, far smaller than the collection
+    let original = ttf_parser::Face::parse(&data, 0).unwrap();
+    let face = ttf_parser::Face::parse(&subset.data, 0).unwrap();
+    for c in options.text.chars().chain('A'..='Z') {
+        assert_eq!(face.glyph_index(c), original.glyph_index(c), "{}", c);
+    }
+    assert_eq!(face.number_of_glyphs(), original.number_of_glyphs());
+    assert_eq!(face.glyph_index('岳'), None);
+    assert!(subset.report.glyph_count >= subset.report.codepoint_count);
+    assert!(subset.report.subset_size * 10 < subset.report.original_size);
+    assert!(subset.report.dropped_tables.iter().any(|tag| tag == "fpgm"));
+
+    let nothing = SubsetOptions { text: String::new(), unicode_ranges: String::new(), ..options };
+    assert!(subset_face(&data, 0, &nothing).is_err());
+
+    println!("✓ Subset font generated");
+}
+
+/// Integration test: Subsetting a COLR font keeps the outlines its color glyphs paint
+#[test]
+fn test_color_font_subsetting() {
+    use ttf_parser::colr::{ClipBox, CompositeMode, Paint, Painter};
+    use ttf_parser::{GlyphId, RgbaColor, Transform};
+
+    #[derive(Default)]
+    struct Outlines(Vec<GlyphId>);
+    impl<'a> Painter<'a> for Outlines {
+        fn outline_glyph(&mut self, glyph_id: GlyphId) {
+            self.0.push(glyph_id);
+        }
+        fn paint(&mut self, _: Paint<'a>) {}
+        fn push_clip(&mut self) {}
+        fn push_clip_box(&mut self, _: ClipBox) {}
+        fn pop_clip(&mut self) {}
+        fn push_layer(&mut self, _: CompositeMode) {}
+        fn pop_layer(&mut self) {}
+        fn push_transform(&mut self, _: Transform) {}
+        fn pop_transform(&mut self) {}
+    }
+
+    let font_path = PathBuf::from(r"C:\Windows\Fonts\seguiemj.ttf");
+
+    if !font_path.exists() {
+        println!("⚠️  Skipping test: seguiemj.ttf not found");
+        return;
+    }
+
+    println!("📖 Testing: Subsetting Segoe UI Emoji");
+
+    let data = std::fs::read(&font_path).unwrap();
+    let options = SubsetOptions {
+        text: "😀🎉".to_string(),
+        unicode_ranges: String::new(),
+        drop_hinting: true,
+        drop_unused_tables: true,
+    };
+    let subset = subset_face(&data, 0, &options).unwrap();
+    println!("  {:?}", subset.report);
+
+    let original = ttf_parser::Face::parse(&data, 0).unwrap();
+    let face = ttf_parser::Face::parse(&subset.data, 0).unwrap();
+    for c in options.text.chars() {
+        let glyph = original.glyph_index(c).unwrap();
+        let mut outlines = Outlines::default();
+        original.paint_color_glyph(glyph, 0, RgbaColor::new(0, 0, 0, 255), &mut outlines);
+        assert!(!outlines.0.is_empty(), "{} is not a color glyph", c);
+        for layer in outlines.0 {
+            assert_eq!(face.glyph_bounding_box(layer), original.glyph_bounding_box(layer), "{} layer {}", c, layer.0);
+        }
+    }
+
+    println!("✓ Color glyph layers kept");
+}
+
 /// Integration test: Full scan performance and statistics
 #[test]
 fn test_full_scan_statistics() {
@@ -266,7 +846,7 @@ fn test_full_scan_statistics() {
     println!("  Fonts supporting English: {}", english_fonts);
 
     // Count with localized names
-    let with_chinese_names = fonts.iter().filter(|f| f.family_zh.is_some()).count();
+    let with_chinese_names = fonts.iter().filter(|f| chinese_family(f).is_some()).count();
     println!("  Fonts with Chinese localized names: {}", with_chinese_names);
 
     assert!(fonts.len() > 0, "Should find at least some fonts");
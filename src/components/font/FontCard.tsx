@@ -1,10 +1,11 @@
+import { SubsetFontModal } from "@/components/font/SubsetFontModal";
 import { ContextMenu } from "@/components/ui/ContextMenu";
-import { getFontWeightName } from "@/lib/font";
-import { checkGlyphsInFont, toggleFont } from "@/lib/tauri-api";
+import { getDisplayName, getFontWeightName } from "@/lib/font";
+import { checkGlyphsInFont, getFontFaceCss, toggleFont } from "@/lib/tauri-api";
 import { useFontStore } from "@/store/fontStore";
 import { useUIStore } from "@/store/uiStore";
 import { CssFontFamily, FontInfo } from "@/types/font";
-import { Ban, Check, CheckSquare, Info } from "lucide-react";
+import { Ban, Check, CheckSquare, Code, Info, Scissors } from "lucide-react";
 import { useEffect, useState } from "react";
 
 interface FontCardProps {
@@ -15,6 +16,7 @@ interface FontCardProps {
 export function FontCard({ fontFamily, onShowInfo }: FontCardProps) {
   const [contextMenu, setContextMenu] = useState<{ x: number; y: number } | null>(null);
   const [isToggling, setIsToggling] = useState(false);
+  const [showSubset, setShowSubset] = useState(false);
   const [displayText, setdisplayText] = useState("");
   const { getFontById } = useFontStore();
   const [font, _] = useState(getFontById(fontFamily.default_font_id))
@@ -29,7 +31,7 @@ export function FontCard({ fontFamily, onShowInfo }: FontCardProps) {
 
   // Get display name based on locale
   const store = useUIStore()
-  const displayName = (store.language === 'zh-CN' && font.family_zh) ? font.family_zh : font.family;
+  const displayName = getDisplayName(font, store.localeOrder);
 
 
   // 检查 font 是否包含预览文本的字形，没有的用方框替代，以避免 css fallback 问题
@@ -68,6 +70,15 @@ export function FontCard({ fontFamily, onShowInfo }: FontCardProps) {
     }
   }
 
+  // 复制整个家族的 @font-face 规则
+  async function handleCopyFontFace() {
+    try {
+      await navigator.clipboard.writeText(await getFontFaceCss(fontFamily.name));
+    } catch (error) {
+      console.error("Failed to copy @font-face CSS:", error);
+    }
+  }
+
   // 上下文菜单项
   const menuItems = [
     {
@@ -77,6 +88,16 @@ export function FontCard({ fontFamily, onShowInfo }: FontCardProps) {
         onShowInfo(font);
       },
     },
+    {
+      label: "Copy @font-face CSS",
+      icon: <Code className="w-4 h-4" />,
+      onClick: handleCopyFontFace,
+    },
+    {
+      label: "Subset for Web",
+      icon: <Scissors className="w-4 h-4" />,
+      onClick: () => setShowSubset(true),
+    },
     {
       label: "Multi-select",
       icon: <CheckSquare className="w-4 h-4" />,
@@ -137,6 +158,13 @@ export function FontCard({ fontFamily, onShowInfo }: FontCardProps) {
               }`}
             title={font.status}
           />
+          {/* 完整性问题指示器 */}
+          {font.health.worst && font.health.worst !== 'Info' && (
+            <div
+              className={`h-2 w-2 rounded-full ${font.health.worst === 'Error' ? 'bg-red-500' : 'bg-yellow-500'}`}
+              title={`${font.health.issues.length} integrity issue(s)`}
+            />
+          )}
         </div>
 
         {/* 字体预览 - 使用 preview text or display name */}
@@ -167,13 +195,19 @@ export function FontCard({ fontFamily, onShowInfo }: FontCardProps) {
               </div>}
             {/* 字体族 */}
             {fontFamily.font_count > 1 &&
-              <span className="shrink-0 rounded-full px-2 py-0.5 bg-gray-300 text-white text-xs">
+              <span
+                className="shrink-0 rounded-full px-2 py-0.5 bg-gray-300 text-white text-xs"
+                title={fontFamily.styles.map((s) => s.style).join(", ")}
+              >
                 F {fontFamily.font_count}
               </span>
             }
           </div>
           <p className="text-sm text-muted-foreground">
-            {getFontWeightName(font.weight)} • {font.format}
+            {fontFamily.weights.length > 1
+              ? `${getFontWeightName(fontFamily.weights[0])}–${getFontWeightName(fontFamily.weights[fontFamily.weights.length - 1])}`
+              : getFontWeightName(font.weight)}
+            {fontFamily.slopes.length > 1 && " + Italic"} • {font.format}
           </p>
           <div className="flex flex-wrap gap-1 mt-2">
             {font.languages.slice(0, 3).map((lang) => (
@@ -197,6 +231,9 @@ export function FontCard({ fontFamily, onShowInfo }: FontCardProps) {
           items={menuItems}
         />
       )}
+
+      {/* 子集化对话框 */}
+      {showSubset && <SubsetFontModal font={font} onClose={() => setShowSubset(false)} />}
     </>
   );
 }
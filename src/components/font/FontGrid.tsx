@@ -1,3 +1,4 @@
+import { hasColorTrait, hasLayoutFeature, hasRenderingTrait, hasTerminalTrait } from "@/lib/font";
 import { useFontStore } from "@/store/fontStore";
 import { useUIStore } from "@/store/uiStore";
 import { FontInfo, FontState } from "@/types/font";
@@ -45,6 +46,13 @@ export function FontGrid({ fontState }: FontGridProps) {
     const filters = uiStore.filters ?? {};
     const langFilters = Array.isArray(filters.languages) ? filters.languages : [];
     const searchText = filters.searchText?.toLowerCase().trim() || '';
+    const lintRules = filters.lintRules ?? [];
+    const renderingTraits = filters.rendering ?? [];
+    const colorTraits = filters.color ?? [];
+    const terminalTraits = filters.terminal ?? [];
+    const featureTags = filters.features ?? [];
+    const categories = filters.categories ?? [];
+    const cjkStyles = filters.cjkStyles ?? [];
     // const tagFilters = Array.isArray(filters.tags) ? filters.tags : [];
 
     return fontState.css_font_families.filter((fm) => {
@@ -63,18 +71,63 @@ export function FontGrid({ fontState }: FontGridProps) {
       //   return false;
       // }
 
-      // Search filter: check if searchText is included in family, full_name, family_zh, or full_name_zh
+      // Lint filter: any font of the family has one of the selected warnings
+      if (lintRules.length > 0) {
+        const hasWarning = fontStore.getFontsByCssFamily(fm.name).some((f) =>
+          f.lint_warnings.some((w) => lintRules.includes(w.rule))
+        );
+        if (!hasWarning) {
+          return false;
+        }
+      }
+
+      // Category filter: the family's aggregated category
+      if (categories.length > 0 && !categories.includes(fm.category)) {
+        return false;
+      }
+
+      // CJK style filter: the default font has one of the selected styles
+      if (cjkStyles.length > 0 && !(font.cjk_style.style && cjkStyles.includes(font.cjk_style.style))) {
+        return false;
+      }
+
+      // Rendering filter: the default font must have every selected trait
+      if (!renderingTraits.every((t) => hasRenderingTrait(font, t))) {
+        return false;
+      }
+
+      // Terminal filter: the default font must have every selected trait
+      if (!terminalTraits.every((t) => hasTerminalTrait(font, t))) {
+        return false;
+      }
+
+      // Feature filter: the default font must have every selected OpenType feature
+      if (!featureTags.every((tag) => hasLayoutFeature(font, tag))) {
+        return false;
+      }
+
+      // Color filter: any font of the family qualifies, emoji fonts rarely set a default style
+      if (colorTraits.length > 0) {
+        const hasColor = fontStore.getFontsByCssFamily(fm.name).some((f) =>
+          colorTraits.every((t) => hasColorTrait(f, t))
+        );
+        if (!hasColor) {
+          return false;
+        }
+      }
+
+      // Search filter: check if searchText is included in family, full_name, or any localized name
       if (searchText) {
         const family = font.family?.toLowerCase() || '';
         const fullName = font.full_name?.toLowerCase() || '';
-        const familyZh = font.family_zh?.toLowerCase() || '';
-        const fullNameZh = font.full_name_zh?.toLowerCase() || '';
+        const localizedNames = Object.values(font.localized_names ?? {}).flatMap((names) =>
+          [names.family, names.full_name, names.typographic_family]
+        );
 
         const matchesSearch =
           family.includes(searchText) ||
           fullName.includes(searchText) ||
-          familyZh.includes(searchText) ||
-          fullNameZh.includes(searchText);
+          localizedNames.some((name) => name?.toLowerCase().includes(searchText));
 
         if (!matchesSearch) {
           return false;
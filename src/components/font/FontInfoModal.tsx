@@ -1,8 +1,11 @@
-import { formatFontWeight } from "@/lib/font";
+import { formatFontWeight, formatWidthClass, getDisplayName } from "@/lib/font";
+import { getBitmapGlyphs, getColorPalettes, getLayoutFeatures, getNameTable, inspectFontTables, matchFontFace } from "@/lib/tauri-api";
 import { formatDate, formatFileSize } from "@/lib/utils";
+import { useFontStore } from "@/store/fontStore";
 import { useUIStore } from "@/store/uiStore";
-import { FontInfo } from "@/types/font";
+import { BitmapGlyph, BitmapGlyphResult, CjkStyle, ColorPalette, CssFontStyle, FontCategory, FontInfo, FontMatch, FontTables, LayoutFeatures, NameRecord } from "@/types/font";
 import { X } from "lucide-react";
+import { useEffect, useState } from "react";
 
 interface FontInfoModalProps {
   font: FontInfo;
@@ -11,9 +14,24 @@ interface FontInfoModalProps {
 
 export function FontInfoModal({ font, onClose }: FontInfoModalProps) {
   const store = useUIStore()
-  const displayName = (store.language === 'zh-CN' && font.family_zh) ? font.family_zh : font.family;
+  const displayName = getDisplayName(font, store.localeOrder);
+  const [nameTable, setNameTable] = useState<NameRecord[] | null>(null);
+  const [nameTableError, setNameTableError] = useState<string | null>(null);
+  const [fontTables, setFontTables] = useState<FontTables | null>(null);
+  const [fontTablesError, setFontTablesError] = useState<string | null>(null);
+  const [layoutFeatures, setLayoutFeatures] = useState<LayoutFeatures | null>(null);
+  const [layoutFeaturesError, setLayoutFeaturesError] = useState<string | null>(null);
   console.debug("%% FontInfoModal render for font:", font);
 
+  const loadNameTable = async () => {
+    try {
+      setNameTableError(null);
+      setNameTable(await getNameTable(font.id));
+    } catch (error) {
+      setNameTableError(String(error));
+    }
+  };
+
   // Metadata entries to display
   const metadataEntries = [
     { label: "Copyright", value: font.metadata.copyright, id: 0 },
@@ -36,8 +54,32 @@ export function FontInfoModal({ font, onClose }: FontInfoModalProps) {
     { label: "Compatible Full", value: font.metadata.compatible_full, id: 18 },
     { label: "Sample Text", value: font.metadata.sample_text, id: 19 },
     { label: "PostScript CID", value: font.metadata.postscript_cid, id: 20 },
+    { label: "WWS Family", value: font.metadata.wws_family, id: 21 },
+    { label: "WWS Subfamily", value: font.metadata.wws_subfamily, id: 22 },
   ].filter(entry => entry.value); // Only show entries with values
 
+  const loadLayoutFeatures = async () => {
+    try {
+      setLayoutFeaturesError(null);
+      setLayoutFeatures(await getLayoutFeatures(font.id));
+    } catch (error) {
+      setLayoutFeaturesError(String(error));
+    }
+  };
+
+  const loadFontTables = async () => {
+    try {
+      setFontTablesError(null);
+      setFontTables(await inspectFontTables(font.id));
+    } catch (error) {
+      setFontTablesError(String(error));
+    }
+  };
+
+  // Raw value of a name ID when normalization changed the displayed record
+  const rawValueFor = (nameId: number, value?: string) =>
+    font.name_repairs?.find((repair) => repair.name_id === nameId && repair.value === value)?.raw;
+
   return (
     <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50" onClick={onClose}>
       <div className="bg-card border border-border rounded-lg shadow-xl w-full max-w-3xl max-h-[90vh] flex flex-col" onClick={(e) => { e.stopPropagation(); }}>
@@ -86,17 +128,146 @@ export function FontInfoModal({ font, onClose }: FontInfoModalProps) {
             <dl className="grid grid-cols-1 gap-3">
               <InfoRow label="CSS Font Family" value={font.css_font_family} />
               <InfoRow label="Weight" value={formatFontWeight(font.weight)} />
+              <InfoRow label="Width" value={formatWidthClass(font.style_attributes.width)} />
+              <InfoRow label="Slope" value={font.style_attributes.slope} />
+              {font.style_attributes.conflicts.length > 0 && (
+                <InfoRow
+                  label="Style Conflicts"
+                  value={font.style_attributes.conflicts.join(" • ")}
+                />
+              )}
               <InfoRow label="File Path" value={font.path} />
               <InfoRow label="Format" value={font.format} />
               <InfoRow label="File Size" value={formatFileSize(font.fileSize)} />
               <InfoRow label="Status" value={font.status} />
               <InfoRow label="Variable Font" value={font.is_variable ? "Yes" : "No"} />
+              <InfoRow
+                label="Category"
+                value={font.classification.category === "Unknown"
+                  ? "Unknown"
+                  : `${CATEGORY_NAMES[font.classification.category]} (${Math.round(font.classification.confidence * 100)}% • ${font.classification.signals.join("; ")})`}
+              />
+              {font.cjk_style.style && (
+                <InfoRow
+                  label="CJK Style"
+                  value={`${CJK_STYLE_NAMES[font.cjk_style.style]} (${Math.round(font.cjk_style.confidence * 100)}% • ${font.cjk_style.signals.join("; ")})`}
+                />
+              )}
+              <InfoRow label="Outlines" value={font.rendering.outline_format} />
+              <InfoRow
+                label="Hinting"
+                value={[
+                  font.rendering.has_fpgm && "fpgm",
+                  font.rendering.has_prep && "prep",
+                  font.rendering.has_cvt && "cvt",
+                  font.rendering.autohinted && "ttfautohint",
+                  font.rendering.has_gasp && "gasp",
+                ].filter(Boolean).join(", ") || "None"}
+              />
+              <InfoRow label="Embedded Bitmaps" value={font.rendering.has_embedded_bitmaps ? "Yes" : "No"} />
+              <InfoRow
+                label="Vertical Writing"
+                value={font.vertical.vertical_ready
+                  ? ["Ready", font.vertical.has_vert && "vert", font.vertical.has_vrt2 && "vrt2", font.vertical.has_vorg && "VORG"].filter(Boolean).join(" • ")
+                  : `Missing ${font.vertical.missing.join(", ")}`}
+              />
+              <InfoRow
+                label="Math"
+                value={[
+                  font.math.has_math_table ? "MATH table" : "No MATH table",
+                  ...font.math.blocks.filter(b => b.covered > 0).map(b => `${b.name} ${b.covered}/${b.total}`),
+                ].join(" • ")}
+              />
+              {font.math.constants.length > 0 && (
+                <InfoRow
+                  label="Math Constants"
+                  value={font.math.constants
+                    .filter(c => MATH_SUMMARY_CONSTANTS.includes(c.name))
+                    .map(c => `${c.name} ${c.value}`)
+                    .join(" • ")}
+                />
+              )}
+              <InfoRow
+                label="Monospace"
+                value={[
+                  font.monospace.is_monospace ? "Yes" : "No",
+                  font.monospace.advance_width !== undefined && `advance ${font.monospace.advance_width}`,
+                  font.monospace.post_fixed_pitch !== font.monospace.ascii_fixed_pitch &&
+                    `post.isFixedPitch ${font.monospace.post_fixed_pitch ? "set" : "not set"}`,
+                ].filter(Boolean).join(" • ")}
+              />
+              {font.ligatures.has_programming_ligatures && (
+                <InfoRow label="Coding Ligatures" value={font.ligatures.sequences.join("  ")} />
+              )}
+              <InfoRow
+                label="Terminal"
+                value={[
+                  `box drawing ${font.monospace.terminal.box_drawing}/128`,
+                  `blocks ${font.monospace.terminal.block_elements}/32`,
+                  font.monospace.terminal.powerline && "Powerline",
+                  font.monospace.terminal.is_nerd_font && `Nerd Font (${font.monospace.terminal.nerd_font_icons} icons)`,
+                ].filter(Boolean).join(" • ")}
+              />
               <InfoRow label="Languages" value={font.languages.join(", ")} />
               <InfoRow label="Scripts" value={font.scripts.join(", ")} />
               <InfoRow label="Added" value={formatDate(font.created_at)} />
             </dl>
           </section>
 
+          {/* OS/2 Coverage Mismatches */}
+          {font.os2_coverage && font.os2_coverage.mismatches.length > 0 && (
+            <section>
+              <h3 className="text-lg font-semibold mb-3">OS/2 Coverage Mismatches</h3>
+              <dl className="grid grid-cols-1 gap-3">
+                {font.os2_coverage.mismatches.map((m) => (
+                  <InfoRow
+                    key={`${m.source}-${m.bit}`}
+                    label={`${m.name} (${m.source === 'UnicodeRange' ? 'Range' : 'Code Page'} bit ${m.bit})`}
+                    value={`${m.kind === 'DeclaredButMissing' ? 'Declared but missing' : 'Present but undeclared'} • ${m.covered}/${m.total}`}
+                  />
+                ))}
+              </dl>
+            </section>
+          )}
+
+          {/* Integrity */}
+          {font.health.issues.length > 0 && (
+            <section>
+              <h3 className="text-lg font-semibold mb-3">Integrity ({font.health.worst})</h3>
+              <dl className="grid grid-cols-1 gap-3">
+                {font.health.issues.map((issue, index) => (
+                  <InfoRow
+                    key={index}
+                    label={`${issue.severity} • ${issue.check}${issue.table ? ` (${issue.table})` : ''}`}
+                    value={issue.message}
+                  />
+                ))}
+              </dl>
+            </section>
+          )}
+
+          {/* Embedded Bitmaps */}
+          {font.bitmap_strikes.length > 0 && <BitmapStrikes font={font} />}
+
+          {/* Color */}
+          {font.color.technologies.length > 0 && <ColorSection font={font} />}
+
+          {/* Metadata Warnings */}
+          {font.lint_warnings.length > 0 && (
+            <section>
+              <h3 className="text-lg font-semibold mb-3">Metadata Warnings</h3>
+              <dl className="grid grid-cols-1 gap-3">
+                {font.lint_warnings.map((warning, index) => (
+                  <InfoRow
+                    key={index}
+                    label={`${warning.severity} • ${warning.rule}`}
+                    value={warning.message}
+                  />
+                ))}
+              </dl>
+            </section>
+          )}
+
           {/* Font Metadata (Name IDs) */}
           {metadataEntries.length > 0 && (
             <section>
@@ -107,12 +278,161 @@ export function FontInfoModal({ font, onClose }: FontInfoModalProps) {
                     key={entry.id}
                     label={`${entry.label} (ID ${entry.id})`}
                     value={entry.value || ""}
+                    raw={rawValueFor(entry.id, entry.value)}
                     isUrl={entry.isUrl}
                   />
                 ))}
               </dl>
             </section>
           )}
+
+          {/* Raw Name Table */}
+          <section>
+            <div className="flex items-center justify-between mb-3">
+              <h3 className="text-lg font-semibold">Raw Name Table</h3>
+              {nameTable === null && (
+                <button
+                  onClick={loadNameTable}
+                  className="px-3 py-1 text-sm border border-border rounded-lg hover:bg-muted transition-colors"
+                >
+                  Load
+                </button>
+              )}
+            </div>
+            {nameTableError && <p className="text-sm text-destructive">{nameTableError}</p>}
+            {nameTable && (
+              <table className="w-full text-xs">
+                <thead className="text-muted-foreground text-left">
+                  <tr>
+                    <th className="pr-2">Platform</th>
+                    <th className="pr-2">Encoding</th>
+                    <th className="pr-2">Language</th>
+                    <th className="pr-2">Name ID</th>
+                    <th>Value</th>
+                  </tr>
+                </thead>
+                <tbody>
+                  {nameTable.map((record, index) => (
+                    <tr key={index} className="align-top border-t border-border">
+                      <td className="pr-2">{record.platform_id}</td>
+                      <td className="pr-2">{record.encoding_id}</td>
+                      <td className="pr-2">
+                        {record.language ?? `0x${record.language_id.toString(16).padStart(4, "0")}`}
+                      </td>
+                      <td className="pr-2">{record.name_id}</td>
+                      <td className="break-all">
+                        {record.value ?? <span className="font-mono text-muted-foreground">{record.raw_hex}</span>}
+                      </td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            )}
+          </section>
+
+          {/* OpenType Features */}
+          <section>
+            <div className="flex items-center justify-between mb-3">
+              <h3 className="text-lg font-semibold">OpenType Features ({font.features.length})</h3>
+              {layoutFeatures === null && font.features.length > 0 && (
+                <button
+                  onClick={loadLayoutFeatures}
+                  className="px-3 py-1 text-sm border border-border rounded-lg hover:bg-muted transition-colors"
+                >
+                  Load
+                </button>
+              )}
+            </div>
+            {layoutFeaturesError && <p className="text-sm text-destructive">{layoutFeaturesError}</p>}
+            {layoutFeatures === null && font.features.length > 0 && (
+              <p className="text-sm font-mono">{font.features.join(" ")}</p>
+            )}
+            {layoutFeatures && (
+              <div className="space-y-3">
+                <dl className="grid grid-cols-1 gap-3">
+                  {layoutFeatures.features.map((feature) => (
+                    <InfoRow
+                      key={feature.tag}
+                      label={`${feature.tag} (${feature.tables.join("/").toUpperCase()})`}
+                      value={[feature.ui_name, feature.name].filter(Boolean).join(" • ") || "Unregistered"}
+                    />
+                  ))}
+                </dl>
+                <table className="w-full text-xs">
+                  <thead className="text-muted-foreground text-left">
+                    <tr>
+                      <th className="pr-2">Script</th>
+                      <th className="pr-2">Language</th>
+                      <th className="pr-2">GSUB</th>
+                      <th>GPOS</th>
+                    </tr>
+                  </thead>
+                  <tbody className="font-mono">
+                    {layoutFeatures.language_systems.map((system) => (
+                      <tr key={`${system.script}-${system.language}`} className="align-top border-t border-border">
+                        <td className="pr-2">{system.script}</td>
+                        <td className="pr-2">{system.language}</td>
+                        <td className="pr-2 break-words">{system.gsub.join(" ")}</td>
+                        <td className="break-words">{system.gpos.join(" ")}</td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+              </div>
+            )}
+          </section>
+
+          <FontMatching font={font} />
+
+          {/* Table Inspector */}
+          <section>
+            <div className="flex items-center justify-between mb-3">
+              <h3 className="text-lg font-semibold">Tables</h3>
+              {fontTables === null && (
+                <button
+                  onClick={loadFontTables}
+                  className="px-3 py-1 text-sm border border-border rounded-lg hover:bg-muted transition-colors"
+                >
+                  Load
+                </button>
+              )}
+            </div>
+            {fontTablesError && <p className="text-sm text-destructive">{fontTablesError}</p>}
+            {fontTables && (
+              <div className="space-y-3">
+                <table className="w-full text-xs font-mono">
+                  <thead className="text-muted-foreground text-left font-sans">
+                    <tr>
+                      <th className="pr-2">Tag</th>
+                      <th className="pr-2">Offset</th>
+                      <th className="pr-2">Length</th>
+                      <th>Checksum</th>
+                    </tr>
+                  </thead>
+                  <tbody>
+                    {fontTables.tables.map((table) => (
+                      <tr key={table.tag} className="border-t border-border">
+                        <td className="pr-2">{table.tag}</td>
+                        <td className="pr-2">{table.offset}</td>
+                        <td className="pr-2">{table.length}</td>
+                        <td>0x{table.checksum.toString(16).toUpperCase().padStart(8, "0")}</td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+                {(["head", "hhea", "os2", "post", "maxp", "fvar", "gasp"] as const).map((key) =>
+                  fontTables[key] && (
+                    <details key={key}>
+                      <summary className="cursor-pointer font-medium">{key === "os2" ? "OS/2" : key}</summary>
+                      <pre className="mt-2 p-3 bg-muted/30 rounded-lg text-xs overflow-x-auto">
+                        {JSON.stringify(fontTables[key], null, 2)}
+                      </pre>
+                    </details>
+                  )
+                )}
+              </div>
+            )}
+          </section>
         </div>
 
         {/* Footer */}
@@ -129,13 +449,219 @@ export function FontInfoModal({ font, onClose }: FontInfoModalProps) {
   );
 }
 
+/// Strike list plus the preview text rendered from the bitmap strike at a chosen ppem
+const CATEGORY_NAMES: Record<FontCategory, string> = {
+  SansSerif: "Sans Serif",
+  Serif: "Serif",
+  Script: "Handwriting / Script",
+  Display: "Display",
+  Monospace: "Monospace",
+  Unknown: "Unknown",
+};
+
+const CJK_STYLE_NAMES: Record<CjkStyle, string> = {
+  Song: "宋体 / Ming",
+  Hei: "黑体 / Gothic",
+  Kai: "楷体",
+  Fangsong: "仿宋",
+  Yuan: "圆体 / Rounded",
+};
+
+// MATH constants that give a quick impression of the font's math metrics
+const MATH_SUMMARY_CONSTANTS = ["AxisHeight", "FractionRuleThickness", "ScriptPercentScaleDown", "ScriptScriptPercentScaleDown"];
+
+function BitmapStrikes({ font }: { font: FontInfo }) {
+  const store = useUIStore();
+  const sizes = [...new Set(font.bitmap_strikes.map((s) => s.ppem_y))].sort((a, b) => a - b);
+  const [ppem, setPpem] = useState(sizes[0]);
+  const [glyphs, setGlyphs] = useState<BitmapGlyphResult[]>([]);
+  const text = store.previewText.trim() || font.family;
+
+  useEffect(() => {
+    getBitmapGlyphs(font.id, text, ppem).then(setGlyphs).catch((error) => {
+      console.error("Failed to load bitmap glyphs:", error);
+      setGlyphs([]);
+    });
+  }, [font.id, text, ppem]);
+
+  return (
+    <section>
+      <h3 className="text-lg font-semibold mb-3">Embedded Bitmaps</h3>
+      <dl className="grid grid-cols-1 gap-3">
+        {font.bitmap_strikes.map((strike, index) => (
+          <InfoRow
+            key={index}
+            label={`${strike.source} ${strike.ppem_x}×${strike.ppem_y} ppem`}
+            value={[
+              strike.bit_depth !== undefined && `${strike.bit_depth}-bit`,
+              strike.formats.length > 0 && `format ${strike.formats.join(", ")}`,
+              strike.first_glyph !== undefined && `glyphs ${strike.first_glyph}-${strike.last_glyph}`,
+            ].filter(Boolean).join(" • ")}
+          />
+        ))}
+      </dl>
+      <div className="mt-3 flex items-center gap-2 text-sm">
+        <span className="text-muted-foreground">Preview at</span>
+        <select
+          value={ppem}
+          onChange={(e) => setPpem(Number(e.target.value))}
+          className="px-2 py-1 border border-border rounded-lg bg-background"
+        >
+          {sizes.map((size) => (
+            <option key={size} value={size}>{size} ppem</option>
+          ))}
+        </select>
+      </div>
+      <div className="mt-3 p-4 bg-muted/30 rounded-lg flex flex-wrap items-end gap-px">
+        {glyphs.map((g, index) =>
+          g.bitmap
+            ? <img key={index} src={bitmapToUrl(g.bitmap)} alt={g.glyph} title={g.glyph} style={{ imageRendering: "pixelated", width: g.bitmap.width * 2 }} />
+            : <span key={index} className="text-muted-foreground" title="No bitmap, rendered from outlines">{g.glyph}</span>
+        )}
+      </div>
+    </section>
+  );
+}
+
+/// Color technologies plus CPAL palette swatches
+function ColorSection({ font }: { font: FontInfo }) {
+  const [palettes, setPalettes] = useState<ColorPalette[]>([]);
+
+  useEffect(() => {
+    if (font.color.palette_count === 0) {
+      setPalettes([]);
+      return;
+    }
+    getColorPalettes(font.id).then(setPalettes).catch((error) => {
+      console.error("Failed to load color palettes:", error);
+      setPalettes([]);
+    });
+  }, [font.id, font.color.palette_count]);
+
+  return (
+    <section>
+      <h3 className="text-lg font-semibold mb-3">Color</h3>
+      <dl className="grid grid-cols-1 gap-3">
+        <InfoRow label="Technologies" value={font.color.technologies.join(", ")} />
+        <InfoRow label="Palettes" value={String(font.color.palette_count)} />
+        <InfoRow label="Emoji" value={font.color.is_emoji ? "Yes" : "No"} />
+      </dl>
+      {palettes.map((palette) => (
+        <div key={palette.index} className="mt-3">
+          <div className="text-sm text-muted-foreground mb-1">
+            {[
+              `Palette ${palette.index}`,
+              palette.label,
+              palette.usable_with_light_background && "light background",
+              palette.usable_with_dark_background && "dark background",
+            ].filter(Boolean).join(" • ")}
+          </div>
+          <div className="flex flex-wrap gap-1">
+            {palette.colors.map(([r, g, b, a], index) => (
+              <span
+                key={index}
+                title={`rgba(${r}, ${g}, ${b}, ${(a / 255).toFixed(2)})`}
+                className="h-5 w-5 rounded border border-border"
+                style={{ backgroundColor: `rgba(${r}, ${g}, ${b}, ${a / 255})` }}
+              />
+            ))}
+          </div>
+        </div>
+      ))}
+    </section>
+  );
+}
+
+/// Which face a browser picks in this family for a weight / style / stretch request
+function FontMatching({ font }: { font: FontInfo }) {
+  const { getFontById } = useFontStore();
+  const [weight, setWeight] = useState(String(font.weight));
+  const [style, setStyle] = useState("normal");
+  const [stretch, setStretch] = useState("100%");
+  const [result, setResult] = useState<FontMatch | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    matchFontFace(font.css_font_family, weight, style, stretch)
+      .then((match) => {
+        setError(null);
+        setResult(match);
+      })
+      .catch((error) => {
+        setError(String(error));
+        setResult(null);
+      });
+  }, [font.css_font_family, weight, style, stretch]);
+
+  const matched = result ? getFontById(result.font_id) : undefined;
+  const inputClass = "w-28 px-2 py-1 border border-border rounded-lg bg-background";
+
+  return (
+    <section>
+      <h3 className="text-lg font-semibold mb-3">CSS Font Matching</h3>
+      <div className="flex flex-wrap items-center gap-2 text-sm font-mono">
+        <span className="text-muted-foreground">font-weight:</span>
+        <input value={weight} onChange={(e) => setWeight(e.target.value)} className={inputClass} />
+        <span className="text-muted-foreground">font-style:</span>
+        <input value={style} onChange={(e) => setStyle(e.target.value)} className={inputClass} />
+        <span className="text-muted-foreground">font-stretch:</span>
+        <input value={stretch} onChange={(e) => setStretch(e.target.value)} className={inputClass} />
+      </div>
+      {error && <p className="mt-3 text-sm text-destructive">{error}</p>}
+      {result && (
+        <dl className="mt-3 grid grid-cols-1 gap-3">
+          <InfoRow label="Matched Face" value={matched ? `${matched.full_name} (${matched.style})` : result.font_id} />
+          <InfoRow label="Rendered As" value={`${result.weight} • ${result.stretch}% • ${formatCssFontStyle(result.style)}`} />
+          {result.variations.length > 0 && (
+            <InfoRow label="Variations" value={result.variations.map((axis) => `"${axis.tag}" ${axis.value}`).join(", ")} />
+          )}
+          <InfoRow
+            label="Synthesis"
+            value={[result.synthetic_bold && "Bold", result.synthetic_italic && "Italic"].filter(Boolean).join(" + ") || "None"}
+          />
+        </dl>
+      )}
+    </section>
+  );
+}
+
+function formatCssFontStyle(style: CssFontStyle): string {
+  return typeof style === "string" ? style.toLowerCase() : `oblique ${style.Oblique}deg`;
+}
+
+/// Convert a decoded bitmap glyph into an image URL
+function bitmapToUrl(bitmap: BitmapGlyph): string {
+  if (bitmap.format === "Png") {
+    return URL.createObjectURL(new Blob([new Uint8Array(bitmap.data)], { type: "image/png" }));
+  }
+  const canvas = document.createElement("canvas");
+  canvas.width = bitmap.width;
+  canvas.height = bitmap.height;
+  const context = canvas.getContext("2d");
+  if (!context || bitmap.width === 0 || bitmap.height === 0) {
+    return canvas.toDataURL();
+  }
+  const image = context.createImageData(bitmap.width, bitmap.height);
+  for (let i = 0; i < bitmap.width * bitmap.height; i++) {
+    if (bitmap.format === "Gray") {
+      // Black ink with coverage as alpha
+      image.data[i * 4 + 3] = bitmap.data[i];
+    } else {
+      image.data.set(bitmap.data.slice(i * 4, i * 4 + 4), i * 4);
+    }
+  }
+  context.putImageData(image, 0, 0);
+  return canvas.toDataURL();
+}
+
 interface InfoRowProps {
   label: string;
   value: string;
+  raw?: string;     // 规范化前的原始值
   isUrl?: boolean;
 }
 
-function InfoRow({ label, value, isUrl }: InfoRowProps) {
+function InfoRow({ label, value, raw, isUrl }: InfoRowProps) {
   return (
     <div className="grid grid-cols-3 gap-2">
       <dt className="font-medium text-muted-foreground">{label}:</dt>
@@ -152,6 +678,11 @@ function InfoRow({ label, value, isUrl }: InfoRowProps) {
         ) : (
           <span className="text-foreground">{value}</span>
         )}
+        {raw !== undefined && (
+          <span className="block text-xs text-muted-foreground" title="Raw value before normalization">
+            raw: {JSON.stringify(raw)}
+          </span>
+        )}
       </dd>
     </div>
   );
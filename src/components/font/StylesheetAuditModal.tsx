@@ -0,0 +1,166 @@
+import { auditStylesheetFonts } from "@/lib/tauri-api";
+import { useFontStore } from "@/store/fontStore";
+import { useUIStore } from "@/store/uiStore";
+import { ContextAudit, FontStackAudit, StylesheetAudit } from "@/types/font";
+import { X } from "lucide-react";
+import { useState } from "react";
+
+interface StylesheetAuditModalProps {
+  onClose: () => void;
+}
+
+export function StylesheetAuditModal({ onClose }: StylesheetAuditModalProps) {
+  const previewText = useUIStore((state) => state.previewText);
+  const [path, setPath] = useState("");
+  const [sample, setSample] = useState(previewText || "The quick brown fox 永和九年 0123");
+  const [audit, setAudit] = useState<StylesheetAudit | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [isLoading, setIsLoading] = useState(false);
+
+  const runAudit = async () => {
+    try {
+      setIsLoading(true);
+      setError(null);
+      setAudit(await auditStylesheetFonts(path.trim(), sample));
+    } catch (error) {
+      setError(String(error));
+      setAudit(null);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50" onClick={onClose}>
+      <div className="bg-card border border-border rounded-lg shadow-xl w-full max-w-3xl max-h-[90vh] flex flex-col" onClick={(e) => { e.stopPropagation(); }}>
+        {/* Header */}
+        <div className="flex items-center justify-between p-6 border-b border-border">
+          <div className="flex-1">
+            <h2 className="text-2xl font-bold">Stylesheet Font Audit</h2>
+            <p className="text-sm text-muted-foreground mt-1">
+              Which installed face each font-family stack of a CSS or HTML file resolves to
+            </p>
+          </div>
+          <button
+            onClick={onClose}
+            className="p-2 hover:bg-muted rounded-lg transition-colors"
+            title="Close"
+          >
+            <X className="w-5 h-5" />
+          </button>
+        </div>
+
+        {/* Content */}
+        <div className="flex-1 overflow-y-auto p-6 space-y-6">
+          <section className="space-y-3">
+            <input
+              type="text"
+              value={path}
+              onChange={(e) => setPath(e.target.value)}
+              onKeyDown={(e) => { if (e.key === "Enter" && path.trim()) runAudit(); }}
+              placeholder="Path to a .css or .html file"
+              className="w-full h-10 px-3 rounded-lg border border-input bg-background text-sm font-mono focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            <div className="flex items-center gap-2">
+              <input
+                type="text"
+                value={sample}
+                onChange={(e) => setSample(e.target.value)}
+                placeholder="Sample text"
+                className="flex-1 h-10 px-3 rounded-lg border border-input bg-background text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <button
+                onClick={runAudit}
+                disabled={!path.trim() || isLoading}
+                className="px-4 h-10 text-sm border border-border rounded-lg hover:bg-muted transition-colors disabled:opacity-50"
+              >
+                {isLoading ? "Auditing..." : "Audit"}
+              </button>
+            </div>
+            {error && <p className="text-sm text-destructive">{error}</p>}
+          </section>
+
+          {audit && audit.web_fonts.length > 0 && (
+            <p className="text-sm text-muted-foreground">
+              Web fonts (@font-face): {audit.web_fonts.join(", ")}
+            </p>
+          )}
+          {audit && audit.stacks.length === 0 && (
+            <p className="text-sm text-muted-foreground">No font-family declarations found.</p>
+          )}
+          {audit?.stacks.map((stack, index) => (
+            <StackSection key={index} stack={stack} webFonts={audit.web_fonts} />
+          ))}
+        </div>
+      </div>
+    </div>
+  );
+}
+
+function StackSection({ stack, webFonts }: { stack: FontStackAudit; webFonts: string[] }) {
+  const isWebFont = (family: string) => webFonts.some((web) => web.toLowerCase() === family.toLowerCase());
+
+  return (
+    <section>
+      <h3 className="text-lg font-semibold mb-1">
+        {stack.families.map((family, index) => (
+          <span key={index}>
+            {index > 0 && ", "}
+            <span
+              className={stack.missing.includes(family) ? "text-destructive line-through" : isWebFont(family) ? "text-primary" : ""}
+              title={stack.missing.includes(family) ? "Not installed" : isWebFont(family) ? "Web font" : undefined}
+            >
+              {family}
+            </span>
+          </span>
+        ))}
+      </h3>
+      <p className="text-xs text-muted-foreground font-mono mb-3">{stack.selectors.join(", ")}</p>
+      <table className="w-full text-xs">
+        <thead className="text-muted-foreground text-left">
+          <tr>
+            <th className="pr-2">Weight / Style / Stretch</th>
+            <th className="pr-2">Face</th>
+            <th>Fallback Characters</th>
+          </tr>
+        </thead>
+        <tbody>
+          {stack.contexts.map((context, index) => (
+            <ContextRow key={index} audit={context} />
+          ))}
+        </tbody>
+      </table>
+    </section>
+  );
+}
+
+function ContextRow({ audit }: { audit: ContextAudit }) {
+  const { getFontById } = useFontStore();
+  const { context, matched } = audit;
+  const face = matched ? getFontById(matched.font_id) : undefined;
+  const synthesis = matched
+    ? [matched.synthetic_bold && "synthetic bold", matched.synthetic_italic && "synthetic italic"].filter(Boolean).join(", ")
+    : "";
+
+  return (
+    <tr className="align-top border-t border-border">
+      <td className="pr-2 font-mono">{`${context.weight} / ${context.style} / ${context.stretch}`}</td>
+      <td className="pr-2">
+        {audit.error && <span className="text-destructive">{audit.error}</span>}
+        {!audit.error && (face ? face.full_name : matched ? matched.font_id : <span className="text-muted-foreground">System fallback</span>)}
+        {synthesis && <span className="block text-muted-foreground">{synthesis}</span>}
+      </td>
+      <td className="break-words">
+        {audit.fallbacks.map((fallback, index) => (
+          <span
+            key={index}
+            className={`inline-block mr-1 ${fallback.family ? "" : "text-destructive"}`}
+            title={fallback.family ? `Rendered with ${fallback.family}` : "Not covered by the stack"}
+          >
+            {fallback.character}
+          </span>
+        ))}
+      </td>
+    </tr>
+  );
+}
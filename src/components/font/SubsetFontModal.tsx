@@ -0,0 +1,124 @@
+import { subsetFont } from "@/lib/tauri-api";
+import { useUIStore } from "@/store/uiStore";
+import { FontInfo, SubsetReport } from "@/types/font";
+import { X } from "lucide-react";
+import { useState } from "react";
+
+interface SubsetFontModalProps {
+  font: FontInfo;
+  onClose: () => void;
+}
+
+function formatSize(bytes: number): string {
+  if (bytes >= 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+  return `${(bytes / 1024).toFixed(1)} KB`;
+}
+
+export function SubsetFontModal({ font, onClose }: SubsetFontModalProps) {
+  const previewText = useUIStore((state) => state.previewText);
+  const [text, setText] = useState(previewText);
+  const [unicodeRanges, setUnicodeRanges] = useState("U+0020-007E");
+  const [dropHinting, setDropHinting] = useState(true);
+  const [dropUnusedTables, setDropUnusedTables] = useState(true);
+  const [outputPath, setOutputPath] = useState("");
+  const [report, setReport] = useState<SubsetReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [isLoading, setIsLoading] = useState(false);
+
+  const runSubset = async () => {
+    try {
+      setIsLoading(true);
+      setError(null);
+      const options = { text, unicode_ranges: unicodeRanges, drop_hinting: dropHinting, drop_unused_tables: dropUnusedTables };
+      setReport(await subsetFont(font.id, options, outputPath.trim() || undefined));
+    } catch (error) {
+      setError(String(error));
+      setReport(null);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50" onClick={onClose}>
+      <div className="bg-card border border-border rounded-lg shadow-xl w-full max-w-2xl max-h-[90vh] flex flex-col" onClick={(e) => { e.stopPropagation(); }}>
+        {/* Header */}
+        <div className="flex items-center justify-between p-6 border-b border-border">
+          <div className="flex-1">
+            <h2 className="text-2xl font-bold">Subset for Web</h2>
+            <p className="text-sm text-muted-foreground mt-1">
+              Keep only the glyphs {font.full_name} needs for a text corpus or Unicode ranges
+            </p>
+          </div>
+          <button
+            onClick={onClose}
+            className="p-2 hover:bg-muted rounded-lg transition-colors"
+            title="Close"
+          >
+            <X className="w-5 h-5" />
+          </button>
+        </div>
+
+        {/* Content */}
+        <div className="flex-1 overflow-y-auto p-6 space-y-4">
+          <textarea
+            value={text}
+            onChange={(e) => setText(e.target.value)}
+            placeholder="Text corpus: every character used on the site"
+            rows={5}
+            className="w-full px-3 py-2 rounded-lg border border-input bg-background text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+          />
+          <input
+            type="text"
+            value={unicodeRanges}
+            onChange={(e) => setUnicodeRanges(e.target.value)}
+            placeholder="Unicode ranges, e.g. U+0020-007E, U+4E00-9FFF"
+            className="w-full h-10 px-3 rounded-lg border border-input bg-background text-sm font-mono focus:outline-none focus:ring-2 focus:ring-ring"
+          />
+          <div className="flex items-center gap-6 text-sm">
+            <label className="flex items-center gap-2">
+              <input type="checkbox" checked={dropHinting} onChange={(e) => setDropHinting(e.target.checked)} />
+              Drop hinting
+            </label>
+            <label className="flex items-center gap-2">
+              <input type="checkbox" checked={dropUnusedTables} onChange={(e) => setDropUnusedTables(e.target.checked)} />
+              Drop tables browsers don't use
+            </label>
+          </div>
+          <div className="flex items-center gap-2">
+            <input
+              type="text"
+              value={outputPath}
+              onChange={(e) => setOutputPath(e.target.value)}
+              placeholder="Output path (default: Downloads)"
+              className="flex-1 h-10 px-3 rounded-lg border border-input bg-background text-sm font-mono focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            <button
+              onClick={runSubset}
+              disabled={(!text.trim() && !unicodeRanges.trim()) || isLoading}
+              className="px-4 h-10 text-sm border border-border rounded-lg hover:bg-muted transition-colors disabled:opacity-50"
+            >
+              {isLoading ? "Subsetting..." : "Subset"}
+            </button>
+          </div>
+          {error && <p className="text-sm text-destructive">{error}</p>}
+
+          {report && (
+            <section className="text-sm space-y-1">
+              <p className="font-mono break-all">{report.output_path}</p>
+              <p>
+                {formatSize(report.original_size)} → {formatSize(report.subset_size)} • {report.codepoint_count} characters • {report.glyph_count} glyphs
+              </p>
+              {report.dropped_tables.length > 0 && (
+                <p className="text-muted-foreground">Dropped tables: {report.dropped_tables.join(", ")}</p>
+              )}
+              {report.missing.length > 0 && (
+                <p className="text-destructive">Not in the font: {report.missing.join(" ")}</p>
+              )}
+            </section>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
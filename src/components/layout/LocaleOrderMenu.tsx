@@ -0,0 +1,71 @@
+import { systemLocaleOrder, useUIStore } from "@/store/uiStore";
+import { ChevronDown, ChevronUp } from "lucide-react";
+
+const LOCALE_LABELS: Record<string, string> = {
+  'zh-CN': '简体中文',
+  'zh-TW': '繁體中文',
+  'ja-JP': '日本語',
+  'ko-KR': '한국어',
+  'en-US': 'English',
+};
+
+interface LocaleOrderMenuProps {
+  onClose: () => void;
+}
+
+/** Settings panel: which localized family name cards and details show first */
+export function LocaleOrderMenu({ onClose }: LocaleOrderMenuProps) {
+  const { localeOrder, setLocaleOrder } = useUIStore();
+
+  const move = (index: number, offset: number) => {
+    const target = index + offset;
+    if (target < 0 || target >= localeOrder.length) return;
+    const order = [...localeOrder];
+    [order[index], order[target]] = [order[target], order[index]];
+    setLocaleOrder(order);
+  };
+
+  return (
+    <>
+      <div className="fixed inset-0 z-40" onClick={onClose} />
+      <div className="absolute right-0 top-12 z-50 w-64 bg-card border border-border rounded-lg shadow-xl p-4 space-y-3">
+        <div>
+          <h2 className="text-sm font-semibold">Name language order</h2>
+          <p className="text-xs text-muted-foreground mt-1">
+            Font names are shown in the first language the font has a name for
+          </p>
+        </div>
+        <ol className="space-y-1">
+          {localeOrder.map((locale, index) => (
+            <li key={locale} className="flex items-center gap-2 text-sm">
+              <span className="w-4 text-muted-foreground">{index + 1}</span>
+              <span className="flex-1">{LOCALE_LABELS[locale] ?? locale}</span>
+              <button
+                onClick={() => move(index, -1)}
+                disabled={index === 0}
+                className="p-1 rounded hover:bg-muted transition-colors disabled:opacity-30"
+                title="Move up"
+              >
+                <ChevronUp className="h-4 w-4" />
+              </button>
+              <button
+                onClick={() => move(index, 1)}
+                disabled={index === localeOrder.length - 1}
+                className="p-1 rounded hover:bg-muted transition-colors disabled:opacity-30"
+                title="Move down"
+              >
+                <ChevronDown className="h-4 w-4" />
+              </button>
+            </li>
+          ))}
+        </ol>
+        <button
+          onClick={() => setLocaleOrder(systemLocaleOrder())}
+          className="w-full h-8 text-sm border border-border rounded-lg hover:bg-muted transition-colors"
+        >
+          Reset to system language
+        </button>
+      </div>
+    </>
+  );
+}
@@ -1,6 +1,8 @@
 import { useFonts } from "@/hooks/useFonts";
 import { useFontStore } from "@/store/fontStore";
 import { useUIStore } from "@/store/uiStore";
+import { hasColorTrait, hasLayoutFeature, hasRenderingTrait, hasTerminalTrait } from "@/lib/font";
+import { CjkStyle, ColorTrait, FontCategory, LintRule, RenderingTrait, TerminalTrait } from "@/types/font";
 import { ChevronLeft, ChevronRight } from "lucide-react";
 import type { LiHTMLAttributes } from "react";
 import { useCallback } from "react";
@@ -13,7 +15,7 @@ export function Sidebar({ collapsed }: SidebarProps) {
   const toggleSidebar = useUIStore((state) => state.toggleSidebar);
   const { fontState } = useFonts();
   const uiStore = useUIStore();
-  const { getFontById } = useFontStore();
+  const { getFontById, getFontsByCssFamily } = useFontStore();
 
   const countByLanguage = useCallback((lang: string) => {
     if (!fontState) {
@@ -29,6 +31,89 @@ export function Sidebar({ collapsed }: SidebarProps) {
     return count;
   }, [fontState])
 
+  // Number of font families with at least one font carrying the lint rule
+  const countByLintRule = useCallback((rule: LintRule) => {
+    if (!fontState) {
+      return 0;
+    }
+    return fontState.css_font_families.filter(fm =>
+      getFontsByCssFamily(fm.name).some(font => font.lint_warnings.some(w => w.rule === rule))
+    ).length;
+  }, [fontState])
+
+  const countByCategory = useCallback((category: FontCategory) => {
+    if (!fontState) {
+      return 0;
+    }
+    return fontState.css_font_families.filter(fm => fm.category === category).length;
+  }, [fontState])
+
+  const countByCjkStyle = useCallback((style: CjkStyle) => {
+    if (!fontState) {
+      return 0;
+    }
+    let count = 0;
+    fontState.css_font_families.forEach(fm => {
+      const font = getFontById(fm.default_font_id);
+      if (font && font.cjk_style.style === style) {
+        count++;
+      }
+    });
+    return count;
+  }, [fontState])
+
+  const countByRenderingTrait = useCallback((trait: RenderingTrait) => {
+    if (!fontState) {
+      return 0;
+    }
+    let count = 0;
+    fontState.css_font_families.forEach(fm => {
+      const font = getFontById(fm.default_font_id);
+      if (font && hasRenderingTrait(font, trait)) {
+        count++;
+      }
+    });
+    return count;
+  }, [fontState])
+
+  const countByTerminalTrait = useCallback((trait: TerminalTrait) => {
+    if (!fontState) {
+      return 0;
+    }
+    let count = 0;
+    fontState.css_font_families.forEach(fm => {
+      const font = getFontById(fm.default_font_id);
+      if (font && hasTerminalTrait(font, trait)) {
+        count++;
+      }
+    });
+    return count;
+  }, [fontState])
+
+  const countByFeature = useCallback((tag: string) => {
+    if (!fontState) {
+      return 0;
+    }
+    let count = 0;
+    fontState.css_font_families.forEach(fm => {
+      const font = getFontById(fm.default_font_id);
+      if (font && hasLayoutFeature(font, tag)) {
+        count++;
+      }
+    });
+    return count;
+  }, [fontState])
+
+  // Number of font families with at least one font carrying the color trait
+  const countByColorTrait = useCallback((trait: ColorTrait) => {
+    if (!fontState) {
+      return 0;
+    }
+    return fontState.css_font_families.filter(fm =>
+      getFontsByCssFamily(fm.name).some(font => hasColorTrait(font, trait))
+    ).length;
+  }, [fontState])
+
   const enabled_count = useCallback(() => {
     if (!fontState) {
       return 0;
@@ -94,10 +179,80 @@ export function Sidebar({ collapsed }: SidebarProps) {
                 Types
               </h2>
               <ul className="space-y-1">
-                <SidebarItem label="Sans Serif" count={0} />
-                <SidebarItem label="Serif" count={0} />
-                <SidebarItem label="Monospace" count={0} />
-                <SidebarItem label="Handwriting" count={0} />
+                {CATEGORY_LABELS.map(([category, label]) => (
+                  <SidebarItem key={category} label={label} count={countByCategory(category)} onClick={() => { uiStore.setFilters({ languages: [], tags: [], searchText: uiStore.filters.searchText, categories: [category] }) }} />
+                ))}
+              </ul>
+            </div>
+
+            {/* CJK Styles */}
+            <div>
+              <h2 className="text-xs font-semibold text-muted-foreground uppercase mb-2">
+                CJK Styles
+              </h2>
+              <ul className="space-y-1">
+                {CJK_STYLE_LABELS.map(([style, label]) => (
+                  <SidebarItem key={style} label={label} count={countByCjkStyle(style)} onClick={() => { uiStore.setFilters({ languages: [], tags: [], searchText: uiStore.filters.searchText, cjkStyles: [style] }) }} />
+                ))}
+              </ul>
+            </div>
+
+            {/* Rendering */}
+            <div>
+              <h2 className="text-xs font-semibold text-muted-foreground uppercase mb-2">
+                Rendering
+              </h2>
+              <ul className="space-y-1">
+                {RENDERING_TRAIT_LABELS.map(([trait, label]) => (
+                  <SidebarItem key={trait} label={label} count={countByRenderingTrait(trait)} onClick={() => { uiStore.setFilters({ languages: [], tags: [], searchText: uiStore.filters.searchText, rendering: [trait] }) }} />
+                ))}
+              </ul>
+            </div>
+
+            {/* Terminal */}
+            <div>
+              <h2 className="text-xs font-semibold text-muted-foreground uppercase mb-2">
+                Terminal
+              </h2>
+              <ul className="space-y-1">
+                {TERMINAL_TRAIT_LABELS.map(([trait, label]) => (
+                  <SidebarItem key={trait} label={label} count={countByTerminalTrait(trait)} onClick={() => { uiStore.setFilters({ languages: [], tags: [], searchText: uiStore.filters.searchText, terminal: [trait] }) }} />
+                ))}
+              </ul>
+            </div>
+
+            {/* OpenType Features */}
+            <div>
+              <h2 className="text-xs font-semibold text-muted-foreground uppercase mb-2">
+                OpenType Features
+              </h2>
+              <ul className="space-y-1">
+                {FEATURE_LABELS.map(([tag, label]) => (
+                  <SidebarItem key={tag} label={label} count={countByFeature(tag)} onClick={() => { uiStore.setFilters({ languages: [], tags: [], searchText: uiStore.filters.searchText, features: [tag] }) }} />
+                ))}
+              </ul>
+            </div>
+
+            {/* Color */}
+            <div>
+              <h2 className="text-xs font-semibold text-muted-foreground uppercase mb-2">
+                Color
+              </h2>
+              <ul className="space-y-1">
+                <SidebarItem label="Color Fonts" count={countByColorTrait("Color")} onClick={() => { uiStore.setFilters({ languages: [], tags: [], searchText: uiStore.filters.searchText, color: ["Color"] }) }} />
+                <SidebarItem label="Emoji" count={countByColorTrait("Emoji")} onClick={() => { uiStore.setFilters({ languages: [], tags: [], searchText: uiStore.filters.searchText, color: ["Emoji"] }) }} />
+              </ul>
+            </div>
+
+            {/* Metadata Warnings */}
+            <div>
+              <h2 className="text-xs font-semibold text-muted-foreground uppercase mb-2">
+                Metadata Warnings
+              </h2>
+              <ul className="space-y-1">
+                {LINT_RULE_LABELS.map(([rule, label]) => (
+                  <SidebarItem key={rule} label={label} count={countByLintRule(rule)} onClick={() => { uiStore.setFilters({ languages: [], tags: [], searchText: uiStore.filters.searchText, lintRules: [rule] }) }} />
+                ))}
               </ul>
             </div>
 
@@ -118,6 +273,60 @@ export function Sidebar({ collapsed }: SidebarProps) {
   );
 }
 
+const CATEGORY_LABELS: [FontCategory, string][] = [
+  ["SansSerif", "Sans Serif"],
+  ["Serif", "Serif"],
+  ["Monospace", "Monospace"],
+  ["Script", "Handwriting"],
+  ["Display", "Display"],
+];
+
+const CJK_STYLE_LABELS: [CjkStyle, string][] = [
+  ["Song", "宋体 / Ming"],
+  ["Hei", "黑体 / Gothic"],
+  ["Kai", "楷体 / Kai"],
+  ["Fangsong", "仿宋 / Fangsong"],
+  ["Yuan", "圆体 / Rounded"],
+];
+
+const RENDERING_TRAIT_LABELS: [RenderingTrait, string][] = [
+  ["TrueType", "TrueType Outlines"],
+  ["Cff", "CFF Outlines"],
+  ["Hinted", "Hinted"],
+  ["Autohinted", "Autohinted"],
+  ["Gasp", "Has gasp"],
+  ["EmbeddedBitmaps", "Embedded Bitmaps"],
+  ["VerticalWriting", "Vertical Writing"],
+  ["Math", "Math (OpenType MATH)"],
+];
+
+const TERMINAL_TRAIT_LABELS: [TerminalTrait, string][] = [
+  ["Ligatures", "Coding Ligatures"],
+  ["TerminalReady", "Terminal Ready"],
+  ["Powerline", "Powerline Symbols"],
+  ["NerdFont", "Nerd Fonts"],
+];
+
+// "ssXX" matches any stylistic set
+const FEATURE_LABELS: [string, string][] = [
+  ["tnum", "Tabular Figures"],
+  ["onum", "Oldstyle Figures"],
+  ["smcp", "Small Caps"],
+  ["frac", "Fractions"],
+  ["vert", "Vertical Alternates"],
+  ["ssXX", "Stylistic Sets"],
+];
+
+const LINT_RULE_LABELS: [LintRule, string][] = [
+  ["WeightClassOutOfRange", "Weight Out of Range"],
+  ["WeightNameMismatch", "Weight/Name Mismatch"],
+  ["ItalicBitMissing", "Italic Bit Missing"],
+  ["ItalicBitUnexpected", "Unexpected Italic Bit"],
+  ["BoldBitMismatch", "Bold Bit Mismatch"],
+  ["InvalidPostScriptName", "Invalid PostScript Name"],
+  ["DuplicatePostScriptName", "Duplicate PostScript Name"],
+];
+
 interface SidebarItemProps extends React.DetailedHTMLProps<LiHTMLAttributes<HTMLLIElement>, HTMLLIElement> {
   label: string;
   count: number;
@@ -1,13 +1,34 @@
+import { setFamilyGrouping } from "@/lib/tauri-api";
 import { debounceWithCallbackRef } from "@/lib/utils";
+import { useFontStore } from "@/store/fontStore";
 import { useUIStore } from "@/store/uiStore";
-import { Grid3x3, List, Search, Settings, Type } from "lucide-react";
-import { useCallback, useEffect, useRef } from "react";
+import { FamilyGrouping } from "@/types/font";
+import { StylesheetAuditModal } from "@/components/font/StylesheetAuditModal";
+import { LocaleOrderMenu } from "@/components/layout/LocaleOrderMenu";
+import { FileSearch, Grid3x3, List, Search, Settings, Type } from "lucide-react";
+import { useCallback, useEffect, useRef, useState } from "react";
 
 export function Topbar() {
   const { viewMode, setViewMode, previewText, setPreviewText } = useUIStore();
   const store = useUIStore();
+  const { fontState, setFontState } = useFontStore();
+  const [showAudit, setShowAudit] = useState(false);
+  const [showSettings, setShowSettings] = useState(false);
   const appName = 'Fontscape';
 
+  // Same files grouped the way browsers/design apps, DirectWrite and GDI see them
+  const handleGroupingChange = useCallback(
+    async (e: React.ChangeEvent<HTMLSelectElement>) => {
+      const grouping = e.currentTarget.value as FamilyGrouping;
+      try {
+        setFontState(await setFamilyGrouping(grouping));
+      } catch (error) {
+        console.error('Failed to change family grouping:', error);
+      }
+    },
+    [setFontState]
+  );
+
   const debouncedSetSearch = useRef(
     debounceWithCallbackRef((text: string) => {
       store.setFilters({
@@ -121,6 +142,19 @@ export function Topbar() {
 
       {/* Actions */}
       <div className="flex items-center gap-2 flex-shrink-0">
+        {/* Family grouping */}
+        <select
+          value={fontState?.grouping ?? 'Typographic'}
+          onChange={handleGroupingChange}
+          disabled={!fontState}
+          className="h-10 px-2 rounded-lg border border-input bg-background text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+          title="Family grouping"
+        >
+          <option value="Typographic">Typographic (Browsers, Design apps)</option>
+          <option value="Wws">WWS (DirectWrite)</option>
+          <option value="Legacy">Legacy (Windows GDI)</option>
+        </select>
+
         {/* View mode toggle */}
         <div className="flex items-center gap-1 p-1 rounded-lg bg-muted">
           <button
@@ -145,14 +179,29 @@ export function Topbar() {
           </button>
         </div>
 
-        {/* Settings */}
+        {/* Stylesheet audit */}
         <button
-          className="p-2 rounded-lg hover:bg-muted transition-colors"
-          title="Settings"
+          onClick={() => setShowAudit(true)}
+          disabled={!fontState}
+          className="p-2 rounded-lg hover:bg-muted transition-colors disabled:opacity-50"
+          title="Audit stylesheet fonts"
         >
-          <Settings className="h-5 w-5" />
+          <FileSearch className="h-5 w-5" />
         </button>
+
+        {/* Settings */}
+        <div className="relative">
+          <button
+            onClick={() => setShowSettings(!showSettings)}
+            className="p-2 rounded-lg hover:bg-muted transition-colors"
+            title="Settings"
+          >
+            <Settings className="h-5 w-5" />
+          </button>
+          {showSettings && <LocaleOrderMenu onClose={() => setShowSettings(false)} />}
+        </div>
       </div>
+      {showAudit && <StylesheetAuditModal onClose={() => setShowAudit(false)} />}
     </header>
   );
 }
@@ -2,6 +2,8 @@
  * Font utility functions
  */
 
+import { ColorTrait, FontInfo, LocalizedNames, RenderingTrait, TerminalTrait } from "@/types/font";
+
 /**
  * CSS font-weight to human-readable name mapping
  * Based on CSS font-weight specification
@@ -47,3 +49,132 @@ export function formatFontWeight(weight: number): string {
   const name = getFontWeightName(weight);
   return `${name} (${weight})`;
 }
+
+/**
+ * OS/2 usWidthClass names, index 0 is width class 1
+ */
+const WIDTH_CLASS_NAMES = [
+  'Ultra Condensed', 'Extra Condensed', 'Condensed', 'Semi Condensed', 'Normal',
+  'Semi Expanded', 'Expanded', 'Extra Expanded', 'Ultra Expanded',
+];
+
+/**
+ * Format an OS/2 width class (1-9) as its name
+ * @param width - usWidthClass value
+ */
+export function formatWidthClass(width: number): string {
+  return WIDTH_CLASS_NAMES[width - 1] ?? `Width ${width}`;
+}
+
+/**
+ * Find the localized names entry that best matches a preferred locale.
+ * Tries an exact tag match first, then any tag with the same primary language
+ * (e.g. "zh-CN" falls back to "zh-SG" / "zh-TW").
+ */
+function matchLocale(
+  localizedNames: Record<string, LocalizedNames>,
+  locale: string
+): LocalizedNames | undefined {
+  const wanted = locale.toLowerCase();
+  const tags = Object.keys(localizedNames);
+
+  const exact = tags.find((tag) => tag.toLowerCase() === wanted);
+  if (exact) {
+    return localizedNames[exact];
+  }
+
+  const language = wanted.split('-')[0];
+  const sameLanguage = tags.find((tag) => tag.toLowerCase().split('-')[0] === language);
+  return sameLanguage ? localizedNames[sameLanguage] : undefined;
+}
+
+/**
+ * Resolve the display family name following the user's locale order
+ * @param font - Font to resolve the name for
+ * @param localeOrder - BCP 47 tags in order of preference (e.g. ["zh-CN", "ja-JP", "en-US"])
+ * @returns Typographic family (ID 16) or family (ID 1) of the first matching locale, else the English family
+ */
+export function getDisplayName(font: FontInfo, localeOrder: string[]): string {
+  for (const locale of localeOrder) {
+    const names = matchLocale(font.localized_names ?? {}, locale);
+    const name = names?.typographic_family || names?.family;
+    if (name) {
+      return name;
+    }
+  }
+  return font.family;
+}
+
+/**
+ * Check a rendering attribute used by the sidebar filters
+ * @param font - Font to check
+ * @param trait - Outline format or hinting attribute
+ */
+export function hasRenderingTrait(font: FontInfo, trait: RenderingTrait): boolean {
+  const rendering = font.rendering;
+  switch (trait) {
+    case 'TrueType':
+      return rendering.outline_format === 'TrueType';
+    case 'Cff':
+      return rendering.outline_format === 'Cff' || rendering.outline_format === 'Cff2';
+    case 'Hinted':
+      return rendering.hinted;
+    case 'Autohinted':
+      return rendering.autohinted;
+    case 'Gasp':
+      return rendering.has_gasp;
+    case 'EmbeddedBitmaps':
+      return rendering.has_embedded_bitmaps;
+    case 'VerticalWriting':
+      return font.vertical.vertical_ready;
+    case 'Math':
+      return font.math.has_math_table;
+  }
+}
+
+/**
+ * Check a color attribute used by the sidebar filters
+ * @param font - Font to check
+ * @param trait - Any color technology, or emoji coverage
+ */
+export function hasColorTrait(font: FontInfo, trait: ColorTrait): boolean {
+  switch (trait) {
+    case 'Color':
+      return font.color.technologies.length > 0;
+    case 'Emoji':
+      return font.color.is_emoji;
+  }
+}
+
+/**
+ * Check a coding/terminal attribute used by the sidebar filters
+ * @param font - Font to check
+ * @param trait - Fixed pitch, coding ligatures or terminal glyph coverage
+ */
+export function hasTerminalTrait(font: FontInfo, trait: TerminalTrait): boolean {
+  const monospace = font.monospace;
+  switch (trait) {
+    case 'Monospace':
+      return monospace.is_monospace;
+    case 'Ligatures':
+      return font.ligatures.has_programming_ligatures;
+    case 'TerminalReady':
+      return monospace.terminal.terminal_ready;
+    case 'Powerline':
+      return monospace.terminal.powerline;
+    case 'NerdFont':
+      return monospace.terminal.is_nerd_font;
+  }
+}
+
+/**
+ * Check an OpenType feature used by the sidebar filters
+ * @param font - Font to check
+ * @param tag - Feature tag; "ssXX" matches any stylistic set
+ */
+export function hasLayoutFeature(font: FontInfo, tag: string): boolean {
+  if (tag === 'ssXX') {
+    return font.features.some((f) => /^ss\d\d$/.test(f));
+  }
+  return font.features.includes(tag);
+}
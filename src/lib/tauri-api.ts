@@ -1,4 +1,4 @@
-import { FontState } from '@/types/font';
+import { BitmapGlyphResult, ColorPalette, FamilyGrouping, FontMatch, FontState, FontTables, LayoutFeatures, LintWarning, NameRecord, StylesheetAudit, SubsetOptions, SubsetReport } from '@/types/font';
 import { invoke } from '@tauri-apps/api/core';
 
 // Mock data for development until backend is ready
@@ -11,6 +11,7 @@ const MOCK_FONT_STATE: FontState = {
       postscript_name: 'ArialMT',
       style: 'Regular',
       path: 'C:\\Windows\\Fonts\\arial.ttf',
+      face_index: 0,
       fileSize: 524288,
       format: 'TrueType',
       is_variable: false,
@@ -23,7 +24,66 @@ const MOCK_FONT_STATE: FontState = {
       },
       status: 'Enabled',
       created_at: Date.now(),
+      localized_names: { 'en-US': { family: 'Arial' } },
       css_font_family: 'Arial',
+      name_repairs: [],
+      health: { issues: [] },
+      lint_warnings: [],
+      rendering: {
+        outline_format: 'TrueType',
+        has_fpgm: true,
+        has_prep: true,
+        has_cvt: true,
+        hinted: true,
+        autohinted: false,
+        has_gasp: true,
+        has_embedded_bitmaps: false,
+      },
+      bitmap_strikes: [],
+      color: { technologies: [], palette_count: 0, is_emoji: false },
+      ligatures: { has_programming_ligatures: false, sequences: [] },
+      features: ['kern', 'liga'],
+      vertical: {
+        has_vhea: false,
+        has_vmtx: false,
+        has_vorg: false,
+        needs_vorg: false,
+        has_vert: false,
+        has_vrt2: false,
+        vertical_ready: false,
+        missing: ['vhea', 'vmtx', 'vert/vrt2'],
+      },
+      math: {
+        has_math_table: false,
+        constants: [],
+        blocks: [],
+      },
+      classification: {
+        category: 'SansSerif',
+        confidence: 0.95,
+        signals: ['PANOSE family type 2, serif style 11', 'sFamilyClass 8', "'I' foot/stem width ratio 1.00"],
+      },
+      cjk_style: {
+        confidence: 0,
+        signals: [],
+      },
+      style_attributes: {
+        weight: 400,
+        width: 5,
+        slope: 'Upright',
+        os2_weight: 400,
+        os2_width: 5,
+        os2_slope: 'Upright',
+        fs_selection: 64,
+        conflicts: [],
+      },
+      variation_axes: [],
+      monospace: {
+        post_fixed_pitch: false,
+        ascii_fixed_pitch: false,
+        is_monospace: false,
+        terminal: { box_drawing: 0, block_elements: 0, powerline: false, nerd_font_icons: 0, is_nerd_font: false, terminal_ready: false },
+      },
     },
     {
       id: '2',
@@ -32,6 +92,7 @@ const MOCK_FONT_STATE: FontState = {
       postscript_name: 'MicrosoftYaHei',
       style: 'Regular',
       path: 'C:\\Windows\\Fonts\\msyh.ttc',
+      face_index: 0,
       fileSize: 1048576,
       format: 'TrueTypeCollection',
       is_variable: false,
@@ -44,9 +105,70 @@ const MOCK_FONT_STATE: FontState = {
       },
       status: 'SystemFont',
       created_at: Date.now(),
-      family_zh: '微软雅黑',
-      full_name_zh: '微软雅黑 Regular',
+      localized_names: {
+        'en-US': { family: 'Microsoft YaHei', full_name: 'Microsoft YaHei Regular' },
+        'zh-CN': { family: '微软雅黑', full_name: '微软雅黑 Regular' },
+      },
       css_font_family: 'Microsoft YaHei',
+      name_repairs: [],
+      health: { issues: [] },
+      lint_warnings: [],
+      rendering: {
+        outline_format: 'TrueType',
+        has_fpgm: true,
+        has_prep: true,
+        has_cvt: true,
+        hinted: true,
+        autohinted: false,
+        has_gasp: true,
+        has_embedded_bitmaps: false,
+      },
+      bitmap_strikes: [],
+      color: { technologies: [], palette_count: 0, is_emoji: false },
+      ligatures: { has_programming_ligatures: false, sequences: [] },
+      features: ['kern', 'liga'],
+      vertical: {
+        has_vhea: true,
+        has_vmtx: true,
+        has_vorg: false,
+        needs_vorg: false,
+        has_vert: true,
+        has_vrt2: true,
+        vertical_ready: true,
+        missing: [],
+      },
+      math: {
+        has_math_table: false,
+        constants: [],
+        blocks: [],
+      },
+      classification: {
+        category: 'SansSerif',
+        confidence: 0.4,
+        signals: ['name contains "yahei"'],
+      },
+      cjk_style: {
+        style: 'Hei',
+        confidence: 1,
+        signals: ['name contains "yahei"', 'stroke end 1.04, tip 0.97, slope 0.002, weight 0.071'],
+      },
+      style_attributes: {
+        weight: 400,
+        width: 5,
+        slope: 'Upright',
+        os2_weight: 400,
+        os2_width: 5,
+        os2_slope: 'Upright',
+        fs_selection: 64,
+        conflicts: [],
+      },
+      variation_axes: [],
+      monospace: {
+        post_fixed_pitch: false,
+        ascii_fixed_pitch: false,
+        is_monospace: false,
+        terminal: { box_drawing: 0, block_elements: 0, powerline: false, nerd_font_icons: 0, is_nerd_font: false, terminal_ready: false },
+      },
     },
     {
       id: '3',
@@ -55,6 +177,7 @@ const MOCK_FONT_STATE: FontState = {
       postscript_name: 'TimesNewRomanPSMT',
       style: 'Regular',
       path: 'C:\\Windows\\Fonts\\times.ttf',
+      face_index: 0,
       fileSize: 612352,
       format: 'TrueType',
       is_variable: false,
@@ -67,7 +190,66 @@ const MOCK_FONT_STATE: FontState = {
       },
       status: 'Enabled',
       created_at: Date.now(),
+      localized_names: { 'en-US': { family: 'Times New Roman' } },
       css_font_family: 'Times New Roman',
+      name_repairs: [],
+      health: { issues: [] },
+      lint_warnings: [],
+      rendering: {
+        outline_format: 'TrueType',
+        has_fpgm: true,
+        has_prep: true,
+        has_cvt: true,
+        hinted: true,
+        autohinted: false,
+        has_gasp: true,
+        has_embedded_bitmaps: false,
+      },
+      bitmap_strikes: [],
+      color: { technologies: [], palette_count: 0, is_emoji: false },
+      ligatures: { has_programming_ligatures: false, sequences: [] },
+      features: ['kern', 'liga'],
+      vertical: {
+        has_vhea: false,
+        has_vmtx: false,
+        has_vorg: false,
+        needs_vorg: false,
+        has_vert: false,
+        has_vrt2: false,
+        vertical_ready: false,
+        missing: ['vhea', 'vmtx', 'vert/vrt2'],
+      },
+      math: {
+        has_math_table: false,
+        constants: [],
+        blocks: [],
+      },
+      classification: {
+        category: 'Serif',
+        confidence: 1.0,
+        signals: ['name contains "roman"', 'PANOSE family type 2, serif style 2', 'sFamilyClass 1', "'I' foot/stem width ratio 2.41"],
+      },
+      cjk_style: {
+        confidence: 0,
+        signals: [],
+      },
+      style_attributes: {
+        weight: 400,
+        width: 5,
+        slope: 'Upright',
+        os2_weight: 400,
+        os2_width: 5,
+        os2_slope: 'Upright',
+        fs_selection: 64,
+        conflicts: [],
+      },
+      variation_axes: [],
+      monospace: {
+        post_fixed_pitch: false,
+        ascii_fixed_pitch: false,
+        is_monospace: false,
+        terminal: { box_drawing: 0, block_elements: 0, powerline: false, nerd_font_icons: 0, is_nerd_font: false, terminal_ready: false },
+      },
     },
     {
       id: '4',
@@ -76,6 +258,7 @@ const MOCK_FONT_STATE: FontState = {
       postscript_name: 'CourierNewPSMT',
       style: 'Regular',
       path: 'C:\\Windows\\Fonts\\cour.ttf',
+      face_index: 0,
       fileSize: 423456,
       format: 'TrueType',
       is_variable: false,
@@ -88,7 +271,67 @@ const MOCK_FONT_STATE: FontState = {
       },
       status: 'Enabled',
       created_at: Date.now(),
+      localized_names: { 'en-US': { family: 'Courier New' } },
       css_font_family: 'Courier New',
+      name_repairs: [],
+      health: { issues: [] },
+      lint_warnings: [],
+      rendering: {
+        outline_format: 'TrueType',
+        has_fpgm: true,
+        has_prep: true,
+        has_cvt: true,
+        hinted: true,
+        autohinted: false,
+        has_gasp: true,
+        has_embedded_bitmaps: false,
+      },
+      bitmap_strikes: [],
+      color: { technologies: [], palette_count: 0, is_emoji: false },
+      ligatures: { has_programming_ligatures: false, sequences: [] },
+      features: ['kern', 'liga'],
+      vertical: {
+        has_vhea: false,
+        has_vmtx: false,
+        has_vorg: false,
+        needs_vorg: false,
+        has_vert: false,
+        has_vrt2: false,
+        vertical_ready: false,
+        missing: ['vhea', 'vmtx', 'vert/vrt2'],
+      },
+      math: {
+        has_math_table: false,
+        constants: [],
+        blocks: [],
+      },
+      classification: {
+        category: 'Monospace',
+        confidence: 1.0,
+        signals: ['ASCII advance widths are equal'],
+      },
+      cjk_style: {
+        confidence: 0,
+        signals: [],
+      },
+      style_attributes: {
+        weight: 400,
+        width: 5,
+        slope: 'Upright',
+        os2_weight: 400,
+        os2_width: 5,
+        os2_slope: 'Upright',
+        fs_selection: 64,
+        conflicts: [],
+      },
+      variation_axes: [],
+      monospace: {
+        post_fixed_pitch: true,
+        ascii_fixed_pitch: true,
+        advance_width: 1229,
+        is_monospace: true,
+        terminal: { box_drawing: 128, block_elements: 32, powerline: false, nerd_font_icons: 0, is_nerd_font: false, terminal_ready: true },
+      },
     },
     {
       id: '5',
@@ -97,6 +340,7 @@ const MOCK_FONT_STATE: FontState = {
       postscript_name: 'Verdana',
       style: 'Regular',
       path: 'C:\\Windows\\Fonts\\verdana.ttf',
+      face_index: 0,
       fileSize: 753664,
       format: 'TrueType',
       is_variable: false,
@@ -109,7 +353,66 @@ const MOCK_FONT_STATE: FontState = {
       },
       status: 'Enabled',
       created_at: Date.now(),
+      localized_names: { 'en-US': { family: 'Verdana' } },
       css_font_family: 'Verdana',
+      name_repairs: [],
+      health: { issues: [] },
+      lint_warnings: [],
+      rendering: {
+        outline_format: 'TrueType',
+        has_fpgm: true,
+        has_prep: true,
+        has_cvt: true,
+        hinted: true,
+        autohinted: false,
+        has_gasp: true,
+        has_embedded_bitmaps: false,
+      },
+      bitmap_strikes: [],
+      color: { technologies: [], palette_count: 0, is_emoji: false },
+      ligatures: { has_programming_ligatures: false, sequences: [] },
+      features: ['kern', 'liga'],
+      vertical: {
+        has_vhea: false,
+        has_vmtx: false,
+        has_vorg: false,
+        needs_vorg: false,
+        has_vert: false,
+        has_vrt2: false,
+        vertical_ready: false,
+        missing: ['vhea', 'vmtx', 'vert/vrt2'],
+      },
+      math: {
+        has_math_table: false,
+        constants: [],
+        blocks: [],
+      },
+      classification: {
+        category: 'SansSerif',
+        confidence: 0.95,
+        signals: ['PANOSE family type 2, serif style 11', 'sFamilyClass 8', "'I' foot/stem width ratio 1.00"],
+      },
+      cjk_style: {
+        confidence: 0,
+        signals: [],
+      },
+      style_attributes: {
+        weight: 400,
+        width: 5,
+        slope: 'Upright',
+        os2_weight: 400,
+        os2_width: 5,
+        os2_slope: 'Upright',
+        fs_selection: 64,
+        conflicts: [],
+      },
+      variation_axes: [],
+      monospace: {
+        post_fixed_pitch: false,
+        ascii_fixed_pitch: false,
+        is_monospace: false,
+        terminal: { box_drawing: 0, block_elements: 0, powerline: false, nerd_font_icons: 0, is_nerd_font: false, terminal_ready: false },
+      },
     },
     {
       id: '6',
@@ -118,6 +421,7 @@ const MOCK_FONT_STATE: FontState = {
       postscript_name: 'Georgia',
       style: 'Regular',
       path: 'C:\\Windows\\Fonts\\georgia.ttf',
+      face_index: 0,
       fileSize: 567808,
       format: 'TrueType',
       is_variable: false,
@@ -130,17 +434,143 @@ const MOCK_FONT_STATE: FontState = {
       },
       status: 'Enabled',
       created_at: Date.now(),
+      localized_names: { 'en-US': { family: 'Georgia' } },
       css_font_family: 'Georgia',
+      name_repairs: [],
+      health: { issues: [] },
+      lint_warnings: [],
+      rendering: {
+        outline_format: 'TrueType',
+        has_fpgm: true,
+        has_prep: true,
+        has_cvt: true,
+        hinted: true,
+        autohinted: false,
+        has_gasp: true,
+        has_embedded_bitmaps: false,
+      },
+      bitmap_strikes: [],
+      color: { technologies: [], palette_count: 0, is_emoji: false },
+      ligatures: { has_programming_ligatures: false, sequences: [] },
+      features: ['kern', 'liga'],
+      vertical: {
+        has_vhea: false,
+        has_vmtx: false,
+        has_vorg: false,
+        needs_vorg: false,
+        has_vert: false,
+        has_vrt2: false,
+        vertical_ready: false,
+        missing: ['vhea', 'vmtx', 'vert/vrt2'],
+      },
+      math: {
+        has_math_table: false,
+        constants: [],
+        blocks: [],
+      },
+      classification: {
+        category: 'Serif',
+        confidence: 0.95,
+        signals: ['PANOSE family type 2, serif style 4', 'sFamilyClass 1', "'I' foot/stem width ratio 2.20"],
+      },
+      cjk_style: {
+        confidence: 0,
+        signals: [],
+      },
+      style_attributes: {
+        weight: 400,
+        width: 5,
+        slope: 'Upright',
+        os2_weight: 400,
+        os2_width: 5,
+        os2_slope: 'Upright',
+        fs_selection: 64,
+        conflicts: [],
+      },
+      variation_axes: [],
+      monospace: {
+        post_fixed_pitch: false,
+        ascii_fixed_pitch: false,
+        is_monospace: false,
+        terminal: { box_drawing: 0, block_elements: 0, powerline: false, nerd_font_icons: 0, is_nerd_font: false, terminal_ready: false },
+      },
     },
   ],
   css_font_families: [
-    { name: 'Arial', font_count: 1, default_font_id: '1' },
-    { name: 'Courier New', font_count: 1, default_font_id: '4' },
-    { name: 'Georgia', font_count: 1, default_font_id: '6' },
-    { name: 'Microsoft YaHei', font_count: 1, default_font_id: '2' },
-    { name: 'Times New Roman', font_count: 1, default_font_id: '3' },
-    { name: 'Verdana', font_count: 1, default_font_id: '5' },
+    {
+      name: 'Arial',
+      font_count: 1,
+      default_font_id: '1',
+      category: 'SansSerif',
+      category_confidence: 0.95,
+      styles: [{ font_id: '1', style: 'Regular', weight: 400, width: 5, slope: 'Upright' }],
+      ribbi: { regular: '1' },
+      weights: [400],
+      widths: [5],
+      slopes: ['Upright'],
+    },
+    {
+      name: 'Courier New',
+      font_count: 1,
+      default_font_id: '4',
+      category: 'Monospace',
+      category_confidence: 1,
+      styles: [{ font_id: '4', style: 'Regular', weight: 400, width: 5, slope: 'Upright' }],
+      ribbi: { regular: '4' },
+      weights: [400],
+      widths: [5],
+      slopes: ['Upright'],
+    },
+    {
+      name: 'Georgia',
+      font_count: 1,
+      default_font_id: '6',
+      category: 'Serif',
+      category_confidence: 0.95,
+      styles: [{ font_id: '6', style: 'Regular', weight: 400, width: 5, slope: 'Upright' }],
+      ribbi: { regular: '6' },
+      weights: [400],
+      widths: [5],
+      slopes: ['Upright'],
+    },
+    {
+      name: 'Microsoft YaHei',
+      font_count: 1,
+      default_font_id: '2',
+      category: 'SansSerif',
+      category_confidence: 0.4,
+      styles: [{ font_id: '2', style: 'Regular', weight: 400, width: 5, slope: 'Upright' }],
+      ribbi: { regular: '2' },
+      weights: [400],
+      widths: [5],
+      slopes: ['Upright'],
+    },
+    {
+      name: 'Times New Roman',
+      font_count: 1,
+      default_font_id: '3',
+      category: 'Serif',
+      category_confidence: 1,
+      styles: [{ font_id: '3', style: 'Regular', weight: 400, width: 5, slope: 'Upright' }],
+      ribbi: { regular: '3' },
+      weights: [400],
+      widths: [5],
+      slopes: ['Upright'],
+    },
+    {
+      name: 'Verdana',
+      font_count: 1,
+      default_font_id: '5',
+      category: 'SansSerif',
+      category_confidence: 0.95,
+      styles: [{ font_id: '5', style: 'Regular', weight: 400, width: 5, slope: 'Upright' }],
+      ribbi: { regular: '5' },
+      weights: [400],
+      widths: [5],
+      slopes: ['Upright'],
+    },
   ],
+  grouping: 'Typographic',
 };
 
 const USE_MOCK = false; // Toggle for development
@@ -202,4 +632,203 @@ export async function checkGlyphsInFont(fontPath: string, text: string): Promise
     console.error('Failed to check glyphs in font:', error);
     throw error;
   }
-}
\ No newline at end of file
+}
+
+export async function getNameTable(fontId: string): Promise<NameRecord[]> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    const font = MOCK_FONT_STATE.fonts.find(f => f.id === fontId);
+    return font
+      ? [{ platform_id: 3, encoding_id: 1, language_id: 0x0409, language: 'en-US', name_id: 1, value: font.family, raw_hex: '' }]
+      : [];
+  }
+
+  try {
+    return await invoke<NameRecord[]>('get_name_table', { fontId });
+  } catch (error) {
+    console.error('Failed to read name table:', error);
+    throw error;
+  }
+}
+
+export async function inspectFontTables(fontId: string): Promise<FontTables> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    return { tables: [] };
+  }
+
+  try {
+    return await invoke<FontTables>('inspect_font_tables', { fontId });
+  } catch (error) {
+    console.error('Failed to inspect font tables:', error);
+    throw error;
+  }
+}
+
+/// Lint warnings of every font that has any, keyed by font ID
+export async function lintFonts(): Promise<Record<string, LintWarning[]>> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    return {};
+  }
+
+  try {
+    return await invoke<Record<string, LintWarning[]>>('lint_fonts');
+  } catch (error) {
+    console.error('Failed to lint fonts:', error);
+    throw error;
+  }
+}
+
+export async function getBitmapGlyphs(fontId: string, text: string, ppem: number): Promise<BitmapGlyphResult[]> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    return Array.from(text).map(glyph => ({ glyph }));
+  }
+
+  try {
+    return await invoke<BitmapGlyphResult[]>('get_bitmap_glyphs', { fontId, text, ppem });
+  } catch (error) {
+    console.error('Failed to get bitmap glyphs:', error);
+    throw error;
+  }
+}
+
+export async function getColorPalettes(fontId: string): Promise<ColorPalette[]> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    return [];
+  }
+
+  try {
+    return await invoke<ColorPalette[]>('get_color_palettes', { fontId });
+  } catch (error) {
+    console.error('Failed to get color palettes:', error);
+    throw error;
+  }
+}
+
+export async function getLayoutFeatures(fontId: string): Promise<LayoutFeatures> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    return {
+      features: [
+        { tag: 'kern', name: 'Kerning', tables: ['Gpos'] },
+        { tag: 'liga', name: 'Standard Ligatures', tables: ['Gsub'] },
+      ],
+      language_systems: [{ script: 'latn', language: 'dflt', gsub: ['liga'], gpos: ['kern'] }],
+    };
+  }
+
+  try {
+    return await invoke<LayoutFeatures>('get_layout_features', { fontId });
+  } catch (error) {
+    console.error('Failed to get layout features:', error);
+    throw error;
+  }
+}
+
+export async function setFamilyGrouping(grouping: FamilyGrouping): Promise<FontState> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    return { ...MOCK_FONT_STATE, grouping };
+  }
+
+  try {
+    return await invoke<FontState>('set_family_grouping', { grouping });
+  } catch (error) {
+    console.error('Failed to set family grouping:', error);
+    throw error;
+  }
+}
+
+export async function matchFontFace(family: string, weight: string, style: string, stretch: string): Promise<FontMatch | null> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    const font = MOCK_FONT_STATE.fonts.find(f => f.css_font_family === family);
+    return font ? {
+      font_id: font.id,
+      family,
+      weight: font.weight,
+      stretch: 100,
+      style: 'Normal',
+      variations: [],
+      synthetic_bold: Number(weight) >= 600 && font.weight < 600,
+      synthetic_italic: style !== 'normal',
+    } : null;
+  }
+
+  try {
+    return await invoke<FontMatch | null>('match_font_face', { family, weight, style, stretch });
+  } catch (error) {
+    console.error('Failed to match font face:', error);
+    throw error;
+  }
+}
+
+export async function auditStylesheetFonts(path: string, sample: string): Promise<StylesheetAudit> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    return {
+      path,
+      stacks: [{
+        families: ['Brand Sans', 'Arial', 'sans-serif'],
+        selectors: ['body'],
+        missing: ['Brand Sans'],
+        contexts: [{
+          context: { weight: 'normal', style: 'normal', stretch: 'normal' },
+          matched: { font_id: '1', family: 'Arial', weight: 400, stretch: 100, style: 'Normal', variations: [], synthetic_bold: false, synthetic_italic: false },
+          fallbacks: [...sample].filter(c => c.trim() && c.charCodeAt(0) > 0x2e80).map(character => ({ character })),
+        }],
+      }],
+      web_fonts: [],
+    };
+  }
+
+  try {
+    return await invoke<StylesheetAudit>('audit_stylesheet_fonts', { path, sample });
+  } catch (error) {
+    console.error('Failed to audit stylesheet:', error);
+    throw error;
+  }
+}
+
+export async function getFontFaceCss(family: string): Promise<string> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 300));
+    return MOCK_FONT_STATE.fonts
+      .filter(f => f.css_font_family === family)
+      .map(f => `/* ${f.full_name} */\n@font-face {\n  font-family: "${family}";\n  src: local("${f.full_name}"), local("${f.postscript_name}");\n  font-weight: ${f.weight};\n}\n`)
+      .join('\n');
+  }
+
+  try {
+    return await invoke<string>('get_font_face_css', { family });
+  } catch (error) {
+    console.error('Failed to generate @font-face CSS:', error);
+    throw error;
+  }
+}
+
+export async function subsetFont(fontId: string, options: SubsetOptions, outputPath?: string): Promise<SubsetReport> {
+  if (USE_MOCK) {
+    await new Promise(resolve => setTimeout(resolve, 500));
+    const codepoints = new Set([...options.text].filter(c => c.trim()));
+    return {
+      output_path: outputPath || `C:\\Users\\me\\Downloads\\${MOCK_FONT_STATE.fonts.find(f => f.id === fontId)?.postscript_name ?? 'Font'}-subset.ttf`,
+      original_size: 20_000_000,
+      subset_size: 40_000 + codepoints.size * 1_200,
+      codepoint_count: codepoints.size,
+      glyph_count: codepoints.size + 1,
+      missing: [],
+      dropped_tables: options.drop_hinting ? ['DSIG', 'cvt ', 'fpgm', 'prep'] : ['DSIG'],
+    };
+  }
+
+  try {
+    return await invoke<SubsetReport>('subset_font', { fontId, options, outputPath: outputPath || null });
+  } catch (error) {
+    console.error('Failed to subset font:', error);
+    throw error;
+  }
+}
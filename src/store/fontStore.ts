@@ -24,10 +24,19 @@ export const useFontStore = create<FontStore>((set, get) => ({
         fonts: state.fontState?.fonts.map((font) =>
           font.id === fontId ? { ...font, status } : font
         ) || [],
-        css_font_families: state.fontState?.css_font_families || []
+        css_font_families: state.fontState?.css_font_families || [],
+        grouping: state.fontState?.grouping || 'Typographic',
       } as FontState,
     })),
   getFontById: (fontId) => get().fontState?.fonts.find((f) => f.id === fontId),
-  getFontsByCssFamily: (familyName) =>
-    get().fontState?.fonts.filter((f) => f.css_font_family === familyName) || [],
+  // Members come from the family's style matrix so every grouping mode resolves the same way
+  getFontsByCssFamily: (familyName) => {
+    const fontState = get().fontState;
+    const family = fontState?.css_font_families.find((fm) => fm.name === familyName);
+    if (!fontState || !family) {
+      return [];
+    }
+    const ids = new Set(family.styles.map((s) => s.font_id));
+    return fontState.fonts.filter((f) => ids.has(f.id));
+  },
 }));
@@ -1,3 +1,4 @@
+import { CjkStyle, ColorTrait, FontCategory, LintRule, RenderingTrait, TerminalTrait } from '@/types/font';
 import { create } from 'zustand';
 
 type ViewMode = 'grid' | 'list';
@@ -19,12 +20,68 @@ interface UIStore {
   selectAll: (fontIds: string[]) => void;
   language: string;
   setLanguage: (lang: string) => void;
+  localeOrder: string[]; // 显示名称的语言优先级 (BCP 47)
+  setLocaleOrder: (order: string[]) => void;
   filters: {
     languages: string[];
     tags: string[];
     searchText: string;
+    lintRules?: LintRule[]; // 只显示含这些元数据警告的字体家族
+    rendering?: RenderingTrait[]; // 默认字体需满足所有选中的渲染属性
+    color?: ColorTrait[]; // 彩色 / emoji 字体
+    terminal?: TerminalTrait[]; // 默认字体需满足所有选中的等宽/终端属性
+    features?: string[]; // 默认字体需含所有选中的 OpenType 特性标签
+    categories?: FontCategory[]; // 字体家族分类 (衬线/无衬线/手写/展示/等宽)
+    cjkStyles?: CjkStyle[]; // 默认字体的 CJK 风格 (宋/黑/楷/仿宋/圆)
   };
-  setFilters: (filters: { languages: string[]; tags: string[]; searchText: string }) => void;
+  setFilters: (filters: {
+    languages: string[];
+    tags: string[];
+    searchText: string;
+    lintRules?: LintRule[];
+    rendering?: RenderingTrait[];
+    color?: ColorTrait[];
+    terminal?: TerminalTrait[];
+    features?: string[];
+    categories?: FontCategory[];
+    cjkStyles?: CjkStyle[];
+  }) => void;
+}
+
+const CJK_LOCALES = ['zh-CN', 'zh-TW', 'ja-JP', 'ko-KR'];
+const LOCALE_ORDER_KEY = 'fontscape.localeOrder';
+
+/**
+ * Initial display name locale order from the system language: its CJK locale first for
+ * CJK users, English first for everyone else
+ */
+export function defaultLocaleOrder(language: string): string[] {
+  const tag = language.toLowerCase();
+  const traditional = /^zh-(tw|hk|mo|hant)/.test(tag);
+  const preferred = tag.startsWith('zh')
+    ? (traditional ? 'zh-TW' : 'zh-CN')
+    : CJK_LOCALES.find((locale) => locale.split('-')[0] === tag.split('-')[0]);
+  if (!preferred) {
+    return ['en-US', ...CJK_LOCALES];
+  }
+  return [preferred, ...CJK_LOCALES.filter((locale) => locale !== preferred), 'en-US'];
+}
+
+export function systemLocaleOrder(): string[] {
+  return defaultLocaleOrder(typeof navigator === 'undefined' ? 'en-US' : navigator.language);
+}
+
+/** The order saved in Settings, or the system language's default */
+function loadLocaleOrder(): string[] {
+  try {
+    const saved = JSON.parse(localStorage.getItem(LOCALE_ORDER_KEY) ?? 'null');
+    if (Array.isArray(saved) && saved.length > 0 && saved.every((locale) => typeof locale === 'string')) {
+      return saved;
+    }
+  } catch {
+    // No storage or a corrupt entry: fall back to the system language
+  }
+  return systemLocaleOrder();
 }
 
 export const useUIStore = create<UIStore>((set) => ({
@@ -55,6 +112,15 @@ export const useUIStore = create<UIStore>((set) => ({
   selectAll: (fontIds) => set({ selectedFontIds: new Set(fontIds) }),
   language: 'zh-CN',
   setLanguage: (lang) => set({ language: lang }),
+  localeOrder: loadLocaleOrder(),
+  setLocaleOrder: (order) => {
+    try {
+      localStorage.setItem(LOCALE_ORDER_KEY, JSON.stringify(order));
+    } catch {
+      // Keep the order for this session only
+    }
+    set({ localeOrder: order });
+  },
   filters: { languages: [], tags: [], searchText: '' },
   setFilters: (f) => set({ filters: f }),
 }));
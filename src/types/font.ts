@@ -6,6 +6,7 @@ export interface FontInfo {
   postscript_name: string;
   style: string;
   path: string;
+  face_index: number;          // TTC/OTC 中的字体索引，单字体文件为 0
   fileSize: number;
   format: FontFormat;
   is_variable: boolean;
@@ -15,11 +16,532 @@ export interface FontInfo {
   metadata: FontMetadata;
   status: FontStatus;
   created_at: number;
-  // Localized names
-  family_zh?: string;              // Chinese (PRC) family name
-  full_name_zh?: string;           // Chinese (PRC) full name
+  // Localized names - key 为 BCP 47 语言标签 (如 "zh-CN", "ja-JP")
+  localized_names: Record<string, LocalizedNames>;
   // CSS font-family 名称 - 浏览器匹配优先级: ID 16 > ID 1 > ID 21
   css_font_family: string;         // 优先 Name ID 16 (Typographic Family)，回退到 ID 1
+  // OS/2 ulUnicodeRange / ulCodePageRange 与 cmap 实际覆盖的交叉检查
+  os2_coverage?: Os2Coverage;
+  // 规范化/乱码修复改动过的 name 记录
+  name_repairs: NameRepair[];
+  // 结构完整性检查 (表校验和、边界/重叠、loca/glyf、cmap)
+  health: FontHealth;
+  // 元数据一致性检查 (字重、italic/bold 位、PostScript 名称)
+  lint_warnings: LintWarning[];
+  // 轮廓格式 (glyf/CFF/CFF2)、hinting 程序、gasp、内嵌位图
+  rendering: RenderingInfo;
+  // 内嵌位图 strike (尺寸与图像格式)，小字号下会替代轮廓渲染
+  bitmap_strikes: BitmapStrike[];
+  // 彩色字体技术 (COLR/CPAL, SVG, sbix, CBDT) 与 emoji 判定
+  color: ColorInfo;
+  // 等宽检测 (post.isFixedPitch 与 ASCII advance 实测) 及终端适用性
+  monospace: MonospaceInfo;
+  // GSUB calt/liga/dlig 替换的编程连字序列
+  ligatures: LigatureInfo;
+  // GSUB/GPOS 特性标签 (去重排序)，按脚本/语言系统的完整列表由 getLayoutFeatures 提供
+  features: string[];
+  // 竖排支持 (vhea/vmtx、CFF 的 VORG、GSUB vert/vrt2)
+  vertical: VerticalInfo;
+  math: MathInfo;
+  classification: FontClassification;
+  cjk_style: CjkStyleInfo;
+  style_attributes: StyleAttributes;
+  // fvar 变体轴，静态字体为空
+  variation_axes: VariationAxis[];
+}
+
+/// font-weight / font-style / font-stretch as written in a stylesheet
+export interface FontContext {
+  weight: string;
+  style: string;
+  stretch: string;
+}
+
+/// Sample character the primary face lacks
+export interface CharFallback {
+  character: string;
+  family?: string;   // 覆盖该字符的后续栈条目；为空表示交给系统回退
+  font_id?: string;
+}
+
+/// How one weight/style context of a font stack resolves
+export interface ContextAudit {
+  context: FontContext;
+  matched?: FontMatch;         // 第一个本机已安装条目命中的字体
+  fallbacks: CharFallback[];   // 主字体缺字的示例字符
+  error?: string;              // 无法解析的字重/样式/宽度值
+}
+
+export interface FontStackAudit {
+  families: string[];
+  selectors: string[];
+  missing: string[];           // 本机未安装的条目 (不含通用家族和 @font-face 网络字体)
+  contexts: ContextAudit[];
+}
+
+/// Font stacks of a CSS/HTML file resolved against the installed fonts
+export interface StylesheetAudit {
+  path: string;
+  stacks: FontStackAudit[];
+  web_fonts: string[];         // @font-face 声明的家族
+}
+
+/// What to keep when subsetting a face for web delivery
+export interface SubsetOptions {
+  text: string;                 // 需要保留的文本语料
+  unicode_ranges: string;       // 额外保留的 Unicode 范围，如 "U+0000-00FF, U+4E00-9FFF"
+  drop_hinting: boolean;        // 去掉 TrueType 指令及 fpgm/prep/cvt 等表
+  drop_unused_tables: boolean;  // 只保留网页渲染需要的表，并去掉 post 中的字形名
+}
+
+/// Summary of a written subset font
+export interface SubsetReport {
+  output_path: string;
+  original_size: number;
+  subset_size: number;
+  codepoint_count: number;      // 新 cmap 中的字符数
+  glyph_count: number;          // 保留的字形数，含布局闭包和复合字形部件
+  missing: string[];            // 文本中字体不支持的字符
+  dropped_tables: string[];
+}
+
+/// A variation axis from the fvar table
+export interface VariationAxis {
+  tag: string;  // 轴标签，如 "wght"
+  min: number;
+  default: number;
+  max: number;
+}
+
+/// Requested or matched font-style; Oblique carries the angle in degrees
+export type CssFontStyle = 'Normal' | 'Italic' | { Oblique: number };
+
+/// Variation axis value the browser applies to the matched face
+export interface AxisSetting {
+  tag: string;
+  value: number;
+}
+
+/// Face a browser uses for a font-family / weight / style / stretch combination
+export interface FontMatch {
+  font_id: string;
+  family: string;               // 命中的 font-family 列表项
+  weight: number;               // 字体实际字重；可变字体为夹取到 wght 范围内的值
+  stretch: number;              // 字体实际宽度百分比
+  style: CssFontStyle;          // 字体实际提供的样式
+  variations: AxisSetting[];    // 浏览器为可变字体设置的轴值
+  synthetic_bold: boolean;      // 浏览器合成粗体
+  synthetic_italic: boolean;    // 浏览器合成斜体
+}
+
+/// Tables and features needed for vertical CJK layout
+export interface VerticalInfo {
+  has_vhea: boolean;
+  has_vmtx: boolean;
+  has_vorg: boolean;        // CFF 字形的垂直原点
+  needs_vorg: boolean;      // CFF/CFF2 轮廓
+  has_vert: boolean;
+  has_vrt2: boolean;
+  vertical_ready: boolean;  // vhea+vmtx、vert/vrt2 齐全，CFF 字体另需 VORG
+  missing: string[];        // 缺少的表或特性
+}
+
+export type FontCategory = 'SansSerif' | 'Serif' | 'Script' | 'Display' | 'Monospace' | 'Unknown';
+
+/// Category of a face with the evidence it was derived from
+export interface FontClassification {
+  category: FontCategory;
+  confidence: number;  // 0-1：信号一致程度 × 信号强度
+  signals: string[];   // 参与判断的依据 (PANOSE、sFamilyClass、名称关键字、字形测量)
+}
+
+export type FontSlope = 'Upright' | 'Italic' | 'Oblique';
+
+/// Weight, width and slope inferred from style names, next to the OS/2 values
+export interface StyleAttributes {
+  weight: number;            // 协调后的字重
+  width: number;             // 协调后的宽度等级 1-9 (usWidthClass 尺度)
+  slope: FontSlope;
+  name_weight?: number;      // 由样式名/本地化名称推断 (如 "SemiBold"、"W6"、"中黑")；Regular 等不算
+  name_width?: number;
+  name_slope?: FontSlope;
+  os2_weight?: number;       // OS/2 usWeightClass
+  os2_width?: number;        // OS/2 usWidthClass
+  os2_slope?: FontSlope;     // fsSelection ITALIC/OBLIQUE 位
+  fs_selection?: number;     // OS/2 fsSelection 原值，BOLD/ITALIC/REGULAR 位用于 RIBBI 链接
+  conflicts: string[];       // 样式名与 OS/2 不一致之处
+}
+
+export type CjkStyle = 'Song' | 'Hei' | 'Kai' | 'Fangsong' | 'Yuan';
+
+/// CJK style of a face; style is absent for faces without ideographs or without evidence
+export interface CjkStyleInfo {
+  style?: CjkStyle;    // 宋体 / 黑体 / 楷体 / 仿宋 / 圆体
+  confidence: number;
+  signals: string[];   // 名称关键字与笔画测量依据
+}
+
+/// OpenType MATH support of a face
+export interface MathInfo {
+  has_math_table: boolean;       // 含 MATH 表，可用于 OpenType 数学排版
+  constants: MathConstant[];     // MathConstants，按规范顺序
+  blocks: MathBlockCoverage[];   // 数学字母数字符号与运算符区段覆盖
+}
+
+export interface MathConstant {
+  name: string;   // 如 "AxisHeight"
+  value: number;  // font units；ScalePercent 类常量为百分比
+}
+
+export interface MathBlockCoverage {
+  name: string;
+  covered: number;
+  total: number;
+}
+
+export type LayoutTableKind = 'Gsub' | 'Gpos';
+
+/// A feature tag with its registered name and the font's own UI label
+export interface LayoutFeature {
+  tag: string;
+  name?: string;     // 注册特性的友好名称 (如 "Small Capitals")
+  ui_name?: string;  // ssXX/cvXX 的字体自定义名称
+  tables: LayoutTableKind[];
+}
+
+/// Features available to one script and language system
+export interface LanguageSystemFeatures {
+  script: string;    // OpenType script 标签 (如 "latn", "hani")
+  language: string;  // OpenType language 标签，默认语言系统为 "dflt"
+  gsub: string[];
+  gpos: string[];
+}
+
+export interface LayoutFeatures {
+  features: LayoutFeature[];
+  language_systems: LanguageSystemFeatures[];
+}
+
+/// Coding ligatures substituted by a face
+export interface LigatureInfo {
+  has_programming_ligatures: boolean;
+  sequences: string[];  // 被 calt/liga/dlig 替换的编程符号序列 (如 "->", "!=")
+}
+
+/// Fixed-pitch flags and measured advance widths of a face
+export interface MonospaceInfo {
+  post_fixed_pitch: boolean;   // post.isFixedPitch 非 0
+  ascii_fixed_pitch: boolean;  // 可打印 ASCII 全部映射且 advance width 相同
+  advance_width?: number;      // 等宽时的 advance width (font units)
+  is_monospace: boolean;       // 以实测为准；不含完整 ASCII 时回退到 post.isFixedPitch
+  terminal: TerminalSupport;
+}
+
+/// Glyph coverage relevant to terminal emulators and shell prompts
+export interface TerminalSupport {
+  box_drawing: number;     // U+2500-257F 覆盖字符数，满覆盖为 128
+  block_elements: number;  // U+2580-259F 覆盖字符数，满覆盖为 32
+  powerline: boolean;      // Powerline 核心符号
+  nerd_font_icons: number; // Nerd Fonts 私用区图标数
+  is_nerd_font: boolean; // Powerline 加至少 3 组 Nerd 图标，私用区无其他大量字符
+  terminal_ready: boolean; // 等宽且完整覆盖制表符与方块元素
+}
+
+/// Coding/terminal filters offered in the sidebar
+export type TerminalTrait = 'Monospace' | 'Ligatures' | 'TerminalReady' | 'Powerline' | 'NerdFont';
+
+export type ColorTechnology = 'ColrV0' | 'ColrV1' | 'Svg' | 'Sbix' | 'Cbdt';
+
+/// Color capabilities of a face
+export interface ColorInfo {
+  technologies: ColorTechnology[];
+  palette_count: number;  // CPAL 调色板数量，无 CPAL 为 0
+  is_emoji: boolean;      // 彩色字体且覆盖常用 emoji 区段
+}
+
+/// A CPAL palette with colors converted to RGBA
+export interface ColorPalette {
+  index: number;
+  colors: [number, number, number, number][];  // RGBA
+  usable_with_light_background: boolean;
+  usable_with_dark_background: boolean;
+  label?: string;
+}
+
+/// Color filters offered in the sidebar
+export type ColorTrait = 'Color' | 'Emoji';
+
+export type BitmapSource = 'Eblc' | 'Cblc' | 'Bloc' | 'Sbix';
+
+/// A bitmap strike: the glyph images for one pixel size
+export interface BitmapStrike {
+  source: BitmapSource;
+  ppem_x: number;
+  ppem_y: number;
+  bit_depth?: number;   // EBLC/CBLC: 1/2/4/8/32
+  first_glyph?: number;
+  last_glyph?: number;
+  formats: string[];    // EBLC/CBLC imageFormat 编号；sbix 图形类型 (如 "png")
+}
+
+/// A decoded bitmap glyph image
+export interface BitmapGlyph {
+  strike_ppem: number;  // 实际使用的 strike 尺寸
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  format: 'Png' | 'Gray' | 'Rgba';  // Gray: 每像素 1 字节覆盖度；Rgba: 非预乘
+  data: number[];
+}
+
+export interface BitmapGlyphResult {
+  glyph: string;
+  bitmap?: BitmapGlyph;  // 该 ppem 下没有位图时为空 (使用轮廓渲染)
+}
+
+export type OutlineFormat = 'TrueType' | 'Cff' | 'Cff2' | 'None';
+
+/// Rasterization-related attributes of a face
+export interface RenderingInfo {
+  outline_format: OutlineFormat;
+  has_fpgm: boolean;
+  has_prep: boolean;
+  has_cvt: boolean;
+  hinted: boolean;               // 含 TrueType 指令 (fpgm/prep 或字形指令)；仅有 cvt 不算
+  autohinted: boolean;           // 版本字符串含 ttfautohint 签名
+  has_gasp: boolean;
+  has_embedded_bitmaps: boolean; // EBDT/EBLC, CBDT/CBLC, sbix
+}
+
+/// Rendering filters offered in the sidebar
+export type RenderingTrait = 'TrueType' | 'Cff' | 'Hinted' | 'Autohinted' | 'Gasp' | 'EmbeddedBitmaps' | 'VerticalWriting' | 'Math';
+
+export type LintRule =
+  | 'WeightClassOutOfRange'
+  | 'WeightNameMismatch'
+  | 'ItalicBitMissing'
+  | 'ItalicBitUnexpected'
+  | 'BoldBitMismatch'
+  | 'InvalidPostScriptName'
+  | 'DuplicatePostScriptName';
+
+export interface LintWarning {
+  rule: LintRule;
+  severity: Severity;
+  message: string;
+}
+
+export type Severity = 'Info' | 'Warning' | 'Error';
+
+export type HealthCheck =
+  | 'TableChecksum'
+  | 'ChecksumAdjustment'
+  | 'TableBounds'
+  | 'TableOverlap'
+  | 'LocaGlyf'
+  | 'Cmap';
+
+export interface HealthIssue {
+  severity: Severity;
+  check: HealthCheck;
+  table?: string;
+  message: string;
+}
+
+/// Per-font health report
+export interface FontHealth {
+  worst?: Severity;  // 最严重的问题级别，无问题为空
+  issues: HealthIssue[];
+}
+
+export type NameFix =
+  | 'RepairedLegacyEncoding'
+  | 'RepairedUtf8'
+  | 'TrimmedWhitespace'
+  | 'StrippedControlChars'
+  | 'NarrowedFullWidth'
+  | 'NfcNormalized';
+
+/// A name record whose decoded value was altered by normalization
+export interface NameRepair {
+  name_id: number;
+  platform_id: number;
+  encoding_id: number;
+  language_id: number;
+  language?: string;  // BCP 47
+  raw: string;        // 规范化前的原始值
+  value: string;      // 规范化后的值
+  fixes: NameFix[];
+}
+
+/// A single raw name table record
+export interface NameRecord {
+  platform_id: number;
+  encoding_id: number;
+  language_id: number;
+  language?: string;   // BCP 47，未知语言为空
+  name_id: number;
+  value?: string;      // 解码结果 (未规范化)，不支持的编码为空
+  raw_hex: string;     // 原始字节，十六进制
+}
+
+/// An entry of the sfnt table directory
+export interface TableRecord {
+  tag: string;
+  offset: number;
+  length: number;
+  checksum: number;  // 表目录中记录的校验和
+}
+
+/// Table directory plus parsed views of common tables
+export interface FontTables {
+  tables: TableRecord[];
+  head?: HeadTable;
+  hhea?: HheaTable;
+  os2?: Os2Table;
+  post?: PostTable;
+  maxp?: MaxpTable;
+  fvar?: FvarTable;
+  gasp?: GaspTable;
+}
+
+export interface HeadTable {
+  version: number;
+  font_revision: number;
+  checksum_adjustment: number;
+  magic_number: number;
+  flags: number;
+  units_per_em: number;
+  created: number;   // 自 1904-01-01 起的秒数
+  modified: number;  // 自 1904-01-01 起的秒数
+  x_min: number;
+  y_min: number;
+  x_max: number;
+  y_max: number;
+  mac_style: number;
+  lowest_rec_ppem: number;
+  font_direction_hint: number;
+  index_to_loc_format: number;
+  glyph_data_format: number;
+}
+
+export interface HheaTable {
+  version: number;
+  ascender: number;
+  descender: number;
+  line_gap: number;
+  advance_width_max: number;
+  min_left_side_bearing: number;
+  min_right_side_bearing: number;
+  x_max_extent: number;
+  caret_slope_rise: number;
+  caret_slope_run: number;
+  caret_offset: number;
+  metric_data_format: number;
+  number_of_h_metrics: number;
+}
+
+export interface Os2Table {
+  version: number;
+  x_avg_char_width: number;
+  us_weight_class: number;
+  us_width_class: number;
+  fs_type: number;
+  y_subscript_x_size: number;
+  y_subscript_y_size: number;
+  y_subscript_x_offset: number;
+  y_subscript_y_offset: number;
+  y_superscript_x_size: number;
+  y_superscript_y_size: number;
+  y_superscript_x_offset: number;
+  y_superscript_y_offset: number;
+  y_strikeout_size: number;
+  y_strikeout_position: number;
+  s_family_class: number;
+  panose: number[];
+  ul_unicode_range: number[];
+  ach_vend_id: string;
+  fs_selection: number;
+  us_first_char_index: number;
+  us_last_char_index: number;
+  s_typo_ascender?: number;       // Apple 的 68 字节版本 0 表中没有
+  s_typo_descender?: number;
+  s_typo_line_gap?: number;
+  us_win_ascent?: number;
+  us_win_descent?: number;
+  ul_code_page_range?: number[];  // version >= 1
+  sx_height?: number;             // version >= 2
+  s_cap_height?: number;
+  us_default_char?: number;
+  us_break_char?: number;
+  us_max_context?: number;
+  us_lower_optical_point_size?: number;  // version >= 5
+  us_upper_optical_point_size?: number;
+}
+
+export interface PostTable {
+  version: number;
+  italic_angle: number;
+  underline_position: number;
+  underline_thickness: number;
+  is_fixed_pitch: number;
+  min_mem_type42: number;
+  max_mem_type42: number;
+  min_mem_type1: number;
+  max_mem_type1: number;
+}
+
+export interface MaxpTable {
+  version: number;
+  num_glyphs: number;
+  truetype?: Record<string, number>;  // 仅 version 1.0 (TrueType 轮廓)
+}
+
+export interface FvarTable {
+  axes: {
+    tag: string;
+    min_value: number;
+    default_value: number;
+    max_value: number;
+    flags: number;
+    name_id: number;
+    name?: string;
+  }[];
+  instances: {
+    subfamily_name_id: number;
+    name?: string;
+    coordinates: number[];
+    postscript_name_id?: number;
+  }[];
+}
+
+export interface GaspTable {
+  version: number;
+  ranges: { range_max_ppem: number; behavior: number }[];
+}
+
+/// Localized names for a single language
+export interface LocalizedNames {
+  family?: string;              // Name ID 1
+  full_name?: string;           // Name ID 4
+  typographic_family?: string;  // Name ID 16
+}
+
+/// OS/2 declared ranges compared against actual cmap coverage
+export interface Os2Coverage {
+  declared_unicode_ranges: number[];  // Unicode range bit (0-122)
+  declared_code_pages: number[];      // Code page bit (0-63)
+  mismatches: CoverageMismatch[];
+}
+
+export interface CoverageMismatch {
+  source: 'UnicodeRange' | 'CodePage';
+  kind: 'DeclaredButMissing' | 'PresentButUndeclared';
+  bit: number;
+  name: string;
+  covered: number;  // cmap 中落在该范围内的字符数 (code page 为命中的探测字符数)
+  total: number;    // 范围大小 (code page 为探测字符数)
 }
 
 export type FontFormat = 'TrueType' | 'OpenType' | 'TrueTypeCollection' | 'Woff' | 'Woff2';
@@ -30,12 +552,43 @@ export interface CssFontFamily {
   name: string;              // css_font_family 名称
   font_count: number;        // 该字体家族下的字体数量
   default_font_id: string;   // 默认字体ID (weight最接近400的字体)
+  category: FontCategory;    // 各字体分类按置信度投票的结果
+  category_confidence: number;
+  styles: FamilyStyle[];     // 样式矩阵 (宽度 × 字重 × 倾斜)
+  ribbi: RibbiMembers;       // 传统样式链接的 Regular/Italic/Bold/Bold Italic 成员
+  weights: number[];         // 可用字重
+  widths: number[];          // 可用宽度等级 1-9
+  slopes: FontSlope[];
+}
+
+/// One member of a family's style matrix
+export interface FamilyStyle {
+  font_id: string;
+  style: string;
+  weight: number;
+  width: number;
+  slope: FontSlope;
 }
 
+/// Font IDs of the RIBBI members
+export interface RibbiMembers {
+  regular?: string;
+  italic?: string;
+  bold?: string;
+  bold_italic?: string;
+}
+
+/// Which name groups faces into families
+export type FamilyGrouping =
+  | 'Typographic'  // ID 16 > ID 1：浏览器 CSS font-family 与设计软件
+  | 'Wws'          // ID 21 > ID 16 > ID 1：DirectWrite WWS 模型
+  | 'Legacy';      // ID 1：Windows GDI
+
 /// FontState - stores scanned fonts and aggregated CSS font family data
 export interface FontState {
   fonts: FontInfo[];
   css_font_families: CssFontFamily[];
+  grouping: FamilyGrouping;  // css_font_families 的分组方式
 }
 
 export interface FontMetadata {
@@ -60,6 +613,8 @@ export interface FontMetadata {
   compatible_full?: string;        // ID 18
   sample_text?: string;            // ID 19
   postscript_cid?: string;         // ID 20
+  wws_family?: string;             // ID 21
+  wws_subfamily?: string;          // ID 22
 }
 
 export interface FontCategory {